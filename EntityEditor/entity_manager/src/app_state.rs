@@ -0,0 +1,396 @@
+use std::collections::{BTreeMap, BTreeSet};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use super::dialect::TextEncoding;
+use super::key_conflicts::KeyConflict;
+use super::schema::{ColumnDef, ColumnRules, DataType, TableSchema};
+use super::dyn_entity::{DynEntity, DynRow};
+use super::storage::{
+    load_table, needs_schema_sidecar, ordered_keys, render_schema_sidecar, render_table, save_schema_sidecar, save_table, write_files, FileWrite,
+};
+use super::history::RowEdit;
+use super::children;
+
+
+/// 테이블 하나를 어디서 어떻게 읽을지에 대한 설정.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSpec {
+    pub name: String, // 내부 식별자 (예: "info")
+    #[serde(rename = "display_name")]
+    pub title: String, // 폼 제목 (예: "Info")
+    pub path: String, // 데이터 파일 경로
+    #[serde(rename = "key_column")]
+    pub key_hint: String, // 키 컬럼 힌트 (예: "CharacterUnique")
+    #[serde(default = "default_delimiter", deserialize_with = "ascii_delimiter")]
+    pub delimiter: char, // 필드 구분자 (예: ',' / '\t'). ASCII 한 글자만
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<TextEncoding>, // 파일 인코딩 (None이면 감지)
+    #[serde(rename = "parent_column", default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>, // 자식 테이블: 엔티티 키가 든 컬럼 (복합 키면 생략 시 첫 키 컬럼)
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+/// csv 크레이트는 1바이트 구분자만 쓰므로 프로젝트 파일을 읽을 때 거른다
+fn ascii_delimiter<'de, D: serde::Deserializer<'de>>(d: D) -> std::result::Result<char, D::Error> {
+    let c = char::deserialize(d)?;
+    if !c.is_ascii() {
+        return Err(serde::de::Error::custom(format!("구분자 '{}'은(는) ASCII 문자가 아닙니다", c)));
+    }
+    Ok(c)
+}
+
+impl TableSpec {
+    pub fn new(name: &str, title: &str, path: &str, key_hint: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            title: title.to_string(),
+            path: path.to_string(),
+            key_hint: key_hint.to_string(),
+            delimiter: default_delimiter(),
+            encoding: None,
+            parent: None,
+        }
+    }
+}
+
+
+/// 로드된 테이블: 설정 + 스키마 + key->행
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub spec: TableSpec,
+    pub schema: TableSchema,
+    pub rows: BTreeMap<String, DynRow>,
+    pub order: Vec<String>, // 파일의 행 순서 (저장 시 유지)
+    pub conflicts: Vec<KeyConflict>, // 키가 비었거나 겹쳐 `rows`에 넣지 못한 행 (`key_conflicts`로 해결)
+}
+
+impl Table {
+    pub fn load(spec: &TableSpec) -> Result<Self> {
+        if !spec.delimiter.is_ascii() {
+            bail!("{}: 구분자 '{}'은(는) ASCII 문자가 아닙니다", spec.path, spec.delimiter);
+        }
+        let (mut schema, rows, order, conflicts) =
+            load_table(&spec.path, &spec.key_hint, spec.delimiter, spec.encoding)?;
+        // 부모 컬럼은 헤더 이름으로 맞춘다 (대소문자 무시). 복합 키 테이블은 지정이 없어도 첫 키 컬럼이 부모
+        schema.parent_column = match spec.parent.as_deref().map(str::trim).filter(|p| !p.is_empty()) {
+            Some(p) => match schema.find(p) {
+                Some(c) => Some(c.key.clone()),
+                None => bail!("{}: 부모 컬럼 '{}'이(가) 없습니다", spec.path, p),
+            },
+            None if !schema.sub_keys.is_empty() => Some(schema.key_column.clone()),
+            None => None,
+        };
+        Ok(Self { spec: spec.clone(), schema, rows, order, conflicts })
+    }
+
+    /// 엔티티 하나에 여러 행이 딸린 테이블인지 (`TableSchema::parent_column`)
+    pub fn is_child(&self) -> bool {
+        self.schema.is_child()
+    }
+
+    /// 엔티티 `parent`에 딸린 행 키들 (파일 순서, `ordered_keys`)
+    pub fn child_keys(&self, parent: &str) -> Vec<String> {
+        ordered_keys(&self.rows, &self.order)
+            .into_iter()
+            .filter(|k| self.schema.parent_of(k, &self.rows[*k]) == parent)
+            .cloned()
+            .collect()
+    }
+
+    /// 저장할 파일들: 데이터 파일(백업 대상) + 필요하면 스키마 사이드카 (`needs_schema_sidecar`)
+    pub fn render(&self) -> Result<Vec<FileWrite>> {
+        let bytes = render_table(&self.schema, &self.rows, &self.order, &self.conflicts, false)?;
+        let sidecar = needs_schema_sidecar(&self.spec.path, &self.schema, &bytes);
+        let mut files = vec![FileWrite { path: self.spec.path.clone(), bytes, backup: true }];
+        if sidecar {
+            files.push(render_schema_sidecar(&self.spec.path, &self.schema)?);
+        }
+        Ok(files)
+    }
+
+    /// 계산 컬럼 값까지 포함해 다른 경로로 내보낸다 (원본/사이드카는 그대로)
+    pub fn export_with_computed(&self, path: &str) -> Result<()> {
+        save_table(path, &self.schema, &self.rows, &self.order, &self.conflicts, true)
+    }
+
+    /// 모든 행에 기본값을 채워 컬럼을 끝에 추가
+    pub fn add_column(&mut self, name: &str, dtype: DataType, default: &str) -> Result<()> {
+        let name = name.trim();
+        if name.is_empty() {
+            bail!("컬럼 이름이 비어 있습니다");
+        }
+        if self.schema.find(name).is_some() {
+            bail!("{}: 컬럼 '{}'이(가) 이미 있습니다", self.spec.name, name);
+        }
+        self.schema.columns.push(ColumnDef {
+            key: name.to_string(),
+            label: name.to_string(),
+            dtype,
+            rules: ColumnRules::default(),
+            formula: None,
+            aliases: Vec::new(),
+        });
+        for row in self.rows.values_mut() {
+            row.set(name, default.to_string());
+        }
+        Ok(())
+    }
+
+    /// 식으로 값을 계산하는 읽기 전용 컬럼을 끝에 추가 (값은 `computed::recompute`가 채움)
+    pub fn add_computed_column(&mut self, name: &str, dtype: DataType, formula: &str) -> Result<()> {
+        self.add_column(name, dtype, "")?;
+        if let Some(col) = self.schema.columns.last_mut() {
+            col.formula = Some(formula.trim().to_string());
+        }
+        Ok(())
+    }
+
+    /// 헤더 이름 변경. 라벨이 헤더와 같았으면 라벨도 따라 바꾼다.
+    fn rename_column_local(&mut self, old: &str, new: &str) -> Result<()> {
+        let new = new.trim();
+        if new.is_empty() {
+            bail!("컬럼 이름이 비어 있습니다");
+        }
+        if old != new && self.schema.columns.iter().any(|c| c.key == new) {
+            bail!("{}: 컬럼 '{}'이(가) 이미 있습니다", self.spec.name, new);
+        }
+        let Some(col) = self.schema.columns.iter_mut().find(|c| c.key == old) else {
+            bail!("{}: 컬럼 '{}'이(가) 없습니다", self.spec.name, old);
+        };
+        if col.label == col.key {
+            col.label = new.to_string();
+        }
+        // 이전 이름은 별칭으로 남겨 생성 코드가 옛 파일도 읽게 한다 (되돌리면 별칭에서 뺌)
+        if old != new && !col.aliases.iter().any(|a| a == old) {
+            col.aliases.push(old.to_string());
+        }
+        col.aliases.retain(|a| a != new);
+        col.key = new.to_string();
+        // 다음 로드에서도 같은 키/부모 컬럼을 쓰도록 설정도 함께
+        if self.schema.is_key_column(old) {
+            let schema = &mut self.schema;
+            for k in std::iter::once(&mut schema.key_column).chain(schema.sub_keys.iter_mut()) {
+                if k == old {
+                    *k = new.to_string();
+                }
+            }
+            self.spec.key_hint = self.schema.key_columns().collect::<Vec<_>>().join("+");
+        }
+        if self.schema.parent_column.as_deref() == Some(old) {
+            self.schema.parent_column = Some(new.to_string());
+            if self.spec.parent.is_some() {
+                self.spec.parent = Some(new.to_string());
+            }
+        }
+        for row in self.rows.values_mut() {
+            if let Some(v) = row.cells.remove(old) {
+                row.cells.insert(new.to_string(), v);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        if from < self.schema.columns.len() && to < self.schema.columns.len() {
+            let col = self.schema.columns.remove(from);
+            self.schema.columns.insert(to, col);
+        }
+    }
+
+    /// 컬럼과 모든 행의 셀을 지운다. 키/부모 컬럼은 지울 수 없음.
+    pub fn remove_column(&mut self, name: &str) -> Result<()> {
+        if self.schema.is_key_column(name) || self.schema.parent_column.as_deref() == Some(name) {
+            bail!("{}: 키 컬럼 '{}'은(는) 삭제할 수 없습니다", self.spec.name, name);
+        }
+        let before = self.schema.columns.len();
+        self.schema.columns.retain(|c| c.key != name);
+        if self.schema.columns.len() == before {
+            bail!("{}: 컬럼 '{}'이(가) 없습니다", self.spec.name, name);
+        }
+        for row in self.rows.values_mut() {
+            row.cells.remove(name);
+        }
+        Ok(())
+    }
+
+    /// 스키마만 사이드카 파일로 저장
+    pub fn save_schema(&self) -> Result<()> {
+        save_schema_sidecar(&self.spec.path, &self.schema)
+    }
+}
+
+
+/// 이름 붙은 테이블 모음. 테이블 추가는 `TableSpec` 하나만 늘리면 된다.
+#[derive(Debug, Clone, Default)]
+pub struct DataSets {
+    pub tables: Vec<Table>,
+}
+
+
+impl DataSets {
+    pub fn load(specs: &[TableSpec]) -> Result<Self> {
+        let tables = specs.iter().map(Table::load).collect::<Result<Vec<_>>>()?;
+        Ok(Self { tables })
+    }
+
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.spec.name == name)
+    }
+
+    pub fn table_mut(&mut self, name: &str) -> Option<&mut Table> {
+        self.tables.iter_mut().find(|t| t.spec.name == name)
+    }
+
+    /// 모든 테이블의 엔티티 키 합집합 (정렬/중복 제거). 자식 테이블은 행 키 대신 부모 값.
+    pub fn keys(&self) -> BTreeSet<String> {
+        self.tables
+            .iter()
+            .flat_map(|t| t.rows.iter().map(|(k, r)| t.schema.parent_of(k, r)))
+            .filter(|k| !k.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// 테이블 행 키가 속한 엔티티 키 (자식 테이블이면 부모 값, 없는 행이면 그대로)
+    pub fn entity_key<'a>(&'a self, table: &str, key: &'a str) -> &'a str {
+        match self.table(table).and_then(|t| Some((t, t.rows.get(key)?))) {
+            Some((t, row)) => t.schema.parent_of(key, row),
+            None => key,
+        }
+    }
+
+    pub fn merged(&self) -> Vec<DynEntity> {
+        self.keys()
+            .into_iter()
+            .map(|k| DynEntity {
+                rows: self
+                    .tables
+                    .iter()
+                    .filter(|t| !t.is_child())
+                    .filter_map(|t| t.rows.get(&k).map(|r| (t.spec.name.clone(), r.clone())))
+                    .collect(),
+                children: self
+                    .tables
+                    .iter()
+                    .filter(|t| t.is_child())
+                    .map(|t| (t.spec.name.clone(), t.child_keys(&k).iter().map(|c| t.rows[c].clone()).collect()))
+                    .collect(),
+                unique: k,
+            })
+            .collect()
+    }
+
+    /// 새 엔티티에 쓸 키: 모든 키가 숫자면 최댓값+1, 아니면 "new_N"
+    pub fn next_free_key(&self) -> String {
+        let keys = self.keys();
+        let nums: Option<Vec<u64>> = keys.iter().map(|k| k.parse().ok()).collect();
+        match nums {
+            Some(nums) => (nums.into_iter().max().unwrap_or(0) + 1).to_string(),
+            None => (1..)
+                .map(|i| format!("new_{}", i))
+                .find(|k| !keys.contains(k))
+                .unwrap_or_default(),
+        }
+    }
+
+    /// 모든 테이블에 기본값 행을 만든다 (자식 테이블은 빈 채로). 이미 있는 키면 아무것도 하지 않음.
+    pub fn create_entity(&mut self, key: &str) -> Vec<RowEdit> {
+        if self.keys().contains(key) {
+            return Vec::new();
+        }
+        let mut edits = Vec::new();
+        for t in self.tables.iter_mut().filter(|t| !t.is_child()) {
+            let cells = t
+                .schema
+                .columns
+                .iter()
+                .map(|c| {
+                    let v = if c.key == t.schema.key_column { key.to_string() } else { c.dtype.default_value() };
+                    (c.key.clone(), v)
+                })
+                .collect();
+            let row = DynRow { key: key.to_string(), cells, raw: None };
+            t.rows.insert(key.to_string(), row.clone());
+            edits.push(RowEdit { table: t.spec.name.clone(), key: key.to_string(), before: None, after: Some(row) });
+        }
+        edits
+    }
+
+    /// `src`가 있는 테이블의 행을 `dst` 키로 복사 (자식 행은 모두, 같은 순서로)
+    pub fn duplicate_entity(&mut self, src: &str, dst: &str) -> Vec<RowEdit> {
+        if self.keys().contains(dst) {
+            return Vec::new();
+        }
+        let mut edits = Vec::new();
+        for t in &mut self.tables {
+            if t.is_child() {
+                for key in t.child_keys(src) {
+                    let Ok(row) = children::copy_row(t, &t.rows[&key], dst) else { continue };
+                    t.order.push(row.key.clone());
+                    t.rows.insert(row.key.clone(), row.clone());
+                    edits.push(RowEdit { table: t.spec.name.clone(), key: row.key.clone(), before: None, after: Some(row) });
+                }
+                continue;
+            }
+            let Some(mut row) = t.rows.get(src).cloned() else { continue };
+            row.key = dst.to_string();
+            row.set(&t.schema.key_column, dst.to_string());
+            t.rows.insert(dst.to_string(), row.clone());
+            edits.push(RowEdit { table: t.spec.name.clone(), key: dst.to_string(), before: None, after: Some(row) });
+        }
+        edits
+    }
+
+    /// 모든 테이블에서 키의 행(자식 테이블은 딸린 행 모두)을 지운다
+    pub fn delete_entity(&mut self, key: &str) -> Vec<RowEdit> {
+        let mut edits = Vec::new();
+        for t in &mut self.tables {
+            let keys = if t.is_child() { t.child_keys(key) } else { vec![key.to_string()] };
+            for key in keys {
+                let Some(row) = t.rows.remove(&key) else { continue };
+                edits.push(RowEdit { table: t.spec.name.clone(), key, before: Some(row), after: None });
+            }
+        }
+        edits
+    }
+
+    /// 컬럼 이름 변경 + 다른 테이블에서 이 컬럼을 가리키는 Reference도 갱신.
+    /// 반환: 스키마가 바뀐 테이블 이름들
+    pub fn rename_column(&mut self, table: &str, old: &str, new: &str) -> Result<Vec<String>> {
+        let Some(t) = self.table_mut(table) else {
+            bail!("테이블 '{}'이(가) 없습니다", table);
+        };
+        t.rename_column_local(old, new)?;
+        let new = new.trim();
+
+        let mut changed = vec![table.to_string()];
+        for t in &mut self.tables {
+            let mut touched = false;
+            for col in &mut t.schema.columns {
+                let DataType::Reference(r) = &mut col.dtype else { continue };
+                // 빈 column은 "대상의 키 컬럼"이므로 키 이름이 바뀌어도 그대로 유효
+                if r.table == table && r.column.eq_ignore_ascii_case(old) {
+                    r.column = new.to_string();
+                    touched = true;
+                }
+            }
+            if touched && !changed.contains(&t.spec.name) {
+                changed.push(t.spec.name.clone());
+            }
+        }
+        Ok(changed)
+    }
+
+    /// 모든 테이블을 한 묶음으로 저장. 하나라도 실패하면 어떤 파일도 바뀌지 않는다.
+    /// 덮어쓴 데이터 파일은 테이블마다 최대 `keep_backups`개까지 백업으로 남긴다.
+    pub fn save_all(&self, keep_backups: usize) -> Result<()> {
+        let mut files = Vec::new();
+        for t in &self.tables {
+            files.extend(t.render()?);
+        }
+        write_files(&files, keep_backups)
+    }
+}
@@ -0,0 +1,119 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::{Context, Result};
+
+use super::storage::{write_files, FileWrite};
+
+/// 데이터 파일 옆의 백업 폴더 이름
+pub const BACKUP_DIR: &str = ".backups";
+
+/// 테이블마다 남길 백업 수 기본값
+pub const DEFAULT_KEEP: usize = 5;
+
+/// 백업 파일 하나 (최신이 앞)
+#[derive(Debug, Clone)]
+pub struct BackupEntry {
+    pub path: PathBuf,
+    pub stamp: String, // "YYYYMMDD-HHMMSS-mmm" (UTC)
+    pub len: u64,
+}
+
+impl BackupEntry {
+    /// "2026-10-18 15:30:12" 형태
+    pub fn display_time(&self) -> String {
+        let s = &self.stamp;
+        if s.len() < 15 {
+            return s.clone();
+        }
+        format!("{}-{}-{} {}:{}:{} UTC", &s[0..4], &s[4..6], &s[6..8], &s[9..11], &s[11..13], &s[13..15])
+    }
+}
+
+pub fn backup_dir(path: &str) -> PathBuf {
+    Path::new(path).parent().unwrap_or(Path::new("")).join(BACKUP_DIR)
+}
+
+/// (파일 이름 앞부분, 확장자 부분) 예: character_info.csv -> ("character_info.", ".csv")
+fn name_parts(path: &str) -> (String, String) {
+    let p = Path::new(path);
+    let stem = p.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = p.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+    (format!("{}.", stem), ext)
+}
+
+/// 현재 시각(UTC) 스탬프. 정렬하면 시간 순서가 된다.
+fn timestamp() -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = now.as_secs() as i64;
+    let (days, rem) = (secs.div_euclid(86_400), secs.rem_euclid(86_400));
+    // 1970-01-01부터의 일 수 -> 연/월/일 (proleptic Gregorian)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}-{:03}",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        now.subsec_millis()
+    )
+}
+
+/// 덮어쓰기 직전의 원본(`old`)을 백업 폴더로 옮기고 오래된 것을 정리한다.
+pub fn archive(path: &str, old: &Path, keep: usize) -> Result<()> {
+    let dir = backup_dir(path);
+    fs::create_dir_all(&dir).with_context(|| format!("create {}", dir.display()))?;
+    let (prefix, ext) = name_parts(path);
+    let mut target = dir.join(format!("{}{}{}", prefix, timestamp(), ext));
+    while target.exists() {
+        // 같은 밀리초에 두 번 저장한 경우: 이전 백업을 덮어쓰지 않게 다음 스탬프까지 기다린다
+        thread::sleep(Duration::from_millis(1));
+        target = dir.join(format!("{}{}{}", prefix, timestamp(), ext));
+    }
+    fs::rename(old, &target).with_context(|| format!("backup {}", target.display()))?;
+    for stale in list_backups(path).into_iter().skip(keep) {
+        let _ = fs::remove_file(stale.path);
+    }
+    Ok(())
+}
+
+/// 데이터 파일의 백업 목록 (최신 순)
+pub fn list_backups(path: &str) -> Vec<BackupEntry> {
+    let (prefix, ext) = name_parts(path);
+    let Ok(entries) = fs::read_dir(backup_dir(path)) else { return Vec::new() };
+    let mut out: Vec<BackupEntry> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            let stamp = name.strip_prefix(&prefix)?.strip_suffix(&ext)?;
+            // 같은 접두어의 다른 파일(예: x.computed.csv)은 제외
+            if stamp.len() != 19 || !stamp.chars().all(|c| c.is_ascii_digit() || c == '-') {
+                return None;
+            }
+            let len = e.metadata().map(|m| m.len()).unwrap_or(0);
+            Some(BackupEntry { path: e.path(), stamp: stamp.to_string(), len })
+        })
+        .collect();
+    out.sort_by(|a, b| b.stamp.cmp(&a.stamp));
+    out
+}
+
+/// 백업 내용을 데이터 파일에 되살린다. 지금 파일도 먼저 백업으로 남는다.
+pub fn restore(path: &str, entry: &BackupEntry, keep: usize) -> Result<()> {
+    let bytes = fs::read(&entry.path).with_context(|| format!("open {}", entry.path.display()))?;
+    write_files(&[FileWrite { path: path.to_string(), bytes, backup: true }], keep.max(1))
+}
@@ -0,0 +1,301 @@
+// 화면 없이 쓰는 명령줄 도구 (CI/빌드 파이프라인용)
+//   entity-cli validate <project.json | 데이터 파일...> [--strict]
+//   entity-cli convert <입력> <출력> [--schema]       (.csv/.txt <-> .json, --schema: 스키마 사이드카도 씀)
+//   entity-cli diff <이전 파일> <새 파일>
+//   entity-cli fmt <project.json | 데이터 파일...> [--check]
+//   entity-cli export <project.json | 데이터 파일...> [--format json,ron,msgpack,bin] [--out 폴더] [--entities]
+//   entity-cli codegen <project.json | 데이터 파일...> --out <파일> [--lang rust|csharp|cpp] [--template <파일>] [--check]
+// 공통 옵션: --key <헤더명[+헤더명...]>, --parent <헤더명>, --delimiter <문자|\t>, --encoding <utf8|cp949>
+// 종료 코드: 0 = 통과, 1 = 검증 오류/차이/정리 필요, 2 = 사용법/입출력 오류
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{bail, Context, Result};
+
+use entity_manager::app_state::{DataSets, Table, TableSpec};
+use entity_manager::changes::Pending;
+use entity_manager::codegen::{self, CodegenOptions};
+use entity_manager::computed;
+use entity_manager::dialect::TextEncoding;
+use entity_manager::convert::{read_json, write_delimited, write_json};
+use entity_manager::export::{export, resolve_dir, ExportFormat, ExportShape};
+use entity_manager::project::{project_dir, ProjectFile};
+use entity_manager::storage::{render_table, write_files, FileWrite};
+use entity_manager::validation::{encoding_warnings, has_errors, validate, Severity};
+
+const USAGE: &str = "사용법:
+  entity-cli validate <project.json | 데이터 파일...> [--strict]
+  entity-cli convert <입력> <출력> [--schema]
+  entity-cli diff <이전 파일> <새 파일>
+  entity-cli fmt <project.json | 데이터 파일...> [--check]
+  entity-cli export <project.json | 데이터 파일...> [--format json,ron,msgpack,bin] [--out 폴더] [--entities]
+  entity-cli codegen <project.json | 데이터 파일...> --out <파일> [--lang rust|csharp|cpp] [--template <파일>] [--check]
+옵션: --key <헤더명[+헤더명...]>  --parent <헤더명>  --delimiter <문자|\\t>  --encoding <utf8|cp949>";
+
+/// 명령 뒤의 인자들
+#[derive(Debug, Default)]
+struct Args {
+    paths: Vec<String>,
+    key: String,      // 비우면 첫 컬럼, "A+B"면 복합 키
+    parent: Option<String>, // 자식 테이블의 부모 컬럼 (복합 키면 생략 시 첫 키 컬럼)
+    delimiter: Option<char>, // 읽을 때는 힌트(파일에서 감지), convert 출력에는 그대로
+    encoding: Option<TextEncoding>, // 비우면 감지. convert에서는 출력 인코딩 (입력은 감지)
+    strict: bool,     // validate: 경고도 실패로
+    schema: bool,     // convert: 데이터 파일 옆에 스키마 사이드카도 쓴다
+    check: bool,      // fmt/codegen: 쓰지 않고 확인만
+    formats: Vec<ExportFormat>, // export: 비우면 프로젝트 설정
+    out: Option<String>,        // export: 출력 폴더, codegen: 출력 파일
+    entities: bool,             // export: 키로 합친 엔티티 파일 하나
+    lang: String,               // codegen: 비우면 rust
+    template: Option<String>,   // codegen: C#/C++ 템플릿 파일 (비우면 기본 템플릿)
+}
+
+impl Args {
+    fn parse(raw: impl Iterator<Item = String>) -> Result<Self> {
+        let mut args = Args::default();
+        let mut raw = raw.peekable();
+        while let Some(a) = raw.next() {
+            match a.as_str() {
+                "--strict" => args.strict = true,
+                "--check" => args.check = true,
+                "--schema" => args.schema = true,
+                "--key" => args.key = raw.next().unwrap_or_default(),
+                "--parent" => args.parent = raw.next(),
+                "--out" => args.out = raw.next(),
+                "--entities" => args.entities = true,
+                "--lang" => args.lang = raw.next().unwrap_or_default(),
+                "--template" => args.template = raw.next(),
+                "--format" => {
+                    for f in raw.next().unwrap_or_default().split(',') {
+                        let Some(format) = ExportFormat::parse(f) else { bail!("알 수 없는 형식 '{}'", f) };
+                        args.formats.push(format);
+                    }
+                }
+                "--delimiter" => {
+                    args.delimiter = match raw.next().as_deref() {
+                        Some("\\t") | Some("tab") => Some('\t'),
+                        Some(d) if d.len() == 1 => d.chars().next(),
+                        _ => bail!("--delimiter에는 ASCII 한 글자 또는 \\t를 주세요"),
+                    }
+                }
+                "--encoding" => {
+                    let e = raw.next().unwrap_or_default();
+                    let Some(encoding) = TextEncoding::parse(&e) else { bail!("알 수 없는 인코딩 '{}'", e) };
+                    args.encoding = Some(encoding);
+                }
+                s if s.starts_with("--") => bail!("알 수 없는 옵션 {}", s),
+                _ => args.paths.push(a),
+            }
+        }
+        Ok(args)
+    }
+
+    /// 데이터 파일 하나의 설정 (테이블 이름 = 파일 이름)
+    fn spec(&self, path: &str) -> TableSpec {
+        let name = Path::new(path).file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let mut spec = TableSpec::new(&name, &name, path, &self.key);
+        spec.delimiter = self.delimiter.unwrap_or(',');
+        spec.encoding = self.encoding;
+        spec.parent = self.parent.clone();
+        spec
+    }
+
+    /// 인자가 프로젝트 파일(.json) 하나면 그 테이블들, 아니면 데이터 파일마다 테이블 하나
+    fn specs(&self) -> Result<Vec<TableSpec>> {
+        Ok(self.project()?.tables)
+    }
+
+    /// 데이터 파일만 준 경우는 기본 설정의 프로젝트로 본다
+    fn project(&self) -> Result<ProjectFile> {
+        if self.paths.is_empty() {
+            bail!("파일을 지정하세요");
+        }
+        if let [one] = self.paths.as_slice() {
+            if is_json(one) {
+                let mut project = ProjectFile::load(one)?;
+                project.tables = project.status_key_mode.apply(&project.tables);
+                return Ok(project);
+            }
+        }
+        let tables = self.paths.iter().map(|p| self.spec(p)).collect();
+        Ok(ProjectFile { tables, ..Default::default() })
+    }
+
+    /// 상대 경로 설정의 기준 폴더: 프로젝트 폴더, 아니면 첫 데이터 파일 폴더
+    fn base_dir(&self) -> PathBuf {
+        let first = self.paths.first().map(String::as_str).unwrap_or("");
+        if is_json(first) {
+            project_dir(first)
+        } else {
+            Path::new(first).parent().map(Path::to_path_buf).unwrap_or_default()
+        }
+    }
+}
+
+fn is_json(path: &str) -> bool {
+    Path::new(path).extension().is_some_and(|e| e.eq_ignore_ascii_case("json"))
+}
+
+fn load_any(spec: &TableSpec) -> Result<Table> {
+    if is_json(&spec.path) { read_json(spec) } else { Table::load(spec) }
+}
+
+fn cmd_validate(args: &Args) -> Result<bool> {
+    let mut ds = DataSets::load(&args.specs()?)?;
+    computed::recompute(&mut ds);
+    let diags = validate(&ds);
+    for d in &diags {
+        println!("{}", d);
+    }
+    let errors = diags.iter().filter(|d| d.severity == Severity::Error).count();
+    println!("테이블 {}개, 오류 {}건, 경고 {}건", ds.tables.len(), errors, diags.len() - errors);
+    Ok(!has_errors(&diags) && (!args.strict || diags.is_empty()))
+}
+
+fn cmd_convert(args: &Args) -> Result<bool> {
+    let [input, output] = args.paths.as_slice() else { bail!("convert <입력> <출력>") };
+    let mut spec = args.spec(input);
+    spec.encoding = None;
+    let mut table = load_any(&spec)?;
+    if is_json(output) {
+        // JSON 행은 키로 구분하므로 키 문제 행은 넣지 못한다
+        for c in &table.conflicts {
+            println!("⚠️ {} (JSON에서 빠짐)", c);
+        }
+        write_json(&table, output)?;
+    } else {
+        // 표기 방식은 입력 그대로 (JSON이면 기록된 값), --delimiter/--encoding을 주면 그 값으로
+        if let Some(d) = args.delimiter {
+            table.schema.dialect.delimiter = d;
+        }
+        if let Some(e) = args.encoding {
+            table.schema.dialect.encoding = e;
+        }
+        for d in encoding_warnings(std::slice::from_ref(&table)) {
+            println!("{}", d);
+        }
+        write_delimited(&table, output, args.schema)?;
+    }
+    println!("{} -> {} ({}행)", input, output, table.rows.len());
+    Ok(true)
+}
+
+fn cmd_diff(args: &Args) -> Result<bool> {
+    let [old, new] = args.paths.as_slice() else { bail!("diff <이전 파일> <새 파일>") };
+    let (mut base, cur) = (load_any(&args.spec(old))?, load_any(&args.spec(new))?);
+    base.spec.name = cur.spec.name.clone(); // 두 파일 이름이 달라도 같은 테이블로 비교
+    if base.schema.key_column != cur.schema.key_column {
+        println!("⚠️ 키 컬럼이 다릅니다: {} / {}", base.schema.key_column, cur.schema.key_column);
+    }
+    let pending = Pending::diff_tables(&base, &cur);
+    for c in &pending.changes {
+        println!("{}", c);
+    }
+    println!("변경 {}건", pending.changes.len());
+    Ok(pending.is_empty())
+}
+
+fn cmd_fmt(args: &Args) -> Result<bool> {
+    let mut clean = true;
+    for spec in args.specs()? {
+        let t = Table::load(&spec)?;
+        let bytes = render_table(&t.schema, &t.rows, &t.order, &t.conflicts, false)?;
+        if std::fs::read(&spec.path)? == bytes {
+            continue;
+        }
+        clean = false;
+        if args.check {
+            println!("정리 필요: {}", spec.path);
+        } else {
+            write_files(&[FileWrite { path: spec.path.clone(), bytes, backup: false }], 0)?;
+            println!("정리함: {}", spec.path);
+        }
+    }
+    Ok(clean || !args.check)
+}
+
+fn cmd_export(args: &Args) -> Result<bool> {
+    let project = args.project()?;
+    let mut settings = project.export;
+    if !args.formats.is_empty() {
+        settings.formats = args.formats.clone();
+    }
+    if let Some(out) = &args.out {
+        settings.dir = out.clone();
+    }
+    if args.entities {
+        settings.shape = ExportShape::Entities;
+    }
+    let mut ds = DataSets::load(&project.tables)?;
+    computed::recompute(&mut ds);
+    let dir = resolve_dir(&settings, &args.base_dir());
+    for path in export(&ds, &settings, &dir)? {
+        println!("{}", path);
+    }
+    Ok(true)
+}
+
+fn cmd_codegen(args: &Args) -> Result<bool> {
+    let Some(out) = &args.out else { bail!("codegen에는 --out <파일>이 필요합니다") };
+    let ds = DataSets::load(&args.project()?.tables)?;
+    let options = CodegenOptions { base_dir: Some(args.base_dir()), ..Default::default() };
+    let template = match &args.template {
+        Some(path) => Some(std::fs::read_to_string(path).with_context(|| format!("open {}", path))?),
+        None => None,
+    };
+    let source = match (args.lang.as_str(), template) {
+        ("" | "rust" | "rs", None) => codegen::rust::generate(&ds, &options),
+        ("" | "rust" | "rs", Some(_)) => bail!("Rust 생성기는 템플릿을 쓰지 않습니다"),
+        ("csharp" | "cs" | "c#", t) => {
+            codegen::csharp::generate_with(&ds, &options, t.as_deref().unwrap_or(codegen::csharp::DEFAULT_TEMPLATE))?
+        }
+        ("cpp" | "c++", t) => {
+            codegen::cpp::generate_with(&ds, &options, t.as_deref().unwrap_or(codegen::cpp::DEFAULT_TEMPLATE))?
+        }
+        (other, _) => bail!("지원하지 않는 언어 '{}' (rust, csharp, cpp)", other),
+    };
+    for w in codegen::encoding_warnings(&codegen::build_models(&ds, &options)) {
+        eprintln!("⚠️ {}", w);
+    }
+    if std::fs::read(out).ok().as_deref() == Some(source.as_bytes()) {
+        return Ok(true);
+    }
+    if args.check {
+        println!("다시 생성 필요: {}", out);
+        return Ok(false);
+    }
+    write_files(&[FileWrite { path: out.clone(), bytes: source.into_bytes(), backup: false }], 0)?;
+    println!("생성함: {}", out);
+    Ok(true)
+}
+
+fn main() -> ExitCode {
+    let mut raw = std::env::args().skip(1);
+    let Some(cmd) = raw.next() else {
+        eprintln!("{}", USAGE);
+        return ExitCode::from(2);
+    };
+    let result = Args::parse(raw).and_then(|args| match cmd.as_str() {
+        "validate" => cmd_validate(&args),
+        "convert" => cmd_convert(&args),
+        "diff" => cmd_diff(&args),
+        "fmt" => cmd_fmt(&args),
+        "export" => cmd_export(&args),
+        "codegen" => cmd_codegen(&args),
+        "help" | "--help" | "-h" => {
+            println!("{}", USAGE);
+            Ok(true)
+        }
+        other => bail!("알 수 없는 명령 '{}'\n{}", other, USAGE),
+    });
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("❌ {:#}", e);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use super::app_state::DataSets;
+use super::history::CellEdit;
+use super::query::{Expr, Scope, Value};
+
+/// 일괄 편집 미리보기 한 행: 기존 값과 식 결과(또는 오류)
+#[derive(Debug, Clone)]
+pub struct BulkChange {
+    pub key: String,
+    pub old: String,
+    pub new: Result<String, String>,
+}
+
+impl BulkChange {
+    pub fn changed(&self) -> bool {
+        matches!(&self.new, Ok(v) if *v != self.old)
+    }
+}
+
+/// `keys` 중 대상 테이블에 행이 있는 것마다 식을 평가해 새 값을 만든다 (아직 쓰지 않음).
+/// 식에서 테이블 없이 쓴 컬럼은 대상 테이블에서 먼저 찾는다.
+pub fn preview(ds: &DataSets, table: &str, column: &str, expr: &Expr, keys: &[String]) -> Vec<BulkChange> {
+    let Some(t) = ds.table(table) else { return Vec::new() };
+    let Some(col) = t.schema.find(column) else { return Vec::new() };
+    keys.iter()
+        .filter_map(|key| {
+            let row = t.rows.get(key)?;
+            let old = row.get(&col.key).unwrap_or("").to_string();
+            let scope = Scope { ds, key, default_table: Some(table), child_rows: &[] };
+            let new = expr
+                .eval(&scope)
+                .map_err(|e| e.to_string())
+                .and_then(|v| match v {
+                    Value::Null => Err("결과 없음 (참조한 행/컬럼이 없음)".to_string()),
+                    v => v.to_cell(&col.dtype, &old),
+                });
+            Some(BulkChange { key: key.clone(), old, new })
+        })
+        .collect()
+}
+
+/// 바뀌는 행만 `DynRow::set`으로 쓰고, undo 기록용 편집 목록을 돌려준다. 오류 행은 건너뛴다.
+pub fn apply(ds: &mut DataSets, table: &str, column: &str, changes: &[BulkChange]) -> Vec<CellEdit> {
+    let Some(t) = ds.table_mut(table) else { return Vec::new() };
+    let Some(column) = t.schema.find(column).map(|c| c.key.clone()) else { return Vec::new() };
+    let mut edits = Vec::new();
+    for c in changes.iter().filter(|c| c.changed()) {
+        let (Some(row), Ok(new)) = (t.rows.get_mut(&c.key), &c.new) else { continue };
+        row.set(&column, new.clone());
+        edits.push(CellEdit {
+            table: table.to_string(),
+            key: c.key.clone(),
+            column: column.clone(),
+            old: c.old.clone(),
+            new: new.clone(),
+        });
+    }
+    edits
+}
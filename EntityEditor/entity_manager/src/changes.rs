@@ -0,0 +1,187 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+use anyhow::{bail, Result};
+
+use super::app_state::{DataSets, Table};
+use super::dyn_entity::{compare_keys, display_key};
+use super::history::{CellEdit, Command, RowEdit, TableEdit};
+
+/// 로드(또는 마지막 저장) 이후 바뀐 것 한 건의 종류
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    Cell { old: String, new: String },
+    RowAdded,
+    RowRemoved,
+    ColumnAdded,
+    ColumnRemoved,
+}
+
+/// 저장 대기 중인 변경 한 건. 행 변경은 `column`, 컬럼 변경은 `key`가 비어 있다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub table: String, // TableSpec.name
+    pub key: String,
+    pub column: String,
+    pub kind: ChangeKind,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = display_key(&self.key);
+        match &self.kind {
+            ChangeKind::Cell { old, new } => {
+                write!(f, "{}[{}].{}: '{}' → '{}'", self.table, key, self.column, old, new)
+            }
+            ChangeKind::RowAdded => write!(f, "{}[{}] 행 추가", self.table, key),
+            ChangeKind::RowRemoved => write!(f, "{}[{}] 행 삭제", self.table, key),
+            ChangeKind::ColumnAdded => write!(f, "{}.{} 컬럼 추가", self.table, self.column),
+            ChangeKind::ColumnRemoved => write!(f, "{}.{} 컬럼 삭제", self.table, self.column),
+        }
+    }
+}
+
+/// 기준 상태(디스크에 있는 것)와 현재 메모리의 차이. 계산 컬럼은 저장되지 않으므로 제외.
+#[derive(Debug, Default)]
+pub struct Pending {
+    pub changes: Vec<Change>,
+    cells: HashMap<(String, String, String), usize>, // (table, key, column) -> changes 인덱스
+    keys: HashSet<String>,                           // 무엇이든 바뀐 엔티티 키 (자식 행은 부모 키)
+}
+
+impl Pending {
+    pub fn diff(base: &DataSets, cur: &DataSets) -> Self {
+        let mut out = Self::default();
+        for t in &cur.tables {
+            if let Some(b) = base.table(&t.spec.name) {
+                out.diff_table(b, t);
+            }
+        }
+        out
+    }
+
+    /// 테이블 하나의 두 버전 비교 (예: 다른 커밋의 같은 파일)
+    pub fn diff_tables(base: &Table, cur: &Table) -> Self {
+        let mut out = Self::default();
+        out.diff_table(base, cur);
+        out
+    }
+
+    fn diff_table(&mut self, base: &Table, cur: &Table) {
+        let name = &cur.spec.name;
+        let stored = |t: &Table| -> Vec<String> {
+            t.schema.columns.iter().filter(|c| !c.is_computed()).map(|c| c.key.clone()).collect()
+        };
+        let (base_cols, cur_cols) = (stored(base), stored(cur));
+        for c in base_cols.iter().filter(|c| !cur_cols.contains(c)) {
+            self.push(name, "", c, ChangeKind::ColumnRemoved);
+        }
+        for c in cur_cols.iter().filter(|c| !base_cols.contains(c)) {
+            self.push(name, "", c, ChangeKind::ColumnAdded);
+        }
+
+        let mut keys: Vec<&String> = cur.rows.keys().chain(base.rows.keys().filter(|k| !cur.rows.contains_key(*k))).collect();
+        keys.sort_by(|a, b| compare_keys(a, b));
+        for key in keys {
+            let (before, after) = (base.rows.get(key), cur.rows.get(key));
+            // 자식 행이 바뀌면 그 행이 속한(속했던) 엔티티를 바뀐 것으로 본다
+            let parents = [before.map(|r| base.schema.parent_of(key, r)), after.map(|r| cur.schema.parent_of(key, r))];
+            let mut touched = false;
+            let (Some(b), Some(r)) = (before, after) else {
+                let kind = if cur.rows.contains_key(key) { ChangeKind::RowAdded } else { ChangeKind::RowRemoved };
+                self.push(name, key, "", kind);
+                self.keys.extend(parents.into_iter().flatten().map(str::to_string));
+                continue;
+            };
+            // 양쪽에 다 있는 컬럼만 셀 단위로 비교 (키 컬럼은 맵 키에서 나오므로 제외)
+            for c in cur_cols.iter().filter(|c| base_cols.contains(c) && !cur.schema.is_key_column(c)) {
+                let (old, new) = (b.get(c).unwrap_or(""), r.get(c).unwrap_or(""));
+                if old != new {
+                    let kind = ChangeKind::Cell { old: old.to_string(), new: new.to_string() };
+                    self.push(name, key, c, kind);
+                    touched = true;
+                }
+            }
+            if touched {
+                self.keys.extend(parents.into_iter().flatten().map(str::to_string));
+            }
+        }
+    }
+
+    fn push(&mut self, table: &str, key: &str, column: &str, kind: ChangeKind) {
+        if matches!(kind, ChangeKind::Cell { .. }) {
+            let id = (table.to_string(), key.to_string(), column.to_string());
+            self.cells.insert(id, self.changes.len());
+        }
+        self.changes.push(Change { table: table.to_string(), key: key.to_string(), column: column.to_string(), kind });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// 바뀐 셀이면 기준(디스크) 값
+    pub fn old_value(&self, table: &str, key: &str, column: &str) -> Option<&str> {
+        let i = self.cells.get(&(table.to_string(), key.to_string(), column.to_string()))?;
+        match &self.changes[*i].kind {
+            ChangeKind::Cell { old, .. } => Some(old),
+            _ => None,
+        }
+    }
+
+    /// 엔티티의 행(자식 행 포함)이 하나라도 바뀌었는지
+    pub fn key_changed(&self, key: &str) -> bool {
+        self.keys.contains(key)
+    }
+}
+
+/// 변경 한 건을 기준 값으로 되돌리고, undo 기록용 명령을 돌려준다.
+pub fn revert(base: &DataSets, ds: &mut DataSets, change: &Change) -> Result<Command> {
+    let label = format!("되돌리기: {}", change);
+    let (Some(b), Some(t)) = (base.table(&change.table), ds.table_mut(&change.table)) else {
+        bail!("테이블 '{}' 없음", change.table);
+    };
+    match &change.kind {
+        ChangeKind::Cell { old, new } => {
+            let Some(row) = t.rows.get_mut(&change.key) else { bail!("{}[{}] 행 없음", change.table, change.key) };
+            row.set(&change.column, old.clone());
+            let edit = CellEdit {
+                table: change.table.clone(),
+                key: change.key.clone(),
+                column: change.column.clone(),
+                old: new.clone(),
+                new: old.clone(),
+            };
+            Ok(Command::edits(label, vec![edit]))
+        }
+        ChangeKind::RowAdded => {
+            let before = t.rows.remove(&change.key);
+            let edit = RowEdit { table: change.table.clone(), key: change.key.clone(), before, after: None };
+            Ok(Command::rows(label, vec![edit]))
+        }
+        ChangeKind::RowRemoved => {
+            let Some(row) = b.rows.get(&change.key).cloned() else { bail!("기준 행 없음") };
+            t.rows.insert(change.key.clone(), row.clone());
+            let edit = RowEdit { table: change.table.clone(), key: change.key.clone(), before: None, after: Some(row) };
+            Ok(Command::rows(label, vec![edit]))
+        }
+        ChangeKind::ColumnAdded => {
+            let before = t.clone();
+            t.remove_column(&change.column)?;
+            Ok(Command::tables(label, vec![TableEdit { before, after: t.clone() }]))
+        }
+        ChangeKind::ColumnRemoved => {
+            let Some(pos) = b.schema.columns.iter().position(|c| c.key == change.column) else { bail!("기준 컬럼 없음") };
+            let before = t.clone();
+            let col = b.schema.columns[pos].clone();
+            for (key, row) in t.rows.iter_mut() {
+                let v = b.rows.get(key).and_then(|r| r.get(&col.key)).map(|v| v.to_string());
+                row.set(&col.key, v.unwrap_or_else(|| col.dtype.default_value()));
+            }
+            t.schema.columns.insert(pos.min(t.schema.columns.len()), col);
+            Ok(Command::tables(label, vec![TableEdit { before, after: t.clone() }]))
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! 자식 테이블(엔티티 하나에 여러 행, 예: 캐릭터별 스킬) 행 편집.
+//! 행 키는 키 컬럼 값에서 나오므로(`TableSchema::key_of`) 키 칸을 고치면 행 키도 바뀐다.
+//! 순서는 `Table.order` 안에서 형제 행끼리 자리를 바꿔 파일에 그대로 남긴다.
+//! GUI는 전후 `Table` 스냅샷(`history::TableEdit`)으로 되돌리기를 기록한다.
+
+use anyhow::{bail, Result};
+
+use super::app_state::Table;
+use super::dyn_entity::{display_key, DynRow};
+use super::storage::ordered_keys;
+
+/// 새 행 번호를 매길 컬럼: 부모가 아닌 마지막 키 컬럼 (예: SkillSlot)
+fn slot_column(t: &Table) -> Option<String> {
+    let last = t.schema.key_columns().last()?;
+    (t.schema.parent_column.as_deref() != Some(last)).then(|| last.to_string())
+}
+
+/// 행 키가 비었거나 이미 있으면 번호 컬럼에 다음 빈 번호를 매긴다.
+/// 번호는 나머지 키 값이 같은 행들의 최댓값 + 1.
+fn assign_free_key(t: &Table, row: &mut DynRow) -> Result<()> {
+    let key = t.schema.key_of(row);
+    let complete = t.schema.key_columns().all(|c| !row.get(c).unwrap_or("").trim().is_empty());
+    if complete && !t.rows.contains_key(&key) {
+        row.key = key;
+        return Ok(());
+    }
+    let Some(slot) = slot_column(t) else {
+        bail!("{}: 키 '{}'이(가) 이미 있습니다", t.spec.name, display_key(&key));
+    };
+    let same_group = |other: &DynRow| {
+        t.schema.key_columns().filter(|c| *c != slot).all(|c| other.get(c) == row.get(c))
+    };
+    let mut n = t
+        .rows
+        .values()
+        .filter(|r| same_group(r))
+        .filter_map(|r| r.get(&slot)?.trim().parse::<u64>().ok())
+        .max()
+        .unwrap_or(0)
+        + 1;
+    loop {
+        row.set(&slot, n.to_string());
+        let key = t.schema.key_of(row);
+        if !t.rows.contains_key(&key) {
+            row.key = key;
+            return Ok(());
+        }
+        n += 1;
+    }
+}
+
+/// 다른 엔티티(`parent`)로 옮긴 복사본. 키가 겹치면 새 번호를 매긴다.
+pub fn copy_row(t: &Table, row: &DynRow, parent: &str) -> Result<DynRow> {
+    let mut row = DynRow { raw: None, ..row.clone() };
+    if let Some(p) = &t.schema.parent_column {
+        row.set(p, parent.to_string());
+    }
+    assign_free_key(t, &mut row)?;
+    Ok(row)
+}
+
+/// `order`를 현재 행 전체로 맞춘다 (새 행도 자리를 갖도록)
+fn settle_order(t: &mut Table) {
+    t.order = ordered_keys(&t.rows, &t.order).into_iter().cloned().collect();
+}
+
+/// `parent`에 기본값 행을 하나 붙인다 (마지막 형제 행 바로 뒤). 새 행 키를 돌려준다.
+pub fn add_child(t: &mut Table, parent: &str) -> Result<String> {
+    let Some(parent_column) = t.schema.parent_column.clone() else {
+        bail!("{}: 자식 테이블이 아닙니다", t.spec.name);
+    };
+    let mut row = DynRow { key: String::new(), cells: Default::default(), raw: None };
+    for c in &t.schema.columns {
+        row.set(&c.key, c.dtype.default_value());
+    }
+    row.set(&parent_column, parent.to_string());
+    if let Some(slot) = slot_column(t) {
+        row.set(&slot, String::new()); // 번호는 형제 행 다음으로
+    }
+    assign_free_key(t, &mut row)?;
+
+    let last_sibling = t.child_keys(parent).pop();
+    settle_order(t);
+    let at = last_sibling
+        .and_then(|k| t.order.iter().position(|o| *o == k))
+        .map_or(t.order.len(), |i| i + 1);
+    let key = row.key.clone();
+    t.order.insert(at, key.clone());
+    t.rows.insert(key.clone(), row);
+    Ok(key)
+}
+
+pub fn remove_child(t: &mut Table, key: &str) -> Result<()> {
+    if t.rows.remove(key).is_none() {
+        bail!("{}: 행 '{}'이(가) 없습니다", t.spec.name, display_key(key));
+    }
+    t.order.retain(|k| k != key);
+    Ok(())
+}
+
+/// 형제 행 사이에서 한 칸 위/아래로. 움직였으면 true.
+pub fn move_child(t: &mut Table, key: &str, up: bool) -> bool {
+    let Some(row) = t.rows.get(key) else { return false };
+    let siblings = t.child_keys(t.schema.parent_of(key, row));
+    let Some(i) = siblings.iter().position(|k| k == key) else { return false };
+    let j = match (up, i) {
+        (true, 0) => return false,
+        (true, i) => i - 1,
+        (false, i) if i + 1 >= siblings.len() => return false,
+        (false, i) => i + 1,
+    };
+    settle_order(t);
+    let a = t.order.iter().position(|k| *k == siblings[i]);
+    let b = t.order.iter().position(|k| *k == siblings[j]);
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            t.order.swap(a, b);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// 키/부모 컬럼 값을 바꾸고 행 키를 다시 만든다 (파일 자리는 유지). 바뀐 행 키를 돌려준다.
+/// 다른 행과 키가 겹치거나 키 값이 비면 아무것도 바꾸지 않고 실패.
+pub fn set_key_cell(t: &mut Table, key: &str, column: &str, value: &str) -> Result<String> {
+    let Some(mut row) = t.rows.get(key).cloned() else {
+        bail!("{}: 행 '{}'이(가) 없습니다", t.spec.name, display_key(key));
+    };
+    let value = value.trim();
+    if value.is_empty() && t.schema.is_key_column(column) {
+        bail!("{}: 키 값 '{}'이(가) 비어 있습니다", t.spec.name, column);
+    }
+    row.set(column, value.to_string());
+    let new_key = if t.schema.sub_keys.is_empty() && column != t.schema.key_column {
+        key.to_string() // 단일 키 테이블에서 부모만 바뀜
+    } else {
+        t.schema.key_of(&row)
+    };
+    if new_key != key && t.rows.contains_key(&new_key) {
+        bail!("{}: 키 '{}'이(가) 이미 있습니다", t.spec.name, display_key(&new_key));
+    }
+    row.key = new_key.clone();
+    t.rows.remove(key);
+    t.rows.insert(new_key.clone(), row);
+    for k in t.order.iter_mut().filter(|k| *k == key) {
+        *k = new_key.clone();
+    }
+    for c in t.conflicts.iter_mut().filter(|c| c.after.as_deref() == Some(key)) {
+        c.after = Some(new_key.clone());
+    }
+    Ok(new_key)
+}
@@ -0,0 +1,166 @@
+//! C++ 코드 생성 (C++17, 헤더 하나): 테이블마다 구조체 + CSV/JSON 로더, 전체를 묶는 구조체 하나.
+//! JSON 로더는 nlohmann/json이 있을 때만 켜진다. 기본 템플릿은 `templates/cpp.hpp.tpl`.
+//! 다른 템플릿을 쓸 때 쓸 수 있는 변수:
+//!
+//! - 최상위: `namespace`, `guard`(매크로 접두어), `root`, `tables`
+//! - `tables` 항목: `name`, `struct`, `member`, `file`, `json_file`, `delimiter`, `key_type`, `key_name`,
+//!   `key_header`, `many`(자식 테이블), `rows_type`, `enums`, `fields`
+//! - `enums` 항목: `enum`, `enum_fn`, `variants` (`ident`, `value`)
+//! - `fields` 항목: `name`, `type`, `header`, `headers`, `parse`, `summary`, `reference`, `is_key`
+//!
+//! 문자열 값(`file`, `header`, `value` 등)은 이미 따옴표로 감싼 리터럴이다.
+
+use anyhow::Result;
+
+use super::template::{render, Scope};
+use super::{
+    build_models, byte_literal, escape_keyword, header_literals, pascal_case, quote, reference_text, snake_case,
+    CodegenOptions, FieldKind, FieldModel, Names, TableModel,
+};
+use crate::app_state::DataSets;
+
+pub const DEFAULT_TEMPLATE: &str = include_str!("templates/cpp.hpp.tpl");
+
+const KEYWORDS: &[&str] = &[
+    "alignas", "alignof", "and", "asm", "auto", "bool", "break", "case", "catch", "char", "class", "const",
+    "constexpr", "continue", "decltype", "default", "delete", "do", "double", "else", "enum", "explicit", "export",
+    "extern", "false", "float", "for", "friend", "goto", "if", "inline", "int", "long", "mutable", "namespace", "new",
+    "noexcept", "not", "nullptr", "operator", "or", "private", "protected", "public", "register", "return", "short",
+    "signed", "sizeof", "static", "struct", "switch", "template", "this", "throw", "true", "try", "typedef",
+    "typename", "union", "unsigned", "using", "virtual", "void", "volatile", "while", "xor",
+    // 템플릿이 쓰는 이름
+    "cells", "Names",
+];
+
+/// 템플릿이 구조체 안에 만드는 멤버 (컬럼 이름과 겹치면 안 됨)
+const STRUCT_MEMBERS: &[&str] = &["Key", "Rows", "kFile", "kJsonFile", "kDelimiter", "key", "from_row", "load_csv", "load_json"];
+
+fn field_type(kind: &FieldKind) -> String {
+    match kind {
+        FieldKind::Int => "std::int64_t".to_string(),
+        FieldKind::Float => "double".to_string(),
+        FieldKind::Bool => "bool".to_string(),
+        FieldKind::Text | FieldKind::Date => "std::string".to_string(),
+        FieldKind::Enum { type_name, .. } => escape_keyword(type_name, KEYWORDS),
+        FieldKind::List(_) => "std::vector<std::string>".to_string(),
+    }
+}
+
+/// `row`(CsvRow/JsonRow)에서 값을 읽는 식
+fn parse_expr(f: &FieldModel) -> String {
+    let names = format!("{{{}}}", header_literals(f));
+    let get = format!("row.get({})", names);
+    match &f.kind {
+        FieldKind::Int => format!("cells::to_int({})", get),
+        FieldKind::Float => format!("cells::to_float({})", get),
+        FieldKind::Bool => format!("cells::to_bool({})", get),
+        FieldKind::Text | FieldKind::Date => get,
+        FieldKind::Enum { type_name, .. } => format!("parse_{}({})", snake_case(type_name, "value"), get),
+        FieldKind::List(sep) => format!("row.list({}, {})", names, quote(sep)),
+    }
+}
+
+/// `///` 한 줄에 들어갈 글
+fn comment_text(s: &str) -> String {
+    s.replace(['\r', '\n'], " ")
+}
+
+fn table_scope(m: &TableModel, member: String) -> Scope {
+    let name = escape_keyword(&m.type_name, KEYWORDS);
+    let reserved: Vec<&str> = STRUCT_MEMBERS.iter().copied().chain([name.as_str()]).collect();
+    let mut names = Names::default();
+    let idents: Vec<String> = m
+        .fields
+        .iter()
+        .map(|f| names.unique(escape_keyword(&escape_keyword(&f.ident, KEYWORDS), &reserved)))
+        .collect();
+
+    let enums = m
+        .enums()
+        .map(|(name, variants)| {
+            let variants = variants
+                .iter()
+                .map(|v| {
+                    let mut s = Scope::new();
+                    s.set("ident", escape_keyword(&v.ident, KEYWORDS)).set("value", quote(&v.value));
+                    s
+                })
+                .collect::<Vec<_>>();
+            let mut s = Scope::new();
+            s.set("enum", escape_keyword(name, KEYWORDS))
+                .set("enum_fn", snake_case(name, "value"))
+                .set("variants", variants);
+            s
+        })
+        .collect::<Vec<_>>();
+
+    let fields = m
+        .fields
+        .iter()
+        .zip(&idents)
+        .enumerate()
+        .map(|(i, (f, ident))| {
+            let mut s = Scope::new();
+            s.set("name", ident.as_str())
+                .set("type", field_type(&f.kind))
+                .set("header", quote(&f.header))
+                .set("headers", header_literals(f))
+                .set("parse", parse_expr(f))
+                .set("summary", if f.label == f.header { String::new() } else { comment_text(&f.label) })
+                .set("reference", f.reference.as_ref().map(|r| comment_text(&reference_text(r))).unwrap_or_default())
+                .set("is_key", i == m.key);
+            s
+        })
+        .collect::<Vec<_>>();
+
+    let rows_type = if m.many {
+        format!("std::map<Key, std::vector<{}>>", name)
+    } else {
+        format!("std::map<Key, {}>", name)
+    };
+    let mut s = Scope::new();
+    s.set("name", m.name.as_str())
+        .set("struct", name)
+        .set("member", member)
+        .set("file", quote(&m.file))
+        .set("json_file", quote(&format!("{}.json", m.name)))
+        .set("delimiter", byte_literal(m.delimiter))
+        .set("key_type", field_type(&m.key_field().kind))
+        .set("key_name", idents[m.key].as_str())
+        .set("key_header", comment_text(&m.key_field().header))
+        .set("many", m.many)
+        .set("rows_type", rows_type)
+        .set("enums", enums)
+        .set("fields", fields);
+    s
+}
+
+/// 템플릿에 넘기는 값 전체
+pub fn scope(ds: &DataSets, options: &CodegenOptions) -> Scope {
+    let root = escape_keyword(&pascal_case(&options.root, "GameData"), KEYWORDS);
+    let namespace = escape_keyword(&snake_case(&options.namespace, "game_tables"), KEYWORDS);
+    let mut members = Names::default();
+    for reserved in [root.as_str(), "load_csv", "load_json"] {
+        members.unique(reserved.to_string());
+    }
+    let tables = build_models(ds, options)
+        .iter()
+        .map(|m| table_scope(m, members.unique(escape_keyword(&m.ident, KEYWORDS))))
+        .collect::<Vec<_>>();
+    let mut s = Scope::new();
+    s.set("guard", namespace.to_ascii_uppercase())
+        .set("namespace", namespace)
+        .set("root", root)
+        .set("tables", tables);
+    s
+}
+
+/// 기본 템플릿으로 생성
+pub fn generate(ds: &DataSets, options: &CodegenOptions) -> Result<String> {
+    generate_with(ds, options, DEFAULT_TEMPLATE)
+}
+
+/// 주어진 템플릿으로 생성
+pub fn generate_with(ds: &DataSets, options: &CodegenOptions, template: &str) -> Result<String> {
+    render(template, &scope(ds, options))
+}
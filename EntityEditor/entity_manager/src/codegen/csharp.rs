@@ -0,0 +1,157 @@
+//! C# 코드 생성 (Unity/.NET): 테이블마다 클래스 + CSV/JSON 로더, 전체를 묶는 클래스 하나.
+//! 기본 템플릿은 `templates/csharp.cs.tpl`. 다른 템플릿을 쓸 때 쓸 수 있는 변수:
+//!
+//! - 최상위: `namespace`, `root`, `tables`
+//! - `tables` 항목: `name`, `class`, `member`, `file`, `json_file`, `delimiter`, `key_type`, `key_name`,
+//!   `key_header`, `many`(자식 테이블), `value_type`, `enums`, `fields`
+//! - `enums` 항목: `enum`, `variants` (`ident`, `value`)
+//! - `fields` 항목: `name`, `type`, `header`, `headers`, `parse`, `summary`, `reference`, `is_key`
+//!
+//! 문자열 값(`file`, `header`, `value` 등)은 이미 따옴표로 감싼 리터럴이다.
+
+use anyhow::Result;
+
+use super::template::{render, Scope};
+use super::{
+    build_models, char_literal, escape_keyword, header_literals, pascal_case, quote, reference_text, CodegenOptions, FieldKind,
+    FieldModel, Names, TableModel,
+};
+use crate::app_state::DataSets;
+
+pub const DEFAULT_TEMPLATE: &str = include_str!("templates/csharp.cs.tpl");
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "base", "bool", "break", "byte", "case", "catch", "char", "checked", "class", "const",
+    "continue", "decimal", "default", "delegate", "do", "double", "else", "enum", "event", "explicit", "extern",
+    "false", "finally", "fixed", "float", "for", "foreach", "goto", "if", "implicit", "in", "int", "interface",
+    "internal", "is", "lock", "long", "namespace", "new", "null", "object", "operator", "out", "override", "params",
+    "private", "protected", "public", "readonly", "ref", "return", "sbyte", "sealed", "short", "sizeof",
+    "stackalloc", "static", "string", "struct", "switch", "this", "throw", "true", "try", "typeof", "uint", "ulong",
+    "unchecked", "unsafe", "ushort", "using", "virtual", "void", "volatile", "while",
+];
+
+/// 템플릿이 클래스 안에 만드는 멤버 (컬럼 이름과 겹치면 안 됨)
+const CLASS_MEMBERS: &[&str] = &["File", "JsonFile", "Delimiter", "Key", "FromRow", "LoadCsv", "LoadJson"];
+
+fn field_type(kind: &FieldKind) -> String {
+    match kind {
+        FieldKind::Int => "long".to_string(),
+        FieldKind::Float => "double".to_string(),
+        FieldKind::Bool => "bool".to_string(),
+        FieldKind::Text | FieldKind::Date => "string".to_string(),
+        FieldKind::Enum { type_name, .. } => escape_keyword(type_name, KEYWORDS),
+        FieldKind::List(_) => "string[]".to_string(),
+    }
+}
+
+/// `row`(IRow)에서 값을 읽는 식
+fn parse_expr(f: &FieldModel) -> String {
+    let names = format!("new[] {{ {} }}", header_literals(f));
+    let get = format!("row.Get({})", names);
+    match &f.kind {
+        FieldKind::Int => format!("Cells.Int({})", get),
+        FieldKind::Float => format!("Cells.Float({})", get),
+        FieldKind::Bool => format!("Cells.Bool({})", get),
+        FieldKind::Text | FieldKind::Date => get,
+        FieldKind::Enum { type_name, .. } => format!("{}Values.Parse({})", escape_keyword(type_name, KEYWORDS), get),
+        FieldKind::List(sep) => format!("row.List({}, {})", names, quote(sep)),
+    }
+}
+
+/// XML 문서 주석에 넣을 글
+fn xml_text(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace(['\r', '\n'], " ")
+}
+
+fn table_scope(m: &TableModel, member: String) -> Scope {
+    let class = escape_keyword(&m.type_name, KEYWORDS);
+    let reserved: Vec<&str> = CLASS_MEMBERS.iter().copied().chain([class.as_str()]).collect();
+    let mut names = Names::default();
+    let idents: Vec<String> = m
+        .fields
+        .iter()
+        .map(|f| {
+            let name = escape_keyword(&pascal_case(&f.ident, "Field"), KEYWORDS);
+            names.unique(escape_keyword(&name, &reserved))
+        })
+        .collect();
+
+    let enums = m
+        .enums()
+        .map(|(name, variants)| {
+            let variants = variants
+                .iter()
+                .map(|v| {
+                    let mut s = Scope::new();
+                    s.set("ident", escape_keyword(&v.ident, KEYWORDS)).set("value", quote(&v.value));
+                    s
+                })
+                .collect::<Vec<_>>();
+            let mut s = Scope::new();
+            s.set("enum", escape_keyword(name, KEYWORDS)).set("variants", variants);
+            s
+        })
+        .collect::<Vec<_>>();
+
+    let fields = m
+        .fields
+        .iter()
+        .zip(&idents)
+        .enumerate()
+        .map(|(i, (f, ident))| {
+            let mut s = Scope::new();
+            s.set("name", ident.as_str())
+                .set("type", field_type(&f.kind))
+                .set("header", quote(&f.header))
+                .set("headers", header_literals(f))
+                .set("parse", parse_expr(f))
+                .set("summary", if f.label == f.header { String::new() } else { xml_text(&f.label) })
+                .set("reference", f.reference.as_ref().map(|r| xml_text(&reference_text(r))).unwrap_or_default())
+                .set("is_key", i == m.key);
+            s
+        })
+        .collect::<Vec<_>>();
+
+    let value_type = if m.many { format!("List<{}>", class) } else { class.clone() };
+    let mut s = Scope::new();
+    s.set("name", m.name.as_str())
+        .set("class", class)
+        .set("member", member)
+        .set("file", quote(&m.file))
+        .set("json_file", quote(&format!("{}.json", m.name)))
+        .set("delimiter", char_literal(m.delimiter))
+        .set("key_type", field_type(&m.key_field().kind))
+        .set("key_name", idents[m.key].as_str())
+        .set("key_header", xml_text(&m.key_field().header))
+        .set("many", m.many)
+        .set("value_type", value_type)
+        .set("enums", enums)
+        .set("fields", fields);
+    s
+}
+
+/// 템플릿에 넘기는 값 전체
+pub fn scope(ds: &DataSets, options: &CodegenOptions) -> Scope {
+    let root = escape_keyword(&pascal_case(&options.root, "GameData"), KEYWORDS);
+    let mut members = Names::default();
+    for reserved in [root.as_str(), "LoadCsv", "LoadJson"] {
+        members.unique(reserved.to_string());
+    }
+    let tables = build_models(ds, options)
+        .iter()
+        .map(|m| table_scope(m, escape_keyword(&members.unique(m.type_name.clone()), KEYWORDS)))
+        .collect::<Vec<_>>();
+    let mut s = Scope::new();
+    s.set("namespace", pascal_case(&options.namespace, "GameTables")).set("root", root).set("tables", tables);
+    s
+}
+
+/// 기본 템플릿으로 생성
+pub fn generate(ds: &DataSets, options: &CodegenOptions) -> Result<String> {
+    generate_with(ds, options, DEFAULT_TEMPLATE)
+}
+
+/// 주어진 템플릿으로 생성
+pub fn generate_with(ds: &DataSets, options: &CodegenOptions, template: &str) -> Result<String> {
+    render(template, &scope(ds, options))
+}
@@ -0,0 +1,337 @@
+//! 스키마(`TableSchema`)에서 게임 코드용 타입/로더를 생성한다.
+//! 언어별 생성기는 같은 중간 모델(`TableModel`)을 쓴다: 이름 규칙, 타입 결정, 참조 해석은 여기서 한 번만.
+
+pub mod cpp;
+pub mod csharp;
+pub mod rust;
+pub mod template;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use super::app_state::DataSets;
+use super::dialect::TextEncoding;
+use super::schema::{ColumnRef, DataType};
+
+/// 생성 공통 설정
+#[derive(Debug, Clone)]
+pub struct CodegenOptions {
+    pub root: String,              // 모든 테이블을 묶는 타입 이름
+    pub namespace: String,         // C#/C++ 네임스페이스 (C++은 snake_case로)
+    pub base_dir: Option<PathBuf>, // 데이터 파일 경로를 이 폴더 기준 상대 경로로 기록 (없으면 파일 이름만)
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self { root: "GameData".to_string(), namespace: "GameTables".to_string(), base_dir: None }
+    }
+}
+
+/// 컬럼 하나의 생성용 타입
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldKind {
+    Int,
+    Float,
+    Bool,
+    Text,
+    Date, // 문자열로 전달 (YYYY-MM-DD 등 원본 표기)
+    Enum { type_name: String, variants: Vec<Variant> },
+    List(String), // 구분자
+}
+
+/// Enum 값 하나: (식별자, 파일에 쓰인 값)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+    pub ident: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct FieldModel {
+    pub header: String,       // 파일의 헤더명
+    pub aliases: Vec<String>, // 예전 헤더명
+    pub label: String,        // 표시 이름 (문서 주석용)
+    pub ident: String,        // 언어별 규칙을 적용하기 전의 snake_case 이름
+    pub kind: FieldKind,
+    pub reference: Option<ColumnRef>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TableModel {
+    pub name: String,      // TableSpec.name
+    pub type_name: String, // PascalCase (스키마 이름 기준)
+    pub ident: String,     // snake_case (묶음 타입의 필드명)
+    pub file: String,      // 데이터 파일 (base_dir 기준 상대 경로, '/' 구분)
+    pub delimiter: char,
+    pub encoding: TextEncoding, // 생성한 로더는 UTF-8로만 읽는다 (`encoding_warnings`)
+    pub fields: Vec<FieldModel>,
+    pub key: usize, // fields 안의 키 컬럼 위치 (자식 테이블은 부모 컬럼)
+    pub many: bool, // 자식 테이블: 키 하나에 행 여러 개 (로더는 키 -> 행 목록)
+}
+
+impl TableModel {
+    pub fn key_field(&self) -> &FieldModel {
+        &self.fields[self.key]
+    }
+
+    /// 이 테이블에서 정의하는 Enum 타입들
+    pub fn enums(&self) -> impl Iterator<Item = (&str, &[Variant])> {
+        self.fields.iter().filter_map(|f| match &f.kind {
+            FieldKind::Enum { type_name, variants } => Some((type_name.as_str(), variants.as_slice())),
+            _ => None,
+        })
+    }
+}
+
+/// 모든 테이블의 생성 모델 (계산 컬럼은 데이터 파일에 없으므로 제외)
+pub fn build_models(ds: &DataSets, options: &CodegenOptions) -> Vec<TableModel> {
+    let mut type_names = Names::default();
+    let mut table_idents = Names::default();
+    ds.tables
+        .iter()
+        .map(|t| {
+            let type_name = type_names.unique(pascal_case(&t.schema.name, "Table"));
+            let mut field_idents = Names::default();
+            let mut enum_names = Names::default();
+            let fields: Vec<FieldModel> = t
+                .schema
+                .columns
+                .iter()
+                .filter(|c| !c.is_computed())
+                .map(|c| {
+                    let kind = resolve_kind(ds, &c.dtype, 0);
+                    let kind = match kind {
+                        FieldKind::Enum { variants, .. } => FieldKind::Enum {
+                            type_name: enum_names.unique(format!("{}{}", type_name, pascal_case(&c.key, "Value"))),
+                            variants,
+                        },
+                        k => k,
+                    };
+                    FieldModel {
+                        header: c.key.clone(),
+                        aliases: c.aliases.clone(),
+                        label: c.label.clone(),
+                        ident: field_idents.unique(snake_case(&c.key, "field")),
+                        kind,
+                        reference: match &c.dtype {
+                            DataType::Reference(r) => Some(r.clone()),
+                            _ => None,
+                        },
+                    }
+                })
+                .collect();
+            let key_column = t.schema.parent_column.as_ref().unwrap_or(&t.schema.key_column);
+            let key = fields.iter().position(|f| f.header == *key_column).unwrap_or(0);
+            let mut model = TableModel {
+                name: t.spec.name.clone(),
+                ident: table_idents.unique(snake_case(&t.schema.name, "table")),
+                type_name,
+                file: relative_file(&t.spec.path, options.base_dir.as_deref()),
+                delimiter: t.schema.dialect.delimiter,
+                encoding: t.schema.dialect.encoding,
+                fields,
+                key,
+                many: t.is_child(),
+            };
+            // 맵의 키는 정렬 가능한 타입만: 정수/Enum/문자열
+            if let Some(f) = model.fields.get_mut(key) {
+                if !matches!(f.kind, FieldKind::Int | FieldKind::Enum { .. }) {
+                    f.kind = FieldKind::Text;
+                }
+            }
+            model
+        })
+        .collect()
+}
+
+/// UTF-8이 아닌 데이터 파일: 생성한 로더(Rust/C#/C++)는 UTF-8로 읽으므로 글자가 깨지거나 로드에 실패한다
+pub fn encoding_warnings(models: &[TableModel]) -> Vec<String> {
+    models
+        .iter()
+        .filter(|m| m.encoding != TextEncoding::Utf8)
+        .map(|m| {
+            format!(
+                "{}: 데이터 파일이 {}입니다 — 생성한 로더는 UTF-8로만 읽으므로 파일 인코딩을 UTF-8로 바꾸세요 ({})",
+                m.name,
+                m.encoding.label(),
+                m.file
+            )
+        })
+        .collect()
+}
+
+/// 참조 컬럼은 대상 컬럼의 타입을 따른다 (대상을 못 찾으면 문자열)
+fn resolve_kind(ds: &DataSets, dtype: &DataType, depth: usize) -> FieldKind {
+    match dtype {
+        DataType::Int => FieldKind::Int,
+        DataType::Float => FieldKind::Float,
+        DataType::Bool => FieldKind::Bool,
+        DataType::Text => FieldKind::Text,
+        DataType::Date => FieldKind::Date,
+        DataType::List(sep) => FieldKind::List(sep.clone()),
+        DataType::Enum(values) if values.is_empty() => FieldKind::Text,
+        DataType::Enum(values) => {
+            let mut idents = Names::default();
+            let variants = values
+                .iter()
+                .map(|v| Variant { ident: idents.unique(pascal_case(v, "Value")), value: v.clone() })
+                .collect();
+            FieldKind::Enum { type_name: String::new(), variants }
+        }
+        DataType::Reference(r) => {
+            let target = ds.table(&r.table).and_then(|t| {
+                let column = if r.column.is_empty() { &t.schema.key_column } else { &r.column };
+                t.schema.find(column)
+            });
+            // 대상 키가 정수면 정수, 그 외(Enum 포함)는 문자열. 참조가 돌고 도는 경우를 위해 깊이 제한.
+            match target {
+                Some(c) if depth < 4 => {
+                    match resolve_kind(ds, &c.dtype, depth + 1) {
+                        FieldKind::Int => FieldKind::Int,
+                        _ => FieldKind::Text,
+                    }
+                }
+                _ => FieldKind::Text,
+            }
+        }
+    }
+}
+
+fn relative_file(path: &str, base: Option<&Path>) -> String {
+    let p = Path::new(path);
+    let rel = match base {
+        Some(base) => p.strip_prefix(base).map(Path::to_path_buf).unwrap_or_else(|_| p.to_path_buf()),
+        None => PathBuf::from(p.file_name().unwrap_or_default()),
+    };
+    rel.to_string_lossy().replace('\\', "/")
+}
+
+// ===== 이름 규칙 =====
+
+/// 헤더/테이블 이름을 단어로 나눈다: "CharacterUnique" / "attack_power" / "HPMax2" -> [Character, Unique] ...
+/// 영문/숫자가 아닌 글자(한글 포함)는 구분자로 본다.
+pub fn split_words(s: &str) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut words = Vec::new();
+    let mut cur = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !cur.is_empty() {
+                words.push(std::mem::take(&mut cur));
+            }
+            continue;
+        }
+        let prev = i.checked_sub(1).map(|j| chars[j]);
+        let next = chars.get(i + 1);
+        let boundary = match prev {
+            Some(p) if c.is_ascii_uppercase() => {
+                p.is_ascii_lowercase() || p.is_ascii_digit() || (p.is_ascii_uppercase() && next.is_some_and(|n| n.is_ascii_lowercase()))
+            }
+            _ => false,
+        };
+        if boundary && !cur.is_empty() {
+            words.push(std::mem::take(&mut cur));
+        }
+        cur.push(c);
+    }
+    if !cur.is_empty() {
+        words.push(cur);
+    }
+    words
+}
+
+/// PascalCase. 쓸 글자가 없으면 `fallback`, 숫자로 시작하면 `fallback`을 앞에 붙인다.
+pub fn pascal_case(s: &str, fallback: &str) -> String {
+    let out: String = split_words(s)
+        .iter()
+        .map(|w| {
+            let lower = w.to_ascii_lowercase();
+            let mut cs = lower.chars();
+            cs.next().map(|f| f.to_ascii_uppercase().to_string() + cs.as_str()).unwrap_or_default()
+        })
+        .collect();
+    fix_start(out, fallback)
+}
+
+/// snake_case. 규칙은 `pascal_case`와 같다.
+pub fn snake_case(s: &str, fallback: &str) -> String {
+    let out = split_words(s).iter().map(|w| w.to_ascii_lowercase()).collect::<Vec<_>>().join("_");
+    fix_start(out, fallback)
+}
+
+fn fix_start(s: String, fallback: &str) -> String {
+    match s.chars().next() {
+        None => fallback.to_string(),
+        Some(c) if c.is_ascii_digit() => format!("{}{}", fallback, s),
+        _ => s,
+    }
+}
+
+/// 같은 범위에서 겹치는 이름에 _2, _3 ...
+#[derive(Default)]
+pub(crate) struct Names(HashSet<String>);
+
+impl Names {
+    pub(crate) fn unique(&mut self, base: String) -> String {
+        let mut name = base.clone();
+        let mut n = 2;
+        while !self.0.insert(name.clone()) {
+            name = format!("{}_{}", base, n);
+            n += 1;
+        }
+        name
+    }
+}
+
+/// 참조 설명 (문서 주석용): `info.CharacterUnique`, 대상 컬럼이 비었으면 `info.<key>`
+pub(crate) fn reference_text(r: &ColumnRef) -> String {
+    let column = if r.column.is_empty() { "<key>" } else { &r.column };
+    format!("{}.{}", r.table, column)
+}
+
+/// 헤더명과 예전 헤더명을 문자열 리터럴로: `"Job", "Class"`
+pub(crate) fn header_literals(f: &FieldModel) -> String {
+    std::iter::once(&f.header).chain(&f.aliases).map(|h| quote(h)).collect::<Vec<_>>().join(", ")
+}
+
+/// 언어 예약어와 겹치면 뒤에 `_`
+pub fn escape_keyword(ident: &str, keywords: &[&str]) -> String {
+    if keywords.contains(&ident) {
+        format!("{}_", ident)
+    } else {
+        ident.to_string()
+    }
+}
+
+/// 문자 리터럴 (Rust/C#/C++ 공통)
+pub(crate) fn char_literal(c: char) -> String {
+    match c {
+        '\t' => "'\\t'".to_string(),
+        '\'' => "'\\''".to_string(),
+        '\\' => "'\\\\'".to_string(),
+        c => format!("'{}'", c),
+    }
+}
+
+/// 한 바이트 문자 리터럴. ASCII가 아닌 구분자는 ',' (TableSpec::delimiter_byte와 같은 규칙)
+pub(crate) fn byte_literal(c: char) -> String {
+    char_literal(if c.is_ascii() { c } else { ',' })
+}
+
+/// 문자열 리터럴 (Rust/C#/C++ 공통으로 안전한 이스케이프)
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
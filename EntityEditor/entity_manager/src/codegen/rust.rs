@@ -0,0 +1,305 @@
+//! Rust 코드 생성: 테이블마다 serde 구조체 + csv 로더, 전체를 묶는 타입 하나.
+//! 생성된 코드는 `serde`(derive)와 `csv` 크레이트만 필요하다.
+//!
+//! build.rs 예:
+//! ```no_run
+//! // build.rs
+//! entity_manager::codegen::rust::build_rs("data/project.json", "game_data.rs").unwrap();
+//! // lib.rs
+//! // mod game_data { include!(concat!(env!("OUT_DIR"), "/game_data.rs")); }
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write as _,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+
+use super::{
+    build_models, byte_literal, escape_keyword, quote, reference_text, CodegenOptions, FieldKind, FieldModel, TableModel,
+};
+use crate::app_state::DataSets;
+use crate::project::{project_dir, ProjectFile};
+use crate::storage::{schema_sidecar_path, write_files, FileWrite};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn",
+    "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "Self",
+    "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while", "abstract", "become",
+    "box", "do", "final", "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+fn ident(s: &str) -> String {
+    escape_keyword(s, KEYWORDS)
+}
+
+fn field_type(kind: &FieldKind) -> String {
+    match kind {
+        FieldKind::Int => "i64".to_string(),
+        FieldKind::Float => "f64".to_string(),
+        FieldKind::Bool => "bool".to_string(),
+        FieldKind::Text | FieldKind::Date => "String".to_string(),
+        FieldKind::Enum { type_name, .. } => ident(type_name),
+        FieldKind::List(_) => "Vec<String>".to_string(),
+    }
+}
+
+/// 목록 구분자별 (역)직렬화 함수 이름: 구분자 -> 번호
+fn list_separators(models: &[TableModel]) -> BTreeMap<String, usize> {
+    let mut seps = BTreeMap::new();
+    for f in models.iter().flat_map(|m| &m.fields) {
+        if let FieldKind::List(sep) = &f.kind {
+            let n = seps.len();
+            seps.entry(sep.clone()).or_insert(n);
+        }
+    }
+    seps
+}
+
+/// `#[serde(...)]` 인자들
+fn serde_attrs(f: &FieldModel, is_key: bool, seps: &BTreeMap<String, usize>) -> Vec<String> {
+    let mut attrs = vec![format!("rename = {}", quote(&f.header))];
+    attrs.extend(f.aliases.iter().map(|a| format!("alias = {}", quote(a))));
+    if !is_key {
+        attrs.push("default".to_string()); // 파일에 컬럼이 없으면 기본값
+    }
+    match &f.kind {
+        FieldKind::Int | FieldKind::Float => attrs.push("deserialize_with = \"de_parse\"".to_string()),
+        FieldKind::Bool => attrs.push("deserialize_with = \"de_bool\"".to_string()),
+        FieldKind::Enum { .. } => attrs.push("deserialize_with = \"de_enum\"".to_string()),
+        FieldKind::List(sep) => {
+            let n = seps[sep];
+            attrs.push(format!("deserialize_with = \"de_list_{n}\", serialize_with = \"se_list_{n}\""));
+        }
+        FieldKind::Text | FieldKind::Date => {}
+    }
+    attrs
+}
+
+/// 모든 테이블의 Rust 소스
+pub fn generate(ds: &DataSets, options: &CodegenOptions) -> String {
+    let models = build_models(ds, options);
+    let seps = list_separators(&models);
+    let mut out = String::new();
+    let w = &mut out;
+
+    let _ = writeln!(w, "// @generated by entity_manager::codegen::rust — 직접 고치지 말고 스키마를 고친 뒤 다시 생성하세요.");
+    for m in &models {
+        let _ = writeln!(w, "// {} <- {}", m.type_name, m.file);
+    }
+    let _ = writeln!(w);
+    let _ = writeln!(w, "use std::collections::BTreeMap;");
+    let _ = writeln!(w, "use std::path::Path;");
+    let _ = writeln!(w);
+    let _ = writeln!(w, "use serde::{{Deserialize, Serialize}};");
+
+    for m in &models {
+        for (name, variants) in m.enums() {
+            let _ = writeln!(w);
+            let _ = writeln!(w, "#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]");
+            let _ = writeln!(w, "pub enum {} {{", ident(name));
+            for (i, v) in variants.iter().enumerate() {
+                if i == 0 {
+                    let _ = writeln!(w, "    #[default]");
+                }
+                let _ = writeln!(w, "    #[serde(rename = {})]", quote(&v.value));
+                let _ = writeln!(w, "    {},", ident(&v.ident));
+            }
+            let _ = writeln!(w, "}}");
+        }
+        write_struct(w, m, &seps);
+    }
+    write_root(w, &models, &options.root);
+    write_helpers(w, &models, &seps);
+    out
+}
+
+/// 로더가 돌려주는 맵 타입: 키 -> 행 `ty` (자식 테이블은 키 -> 행 목록)
+fn map_type(m: &TableModel, ty: &str) -> String {
+    let key_type = field_type(&m.key_field().kind);
+    if m.many {
+        format!("BTreeMap<{}, Vec<{}>>", key_type, ty)
+    } else {
+        format!("BTreeMap<{}, {}>", key_type, ty)
+    }
+}
+
+fn write_struct(w: &mut String, m: &TableModel, seps: &BTreeMap<String, usize>) {
+    let key = m.key_field();
+    let map = map_type(m, "Self");
+    let ty = ident(&m.type_name);
+
+    let _ = writeln!(w);
+    let _ = writeln!(w, "/// `{}` 테이블 한 행 (키: `{}`)", m.name, key.header);
+    let _ = writeln!(w, "#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]");
+    let _ = writeln!(w, "pub struct {} {{", ty);
+    for (i, f) in m.fields.iter().enumerate() {
+        if f.label != f.header {
+            let _ = writeln!(w, "    /// {}", f.label);
+        }
+        if let Some(r) = &f.reference {
+            let _ = writeln!(w, "    /// 참조: {}", reference_text(r));
+        }
+        let _ = writeln!(w, "    #[serde({})]", serde_attrs(f, i == m.key, seps).join(", "));
+        let _ = writeln!(w, "    pub {}: {},", ident(&f.ident), field_type(&f.kind));
+    }
+    let _ = writeln!(w, "}}");
+
+    let _ = writeln!(w);
+    let _ = writeln!(w, "impl {} {{", ty);
+    let _ = writeln!(w, "    /// 데이터 파일 (생성 시점의 경로)");
+    let _ = writeln!(w, "    pub const FILE: &str = {};", quote(&m.file));
+    let _ = writeln!(w, "    pub const DELIMITER: u8 = b{};", byte_literal(m.delimiter));
+    let _ = writeln!(w);
+    if m.many {
+        let _ = writeln!(w, "    /// `{}` -> 행들 (파일 순서)", key.header);
+    } else {
+        let _ = writeln!(w, "    /// 키 -> 행. 같은 키가 여러 번 나오면 마지막 행이 남는다.");
+    }
+    let _ = writeln!(w, "    pub fn load(path: impl AsRef<Path>) -> Result<{}, csv::Error> {{", map);
+    let _ = writeln!(w, "        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_path(path)?)");
+    let _ = writeln!(w, "    }}");
+    let _ = writeln!(w);
+    let _ = writeln!(w, "    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<{}, csv::Error> {{", map);
+    let _ = writeln!(w, "        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_reader(reader))");
+    let _ = writeln!(w, "    }}");
+    let _ = writeln!(w);
+    let _ = writeln!(w, "    fn read<R: std::io::Read>(mut reader: csv::Reader<R>) -> Result<{}, csv::Error> {{", map);
+    let _ = writeln!(w, "        let mut rows = BTreeMap::new();");
+    let _ = writeln!(w, "        for row in reader.deserialize() {{");
+    let _ = writeln!(w, "            let row: Self = row?;");
+    let clone = if matches!(key.kind, FieldKind::Text) { ".clone()" } else { "" }; // 정수/Enum 키는 Copy
+    if m.many {
+        let _ = writeln!(w, "            rows.entry(row.{}{}).or_insert_with(Vec::new).push(row);", ident(&key.ident), clone);
+    } else {
+        let _ = writeln!(w, "            rows.insert(row.{}{}, row);", ident(&key.ident), clone);
+    }
+    let _ = writeln!(w, "        }}");
+    let _ = writeln!(w, "        Ok(rows)");
+    let _ = writeln!(w, "    }}");
+    let _ = writeln!(w, "}}");
+}
+
+fn write_root(w: &mut String, models: &[TableModel], root: &str) {
+    let root = ident(root);
+    let _ = writeln!(w);
+    let _ = writeln!(w, "/// 모든 테이블");
+    let _ = writeln!(w, "#[derive(Debug, Clone, Default, PartialEq)]");
+    let _ = writeln!(w, "pub struct {} {{", root);
+    for m in models {
+        let _ = writeln!(w, "    pub {}: {},", ident(&m.ident), map_type(m, &ident(&m.type_name)));
+    }
+    let _ = writeln!(w, "}}");
+    let _ = writeln!(w);
+    let _ = writeln!(w, "impl {} {{", root);
+    let _ = writeln!(w, "    /// `dir` 기준으로 각 테이블의 `FILE`을 읽는다");
+    let _ = writeln!(w, "    pub fn load(dir: impl AsRef<Path>) -> Result<Self, csv::Error> {{");
+    let _ = writeln!(w, "        let dir = dir.as_ref();");
+    let _ = writeln!(w, "        Ok(Self {{");
+    for m in models {
+        let ty = ident(&m.type_name);
+        let _ = writeln!(w, "            {}: {}::load(dir.join({}::FILE))?,", ident(&m.ident), ty, ty);
+    }
+    let _ = writeln!(w, "        }})");
+    let _ = writeln!(w, "    }}");
+    let _ = writeln!(w, "}}");
+}
+
+const DE_PARSE: &str = r#"
+fn de_parse<'de, D, T>(d: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr + Default,
+    T::Err: std::fmt::Display,
+{
+    let s = String::deserialize(d)?;
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(T::default());
+    }
+    s.parse().map_err(serde::de::Error::custom)
+}
+"#;
+
+const DE_BOOL: &str = r#"
+fn de_bool<'de, D: serde::Deserializer<'de>>(d: D) -> Result<bool, D::Error> {
+    let s = String::deserialize(d)?;
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "false" | "0" | "no" | "n" => Ok(false),
+        "true" | "1" | "yes" | "y" => Ok(true),
+        other => Err(serde::de::Error::custom(format!("'{}' is not a bool", other))),
+    }
+}
+"#;
+
+const DE_ENUM: &str = r#"
+fn de_enum<'de, D, T>(d: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned + Default,
+{
+    use serde::de::IntoDeserializer;
+    let s = String::deserialize(d)?;
+    if s.is_empty() {
+        return Ok(T::default());
+    }
+    T::deserialize(s.into_deserializer())
+}
+"#;
+
+/// 실제로 쓰는 보조 함수만 (쓰지 않는 함수는 생성된 크레이트에서 경고가 된다).
+/// 빈 셀은 기본값, Bool은 true/false/1/0/yes/no/y/n (에디터와 같은 규칙)
+fn write_helpers(w: &mut String, models: &[TableModel], seps: &BTreeMap<String, usize>) {
+    let kinds: Vec<&FieldKind> = models.iter().flat_map(|m| &m.fields).map(|f| &f.kind).collect();
+    if kinds.iter().any(|k| matches!(k, FieldKind::Int | FieldKind::Float)) {
+        w.push_str(DE_PARSE);
+    }
+    if kinds.iter().any(|k| matches!(k, FieldKind::Bool)) {
+        w.push_str(DE_BOOL);
+    }
+    if kinds.iter().any(|k| matches!(k, FieldKind::Enum { .. })) {
+        w.push_str(DE_ENUM);
+    }
+    for (sep, n) in seps {
+        let sep = quote(sep);
+        let _ = write!(
+            w,
+            r#"
+fn de_list_{n}<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {{
+    let s = String::deserialize(d)?;
+    Ok(if s.is_empty() {{ Vec::new() }} else {{ s.split({sep}).map(str::to_string).collect() }})
+}}
+
+fn se_list_{n}<S: serde::Serializer>(v: &[String], s: S) -> Result<S::Ok, S::Error> {{
+    s.serialize_str(&v.join({sep}))
+}}
+"#
+        );
+    }
+}
+
+/// 프로젝트 파일의 테이블 (데이터 파일 경로는 프로젝트 폴더 기준으로 기록)
+fn load_project(project: &str) -> Result<(DataSets, CodegenOptions)> {
+    let p = ProjectFile::load(project)?;
+    let ds = DataSets::load(&p.status_key_mode.apply(&p.tables)).with_context(|| format!("load {}", project))?;
+    let options = CodegenOptions { base_dir: Some(project_dir(project)), ..Default::default() };
+    Ok((ds, options))
+}
+
+/// build.rs용: `OUT_DIR/<file_name>`에 생성하고(내용이 같으면 쓰지 않음),
+/// 프로젝트/데이터/사이드카 파일이 바뀌면 다시 돌도록 cargo에 알린다.
+pub fn build_rs(project: &str, file_name: &str) -> Result<PathBuf> {
+    let out_dir = std::env::var_os("OUT_DIR").context("OUT_DIR 없음 (build.rs에서 호출하세요)")?;
+    let (ds, options) = load_project(project)?;
+    println!("cargo:rerun-if-changed={}", project);
+    for t in &ds.tables {
+        println!("cargo:rerun-if-changed={}", t.spec.path);
+        println!("cargo:rerun-if-changed={}", schema_sidecar_path(&t.spec.path).display());
+    }
+    let path = Path::new(&out_dir).join(file_name);
+    let file = FileWrite { path: path.to_string_lossy().to_string(), bytes: generate(&ds, &options).into_bytes(), backup: false };
+    write_files(&[file], 0)?;
+    Ok(path)
+}
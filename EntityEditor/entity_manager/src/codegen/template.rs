@@ -0,0 +1,178 @@
+//! 코드 생성용 작은 템플릿 (mustache 비슷한 문법, 이스케이프 없음)
+//!
+//! - `{{name}}`: 값 치환. 안쪽 범위부터 바깥 범위 순으로 찾는다.
+//! - `{{#name}} ... {{/name}}`: 목록이면 항목마다 반복, 참/빈 문자열이 아니면 한 번.
+//!   목록 항목 안에서는 `first`, `last`도 쓸 수 있다.
+//! - `{{^name}} ... {{/name}}`: 거짓/빈 목록/빈 문자열일 때만.
+//! - `{{! 주석 }}`
+//!
+//! 섹션/주석 태그만 있는 줄은 줄째로 없어진다.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+
+/// 템플릿에 넘기는 값
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Bool(bool),
+    List(Vec<Scope>),
+}
+
+impl Value {
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Text(s) => !s.is_empty(),
+            Value::Bool(b) => *b,
+            Value::List(items) => !items.is_empty(),
+        }
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::Text(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::Text(s.to_string())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Bool(b)
+    }
+}
+
+impl From<Vec<Scope>> for Value {
+    fn from(items: Vec<Scope>) -> Self {
+        Value::List(items)
+    }
+}
+
+/// 이름 -> 값
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Scope(BTreeMap<String, Value>);
+
+impl Scope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, name: &str, value: impl Into<Value>) -> &mut Self {
+        self.0.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.0.get(name)
+    }
+}
+
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Var(String),
+    Section { name: String, inverted: bool, body: Vec<Node> },
+}
+
+/// 템플릿을 그린다. 없는 이름이나 닫히지 않은 섹션은 오류.
+pub fn render(template: &str, scope: &Scope) -> Result<String> {
+    let nodes = parse(&strip_standalone(template))?;
+    let mut out = String::new();
+    render_nodes(&nodes, &[scope], &mut out)?;
+    Ok(out)
+}
+
+/// 섹션/주석 태그 하나만 있는 줄은 줄바꿈까지 지운다 (태그는 남김)
+fn strip_standalone(template: &str) -> String {
+    let mut out = String::with_capacity(template.len());
+    for line in template.split_inclusive('\n') {
+        let t = line.trim();
+        let standalone = t.starts_with("{{")
+            && t.ends_with("}}")
+            && t[2..].starts_with(['#', '^', '/', '!'])
+            && t.matches("{{").count() == 1;
+        out.push_str(if standalone { t } else { line });
+    }
+    out
+}
+
+fn parse(template: &str) -> Result<Vec<Node>> {
+    // (섹션 이름, 반전 여부, 지금까지의 노드) 스택
+    let mut stack: Vec<(String, bool, Vec<Node>)> = vec![(String::new(), false, Vec::new())];
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else { bail!("닫히지 않은 태그: {}", &rest[start..]) };
+        let text = &rest[..start];
+        let tag = rest[start + 2..start + len].trim();
+        rest = &rest[start + len + 2..];
+        let nodes = &mut stack.last_mut().expect("루트는 항상 있음").2;
+        if !text.is_empty() {
+            nodes.push(Node::Text(text.to_string()));
+        }
+        match tag.chars().next() {
+            Some('!') => {}
+            Some(c @ ('#' | '^')) => stack.push((tag[1..].trim().to_string(), c == '^', Vec::new())),
+            Some('/') => {
+                let name = tag[1..].trim();
+                let (open, inverted, body) = stack.pop().expect("루트는 항상 있음");
+                if stack.is_empty() || open != name {
+                    bail!("{{{{/{}}}}}에 맞는 여는 태그가 없습니다", name);
+                }
+                let parent = &mut stack.last_mut().expect("방금 확인함").2;
+                parent.push(Node::Section { name: open, inverted, body });
+            }
+            _ => nodes.push(Node::Var(tag.to_string())),
+        }
+    }
+    let (open, _, mut nodes) = stack.pop().expect("루트는 항상 있음");
+    if !stack.is_empty() {
+        bail!("{{{{#{}}}}}가 닫히지 않았습니다", open);
+    }
+    if !rest.is_empty() {
+        nodes.push(Node::Text(rest.to_string()));
+    }
+    Ok(nodes)
+}
+
+fn lookup<'a>(scopes: &[&'a Scope], name: &str) -> Result<&'a Value> {
+    scopes.iter().rev().find_map(|s| s.get(name)).ok_or_else(|| anyhow::anyhow!("템플릿 변수 '{}' 없음", name))
+}
+
+fn render_nodes(nodes: &[Node], scopes: &[&Scope], out: &mut String) -> Result<()> {
+    for node in nodes {
+        match node {
+            Node::Text(t) => out.push_str(t),
+            Node::Var(name) => match lookup(scopes, name)? {
+                Value::Text(s) => out.push_str(s),
+                Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+                Value::List(_) => bail!("'{}'는 목록이라 섹션으로 써야 합니다", name),
+            },
+            Node::Section { name, inverted: true, body } => {
+                if !lookup(scopes, name)?.truthy() {
+                    render_nodes(body, scopes, out)?;
+                }
+            }
+            Node::Section { name, inverted: false, body } => match lookup(scopes, name)? {
+                Value::List(items) => {
+                    for (i, item) in items.iter().enumerate() {
+                        // first/last는 항목마다 새로 만든 범위에 둔다
+                        let mut item = item.clone();
+                        item.set("first", i == 0).set("last", i + 1 == items.len());
+                        let mut inner = scopes.to_vec();
+                        inner.push(&item);
+                        render_nodes(body, &inner, out)?;
+                    }
+                }
+                v if v.truthy() => render_nodes(body, scopes, out)?,
+                _ => {}
+            },
+        }
+    }
+    Ok(())
+}
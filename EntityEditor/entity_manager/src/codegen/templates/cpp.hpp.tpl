@@ -0,0 +1,279 @@
+{{! 기본 C++ 템플릿 (C++17, 헤더 하나). 변수 목록은 codegen::cpp 모듈 문서 참고. }}
+// @generated by entity_manager codegen (C++) — 직접 고치지 말고 스키마를 고친 뒤 다시 생성하세요.
+// JSON 로더는 nlohmann/json이 include 경로에 있을 때만 켜진다.
+#pragma once
+
+#include <cctype>
+#include <cstdint>
+#include <fstream>
+#include <initializer_list>
+#include <map>
+#include <sstream>
+#include <stdexcept>
+#include <string>
+#include <vector>
+
+#if __has_include(<nlohmann/json.hpp>)
+#include <nlohmann/json.hpp>
+#define {{guard}}_JSON 1
+#endif
+
+namespace {{namespace}} {
+
+using Names = std::initializer_list<const char*>;
+
+namespace cells {
+
+inline std::int64_t to_int(const std::string& s) {
+    return s.find_first_not_of(" \t") == std::string::npos ? 0 : std::stoll(s);
+}
+
+inline double to_float(const std::string& s) {
+    return s.find_first_not_of(" \t") == std::string::npos ? 0.0 : std::stod(s);
+}
+
+inline bool to_bool(std::string s) {
+    std::string t;
+    for (char c : s) {
+        if (c != ' ' && c != '\t') t += static_cast<char>(std::tolower(static_cast<unsigned char>(c)));
+    }
+    if (t.empty() || t == "false" || t == "0" || t == "no" || t == "n") return false;
+    if (t == "true" || t == "1" || t == "yes" || t == "y") return true;
+    throw std::invalid_argument("'" + s + "' is not a bool");
+}
+
+inline std::vector<std::string> split(const std::string& s, const std::string& sep) {
+    std::vector<std::string> out;
+    if (s.empty()) return out;
+    std::size_t start = 0, at;
+    while ((at = s.find(sep, start)) != std::string::npos) {
+        out.push_back(s.substr(start, at - start));
+        start = at + sep.size();
+    }
+    out.push_back(s.substr(start));
+    return out;
+}
+
+/// CSV 한 행 (헤더명, 예전 헤더명 순으로 찾고 없으면 빈 값)
+class CsvRow {
+public:
+    CsvRow(const std::vector<std::string>& header, const std::vector<std::string>& cells)
+        : header_(header), cells_(cells) {}
+
+    std::string get(Names names) const {
+        for (const char* name : names) {
+            for (std::size_t i = 0; i < header_.size(); ++i) {
+                if (header_[i] == name) return i < cells_.size() ? cells_[i] : std::string();
+            }
+        }
+        return std::string();
+    }
+
+    std::vector<std::string> list(Names names, const std::string& sep) const { return split(get(names), sep); }
+
+private:
+    const std::vector<std::string>& header_;
+    const std::vector<std::string>& cells_;
+};
+
+/// UTF-8 CSV ("" 이스케이프, 따옴표 안 줄바꿈 허용)
+inline std::vector<std::vector<std::string>> parse_csv(const std::string& text, char delimiter) {
+    std::vector<std::vector<std::string>> records;
+    std::vector<std::string> record;
+    std::string cell;
+    bool quoted = false, any = false;
+    for (std::size_t i = 0; i < text.size(); ++i) {
+        char c = text[i];
+        if (quoted) {
+            if (c != '"') cell += c;
+            else if (i + 1 < text.size() && text[i + 1] == '"') { cell += '"'; ++i; }
+            else quoted = false;
+            continue;
+        }
+        if (c == '"') { quoted = true; any = true; }
+        else if (c == delimiter) { record.push_back(cell); cell.clear(); any = true; }
+        else if (c == '\n' || c == '\r') {
+            if (c == '\r' && i + 1 < text.size() && text[i + 1] == '\n') ++i;
+            if (any || !cell.empty()) { record.push_back(cell); records.push_back(record); }
+            record.clear();
+            cell.clear();
+            any = false;
+        }
+        else { cell += c; any = true; }
+    }
+    if (any || !cell.empty()) { record.push_back(cell); records.push_back(record); }
+    if (!records.empty() && !records[0].empty() && records[0][0].rfind("\xEF\xBB\xBF", 0) == 0) {
+        records[0][0].erase(0, 3);
+    }
+    return records;
+}
+
+inline std::string read_file(const std::string& path) {
+    std::ifstream in(path, std::ios::binary);
+    if (!in) throw std::runtime_error("cannot open " + path);
+    std::ostringstream ss;
+    ss << in.rdbuf();
+    return ss.str();
+}
+
+/// 키 -> 행. 같은 키가 여러 번 나오면 마지막 행이 남는다.
+template <class K, class T>
+void put(std::map<K, T>& rows, const T& row) {
+    rows[row.key()] = row;
+}
+
+/// 자식 테이블: 부모 키 -> 행들 (파일 순서)
+template <class K, class T>
+void put(std::map<K, std::vector<T>>& rows, const T& row) {
+    rows[row.key()].push_back(row);
+}
+
+template <class T>
+typename T::Rows load_csv(const std::string& path, char delimiter) {
+    typename T::Rows rows;
+    auto records = parse_csv(read_file(path), delimiter);
+    for (std::size_t i = 1; i < records.size(); ++i) {
+        put(rows, T::from_row(CsvRow(records[0], records[i])));
+    }
+    return rows;
+}
+
+#ifdef {{guard}}_JSON
+/// 내보내기 JSON 한 레코드
+class JsonRow {
+public:
+    explicit JsonRow(const nlohmann::json& row) : row_(row) {}
+
+    std::string get(Names names) const {
+        const nlohmann::json* v = find(names);
+        if (!v) return std::string();
+        return v->is_string() ? v->get<std::string>() : v->dump();
+    }
+
+    std::vector<std::string> list(Names names, const std::string& sep) const {
+        const nlohmann::json* v = find(names);
+        if (v && v->is_array()) return v->get<std::vector<std::string>>();
+        return split(v && v->is_string() ? v->get<std::string>() : std::string(), sep);
+    }
+
+private:
+    const nlohmann::json* find(Names names) const {
+        for (const char* name : names) {
+            auto it = row_.find(name);
+            if (it != row_.end() && !it->is_null()) return &*it;
+        }
+        return nullptr;
+    }
+
+    const nlohmann::json& row_;
+};
+
+/// 내보내기 JSON(레코드 배열) 또는 변환 JSON({ "rows": [...] })
+template <class T>
+typename T::Rows load_json(const std::string& path) {
+    typename T::Rows rows;
+    nlohmann::json root = nlohmann::json::parse(read_file(path));
+    const nlohmann::json& records = root.is_object() && root.contains("rows") ? root["rows"] : root;
+    for (const auto& record : records) {
+        put(rows, T::from_row(JsonRow(record)));
+    }
+    return rows;
+}
+#endif
+
+}  // namespace cells
+{{#tables}}
+{{#enums}}
+
+enum class {{enum}} {
+{{#variants}}
+    {{ident}},
+{{/variants}}
+};
+
+inline {{enum}} parse_{{enum_fn}}(const std::string& s) {
+    if (s.empty()) return {{enum}}{};
+{{#variants}}
+    if (s == {{value}}) return {{enum}}::{{ident}};
+{{/variants}}
+    throw std::invalid_argument("'" + s + "' is not a {{enum}}");
+}
+
+inline const char* to_string({{enum}} v) {
+    switch (v) {
+{{#variants}}
+        case {{enum}}::{{ident}}: return {{value}};
+{{/variants}}
+    }
+    return "";
+}
+{{/enums}}
+
+/// `{{name}}` 테이블 한 행 (키: {{key_header}})
+struct {{struct}} {
+    using Key = {{key_type}};
+    using Rows = {{rows_type}};
+    static constexpr const char* kFile = {{file}};
+    static constexpr const char* kJsonFile = {{json_file}};
+    static constexpr char kDelimiter = {{delimiter}};
+{{#fields}}
+{{#summary}}
+    /// {{summary}}
+{{/summary}}
+{{#reference}}
+    /// 참조: {{reference}}
+{{/reference}}
+    {{type}} {{name}}{};
+{{/fields}}
+
+    Key key() const { return {{key_name}}; }
+
+    template <class Row>
+    static {{struct}} from_row(const Row& row) {
+        {{struct}} r;
+{{#fields}}
+        r.{{name}} = {{parse}};
+{{/fields}}
+        return r;
+    }
+
+    static Rows load_csv(const std::string& path) {
+        return cells::load_csv<{{struct}}>(path, kDelimiter);
+    }
+#ifdef {{guard}}_JSON
+
+    static Rows load_json(const std::string& path) {
+        return cells::load_json<{{struct}}>(path);
+    }
+#endif
+};
+{{/tables}}
+
+/// 모든 테이블
+struct {{root}} {
+{{#tables}}
+    {{struct}}::Rows {{member}};
+{{/tables}}
+
+    /// `dir` 기준으로 각 테이블의 kFile을 읽는다
+    static {{root}} load_csv(const std::string& dir) {
+        {{root}} data;
+{{#tables}}
+        data.{{member}} = {{struct}}::load_csv(dir + "/" + {{struct}}::kFile);
+{{/tables}}
+        return data;
+    }
+#ifdef {{guard}}_JSON
+
+    /// 내보내기(JSON) 폴더에서 각 테이블의 kJsonFile을 읽는다
+    static {{root}} load_json(const std::string& dir) {
+        {{root}} data;
+{{#tables}}
+        data.{{member}} = {{struct}}::load_json(dir + "/" + {{struct}}::kJsonFile);
+{{/tables}}
+        return data;
+    }
+#endif
+};
+
+}  // namespace {{namespace}}
@@ -0,0 +1,277 @@
+{{! 기본 C# 템플릿. 변수 목록은 codegen::csharp 모듈 문서 참고. }}
+// <auto-generated>
+// entity_manager codegen (C#) — 직접 고치지 말고 스키마를 고친 뒤 다시 생성하세요.
+// JSON 로더는 Newtonsoft.Json이 필요합니다 (없으면 GAMEDATA_NO_JSON 정의).
+// </auto-generated>
+using System;
+using System.Collections.Generic;
+using System.Globalization;
+using System.IO;
+using System.Text;
+#if !GAMEDATA_NO_JSON
+using Newtonsoft.Json.Linq;
+#endif
+
+namespace {{namespace}}
+{
+    /// <summary>행 하나의 셀 읽기 (헤더명, 예전 헤더명 순으로 찾고 없으면 빈 값)</summary>
+    public interface IRow
+    {
+        string Get(string[] names);
+        string[] List(string[] names, string separator);
+    }
+{{#tables}}
+{{#enums}}
+
+    public enum {{enum}}
+    {
+{{#variants}}
+        {{ident}},
+{{/variants}}
+    }
+
+    public static class {{enum}}Values
+    {
+        public static readonly string[] Names = { {{#variants}}{{value}}{{^last}}, {{/last}}{{/variants}} };
+
+        public static {{enum}} Parse(string s)
+        {
+            if (s.Length == 0) return default;
+            int at = Array.IndexOf(Names, s);
+            if (at < 0) throw new FormatException($"'{s}' is not a {{enum}}");
+            return ({{enum}})at;
+        }
+
+        public static string ToValue(this {{enum}} v) => Names[(int)v];
+    }
+{{/enums}}
+
+    /// <summary>`{{name}}` 테이블 한 행 (키: {{key_header}})</summary>
+    public sealed class {{class}}
+    {
+        public const string File = {{file}};
+        public const string JsonFile = {{json_file}};
+        public const char Delimiter = {{delimiter}};
+{{#fields}}
+{{#summary}}
+        /// <summary>{{summary}}</summary>
+{{/summary}}
+{{#reference}}
+        /// <remarks>참조: {{reference}}</remarks>
+{{/reference}}
+        public {{type}} {{name}};
+{{/fields}}
+
+        public {{key_type}} Key => {{key_name}};
+
+        public static {{class}} FromRow(IRow row)
+        {
+            return new {{class}}
+            {
+{{#fields}}
+                {{name}} = {{parse}},
+{{/fields}}
+            };
+        }
+
+{{^many}}
+        /// <summary>키 -> 행. 같은 키가 여러 번 나오면 마지막 행이 남는다.</summary>
+        public static Dictionary<{{key_type}}, {{value_type}}> LoadCsv(string path) => Cells.Index(Cells.ReadCsv(path, Delimiter), FromRow, r => r.Key);
+#if !GAMEDATA_NO_JSON
+
+        public static Dictionary<{{key_type}}, {{value_type}}> LoadJson(string path) => Cells.Index(Cells.ReadJson(path), FromRow, r => r.Key);
+#endif
+{{/many}}
+{{#many}}
+        /// <summary>{{key_header}} -> 행들 (파일 순서)</summary>
+        public static Dictionary<{{key_type}}, {{value_type}}> LoadCsv(string path) => Cells.Group(Cells.ReadCsv(path, Delimiter), FromRow, r => r.Key);
+#if !GAMEDATA_NO_JSON
+
+        public static Dictionary<{{key_type}}, {{value_type}}> LoadJson(string path) => Cells.Group(Cells.ReadJson(path), FromRow, r => r.Key);
+#endif
+{{/many}}
+    }
+{{/tables}}
+
+{{! 멤버 이름이 클래스 이름과 같으므로 식에서는 global::로 클래스를 가리킨다 }}
+    /// <summary>모든 테이블</summary>
+    public sealed class {{root}}
+    {
+{{#tables}}
+        public Dictionary<{{key_type}}, {{value_type}}> {{member}} = new Dictionary<{{key_type}}, {{value_type}}>();
+{{/tables}}
+
+        /// <summary>`dir` 기준으로 각 테이블의 File을 읽는다</summary>
+        public static {{root}} LoadCsv(string dir)
+        {
+            return new {{root}}
+            {
+{{#tables}}
+                {{member}} = global::{{namespace}}.{{class}}.LoadCsv(Path.Combine(dir, global::{{namespace}}.{{class}}.File)),
+{{/tables}}
+            };
+        }
+#if !GAMEDATA_NO_JSON
+
+        /// <summary>내보내기(JSON) 폴더에서 각 테이블의 JsonFile을 읽는다</summary>
+        public static {{root}} LoadJson(string dir)
+        {
+            return new {{root}}
+            {
+{{#tables}}
+                {{member}} = global::{{namespace}}.{{class}}.LoadJson(Path.Combine(dir, global::{{namespace}}.{{class}}.JsonFile)),
+{{/tables}}
+            };
+        }
+#endif
+    }
+
+    public static class Cells
+    {
+        public static long Int(string s) =>
+            string.IsNullOrWhiteSpace(s) ? 0 : long.Parse(s.Trim(), NumberStyles.Integer, CultureInfo.InvariantCulture);
+
+        public static double Float(string s) =>
+            string.IsNullOrWhiteSpace(s) ? 0 : double.Parse(s.Trim(), NumberStyles.Float, CultureInfo.InvariantCulture);
+
+        public static bool Bool(string s)
+        {
+            switch (s.Trim().ToLowerInvariant())
+            {
+                case "": case "false": case "0": case "no": case "n": return false;
+                case "true": case "1": case "yes": case "y": return true;
+                default: throw new FormatException($"'{s}' is not a bool");
+            }
+        }
+
+        public static Dictionary<TKey, T> Index<TKey, T>(IEnumerable<IRow> rows, Func<IRow, T> make, Func<T, TKey> key)
+        {
+            var index = new Dictionary<TKey, T>();
+            foreach (var row in rows)
+            {
+                var r = make(row);
+                index[key(r)] = r;
+            }
+            return index;
+        }
+
+        public static Dictionary<TKey, List<T>> Group<TKey, T>(IEnumerable<IRow> rows, Func<IRow, T> make, Func<T, TKey> key)
+        {
+            var groups = new Dictionary<TKey, List<T>>();
+            foreach (var row in rows)
+            {
+                var r = make(row);
+                if (!groups.TryGetValue(key(r), out var list)) groups[key(r)] = list = new List<T>();
+                list.Add(r);
+            }
+            return groups;
+        }
+
+        sealed class CsvRow : IRow
+        {
+            readonly List<string> header, cells;
+            public CsvRow(List<string> header, List<string> cells) { this.header = header; this.cells = cells; }
+
+            public string Get(string[] names)
+            {
+                foreach (var name in names)
+                {
+                    int at = header.IndexOf(name);
+                    if (at >= 0) return at < cells.Count ? cells[at] : "";
+                }
+                return "";
+            }
+
+            public string[] List(string[] names, string separator)
+            {
+                var s = Get(names);
+                return s.Length == 0 ? new string[0] : s.Split(new[] { separator }, StringSplitOptions.None);
+            }
+        }
+
+        /// <summary>UTF-8 CSV ("" 이스케이프, 따옴표 안 줄바꿈 허용)</summary>
+        public static IEnumerable<IRow> ReadCsv(string path, char delimiter)
+        {
+            var records = ParseCsv(System.IO.File.ReadAllText(path, Encoding.UTF8), delimiter);
+            for (int i = 1; i < records.Count; i++) yield return new CsvRow(records[0], records[i]);
+        }
+
+        static List<List<string>> ParseCsv(string text, char delimiter)
+        {
+            var records = new List<List<string>>();
+            var record = new List<string>();
+            var cell = new StringBuilder();
+            bool quoted = false, any = false;
+            for (int i = 0; i < text.Length; i++)
+            {
+                char c = text[i];
+                if (quoted)
+                {
+                    if (c != '"') cell.Append(c);
+                    else if (i + 1 < text.Length && text[i + 1] == '"') { cell.Append('"'); i++; }
+                    else quoted = false;
+                    continue;
+                }
+                if (c == '"') { quoted = true; any = true; }
+                else if (c == delimiter) { record.Add(cell.ToString()); cell.Clear(); any = true; }
+                else if (c == '\n' || c == '\r')
+                {
+                    if (c == '\r' && i + 1 < text.Length && text[i + 1] == '\n') i++;
+                    if (any || cell.Length > 0) { record.Add(cell.ToString()); records.Add(record); }
+                    record = new List<string>();
+                    cell.Clear();
+                    any = false;
+                }
+                else { cell.Append(c); any = true; }
+            }
+            if (any || cell.Length > 0) { record.Add(cell.ToString()); records.Add(record); }
+            if (records.Count > 0 && records[0].Count > 0) records[0][0] = records[0][0].TrimStart('\uFEFF');
+            return records;
+        }
+#if !GAMEDATA_NO_JSON
+
+        sealed class JsonRow : IRow
+        {
+            readonly JObject row;
+            public JsonRow(JObject row) { this.row = row; }
+
+            JToken Find(string[] names)
+            {
+                foreach (var name in names)
+                    if (row.TryGetValue(name, out var v) && v.Type != JTokenType.Null) return v;
+                return null;
+            }
+
+            public string Get(string[] names)
+            {
+                var v = Find(names);
+                if (v == null) return "";
+                if (v.Type == JTokenType.String) return (string)v;
+                if (v.Type == JTokenType.Boolean) return (bool)v ? "true" : "false";
+                return v.ToString(Newtonsoft.Json.Formatting.None);
+            }
+
+            public string[] List(string[] names, string separator)
+            {
+                var v = Find(names);
+                if (v is JArray items)
+                {
+                    var list = new string[items.Count];
+                    for (int i = 0; i < list.Length; i++) list[i] = (string)items[i];
+                    return list;
+                }
+                var s = v == null ? "" : (string)v;
+                return s.Length == 0 ? new string[0] : s.Split(new[] { separator }, StringSplitOptions.None);
+            }
+        }
+
+        /// <summary>내보내기 JSON(레코드 배열) 또는 변환 JSON({ "rows": [...] })</summary>
+        public static IEnumerable<IRow> ReadJson(string path)
+        {
+            var root = JToken.Parse(System.IO.File.ReadAllText(path, Encoding.UTF8));
+            var rows = root is JObject o && o["rows"] is JArray r ? r : (JArray)root;
+            foreach (var row in rows) yield return new JsonRow((JObject)row);
+        }
+#endif
+    }
+}
@@ -0,0 +1,83 @@
+use super::app_state::DataSets;
+use super::query::{self, Expr, ParseError, Scope};
+
+/// 평가에 실패한 계산 셀에 들어가는 표시 값의 접두어
+pub const ERROR_PREFIX: &str = "#ERR";
+
+/// 계산 컬럼 하나 (테이블 이름, 헤더명, 파싱된 식)
+struct Formula {
+    table: String,
+    column: String,
+    expr: Expr,
+}
+
+/// 모든 계산 컬럼의 식을 파싱/검사해 실패한 것만 돌려준다: (테이블, 헤더명, 오류)
+pub fn check(ds: &DataSets) -> Vec<(String, String, ParseError)> {
+    let mut out = Vec::new();
+    for t in &ds.tables {
+        for col in &t.schema.columns {
+            let Some(src) = &col.formula else { continue };
+            if let Err(e) = query::parse(src).and_then(|e| e.check_single_row(ds, Some(&t.spec.name))) {
+                out.push((t.spec.name.clone(), col.key.clone(), e));
+            }
+        }
+    }
+    out
+}
+
+/// 계산 컬럼 값을 모든 행에 다시 채운다. 계산 컬럼끼리 참조할 수 있으므로
+/// 값이 더 바뀌지 않을 때까지(최대 계산 컬럼 수만큼) 반복한다. 바뀐 셀이 있으면 true.
+pub fn recompute(ds: &mut DataSets) -> bool {
+    let formulas: Vec<Formula> = ds
+        .tables
+        .iter()
+        .flat_map(|t| {
+            t.schema.columns.iter().filter_map(|col| {
+                let expr = query::parse(col.formula.as_deref()?).ok()?;
+                Some(Formula { table: t.spec.name.clone(), column: col.key.clone(), expr })
+            })
+        })
+        .collect();
+
+    let mut any = false;
+    for _ in 0..formulas.len().max(1) {
+        let mut changed = false;
+        for f in &formulas {
+            let Some(t) = ds.table(&f.table) else { continue };
+            let Some(col) = t.schema.find(&f.column) else { continue };
+            let values: Vec<(String, String)> = t
+                .rows
+                .iter()
+                .map(|(key, row)| {
+                    let old = row.get(&col.key).unwrap_or("");
+                    // 자식 테이블 행은 엔티티 키로 다른 테이블을 읽고, 자기 테이블은 이 행을 읽는다
+                    let own = [(f.table.as_str(), key.as_str())];
+                    let scope = Scope { ds, key: t.schema.parent_of(key, row), default_table: Some(&f.table), child_rows: &own };
+                    let v = match f.expr.eval(&scope) {
+                        Ok(v) => v.to_cell(&col.dtype, old).unwrap_or_else(|e| format!("{} {}", ERROR_PREFIX, e)),
+                        Err(e) => format!("{} {}", ERROR_PREFIX, e),
+                    };
+                    (key.clone(), v)
+                })
+                .filter(|(key, v)| t.rows[key].get(&col.key) != Some(v.as_str()))
+                .collect();
+            if values.is_empty() {
+                continue;
+            }
+            changed = true;
+            let column = col.key.clone();
+            if let Some(t) = ds.table_mut(&f.table) {
+                for (key, v) in values {
+                    if let Some(row) = t.rows.get_mut(&key) {
+                        row.set(&column, v);
+                    }
+                }
+            }
+        }
+        any |= changed;
+        if !changed {
+            break;
+        }
+    }
+    any
+}
@@ -0,0 +1,106 @@
+use std::{collections::BTreeMap, fs};
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+
+use super::app_state::{Table, TableSpec};
+use super::dyn_entity::DynRow;
+use super::schema::{DataType, TableSchema};
+use super::storage::{ordered_keys, render_schema_sidecar, render_table, write_files, FileWrite};
+
+/// 테이블 JSON 형식: 스키마 + 파일 순서의 행 목록.
+/// 값은 dtype에 맞춰 숫자/불리언으로 쓰되, 그대로 되돌릴 수 없는 표기(예: "1.50", "yes")는 문자열로 남긴다.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TableJson {
+    pub schema: TableSchema,
+    pub rows: Vec<Map<String, Value>>,
+}
+
+/// 셀 문자열 -> JSON 값 (빈 셀은 null)
+pub fn cell_to_json(dtype: &DataType, s: &str) -> Value {
+    if s.is_empty() {
+        return Value::Null;
+    }
+    match dtype {
+        DataType::Int => match s.parse::<i64>() {
+            Ok(n) if n.to_string() == s => Value::from(n),
+            _ => Value::from(s),
+        },
+        DataType::Float => match s.parse::<f64>().ok().and_then(Number::from_f64) {
+            Some(n) if n.to_string() == s => Value::Number(n),
+            _ => Value::from(s),
+        },
+        DataType::Bool if s == "true" || s == "false" => Value::Bool(s == "true"),
+        _ => Value::from(s),
+    }
+}
+
+/// JSON 값 -> 셀 문자열 (`cell_to_json`의 역)
+pub fn json_to_cell(v: &Value) -> Result<String> {
+    Ok(match v {
+        Value::Null => String::new(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.clone(),
+        other => bail!("셀 값은 문자열/숫자/불리언이어야 합니다: {}", other),
+    })
+}
+
+/// 저장되는 컬럼만 JSON으로 (계산 컬럼은 스키마에만 남음)
+pub fn table_to_json(t: &Table) -> TableJson {
+    let columns: Vec<_> = t.schema.columns.iter().filter(|c| !c.is_computed()).collect();
+    let rows = ordered_keys(&t.rows, &t.order)
+        .into_iter()
+        .map(|k| {
+            let row = &t.rows[k];
+            columns
+                .iter()
+                .map(|c| (c.key.clone(), cell_to_json(&c.dtype, row.get(&c.key).unwrap_or(""))))
+                .collect()
+        })
+        .collect();
+    TableJson { schema: t.schema.clone(), rows }
+}
+
+/// JSON에서 테이블 구성. 키는 스키마의 키 컬럼 값.
+pub fn table_from_json(spec: &TableSpec, json: TableJson) -> Result<Table> {
+    let mut rows = BTreeMap::new();
+    let mut order = Vec::new();
+    for (i, obj) in json.rows.into_iter().enumerate() {
+        let mut row = DynRow { key: String::new(), cells: Default::default(), raw: None };
+        for (k, v) in &obj {
+            row.set(k, json_to_cell(v).with_context(|| format!("rows[{}].{}", i, k))?);
+        }
+        if let Some(missing) = json.schema.key_columns().find(|c| row.get(c).is_none()) {
+            bail!("rows[{}]: 키 컬럼 '{}' 없음", i, missing);
+        }
+        let key = json.schema.key_of(&row);
+        row.key = key.clone();
+        order.push(key.clone());
+        rows.insert(key, row);
+    }
+    Ok(Table { spec: spec.clone(), schema: json.schema, rows, order, conflicts: Vec::new() })
+}
+
+pub fn read_json(spec: &TableSpec) -> Result<Table> {
+    let text = fs::read_to_string(&spec.path).with_context(|| format!("open {}", spec.path))?;
+    let json: TableJson = serde_json::from_str(&text).with_context(|| format!("parse {}", spec.path))?;
+    table_from_json(spec, json)
+}
+
+pub fn write_json(t: &Table, path: &str) -> Result<()> {
+    let mut text = serde_json::to_string_pretty(&table_to_json(t))?;
+    text.push('\n');
+    write_files(&[FileWrite { path: path.to_string(), bytes: text.into_bytes(), backup: false }], 0)
+}
+
+/// 데이터 파일로 쓰기. `sidecar`면 스키마 사이드카도 같이 쓴다 (JSON에서 되돌릴 때 dtype/규칙 유지)
+pub fn write_delimited(t: &Table, path: &str, sidecar: bool) -> Result<()> {
+    let bytes = render_table(&t.schema, &t.rows, &t.order, &t.conflicts, false)?;
+    let mut files = vec![FileWrite { path: path.to_string(), bytes, backup: false }];
+    if sidecar {
+        files.push(render_schema_sidecar(path, &t.schema)?);
+    }
+    write_files(&files, 0)
+}
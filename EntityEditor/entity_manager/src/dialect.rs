@@ -0,0 +1,303 @@
+//! 데이터 파일의 CSV 표기 방식(인코딩, 구분자, 따옴표, BOM, 줄바꿈).
+//! 로드할 때 파일에서 알아내 스키마(사이드카)에 기록하고, 저장할 때 그대로 다시 쓴다.
+//! 바뀌지 않은 행은 원본 바이트(`RawRecord`)를 그대로 쓰므로 저장해도 diff가 생기지 않는다.
+
+use anyhow::{anyhow, Result};
+use csv::{QuoteStyle, ReaderBuilder, Terminator, WriterBuilder};
+use serde::{Deserialize, Serialize};
+
+pub const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// 구분자 후보 (지정한 구분자가 맞지 않을 때 이 순서로 시도)
+const DELIMITERS: [char; 4] = [',', '\t', ';', '|'];
+
+/// 감지에 쓰는 앞쪽 레코드 수
+const SNIFF_RECORDS: usize = 50;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+        }
+    }
+}
+
+/// 파일 인코딩. 메모리에서는 항상 UTF-8로 다루고 읽고 쓸 때만 바꾼다.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextEncoding {
+    #[default]
+    Utf8,
+    Cp949, // EUC-KR 포함 (Windows 한국어 코드 페이지)
+}
+
+impl TextEncoding {
+    pub const ALL: [TextEncoding; 2] = [TextEncoding::Utf8, TextEncoding::Cp949];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Cp949 => "CP949",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().replace(['-', '_'], "").as_str() {
+            "utf8" => Some(Self::Utf8),
+            "cp949" | "euckr" | "uhc" | "ms949" => Some(Self::Cp949),
+            _ => None,
+        }
+    }
+
+    /// 올바른 UTF-8이면 UTF-8, 아니면 CP949로 읽히면 CP949. 둘 다 아니면 UTF-8(읽을 때 오류).
+    /// ASCII뿐인 파일은 어느 쪽이든 같으므로 UTF-8.
+    pub fn detect(data: &[u8]) -> Self {
+        if std::str::from_utf8(data).is_err() && TextEncoding::Cp949.decode(data).is_ok() {
+            TextEncoding::Cp949
+        } else {
+            TextEncoding::Utf8
+        }
+    }
+
+    /// 바이트 -> 문자열. 이 인코딩으로 읽을 수 없는 바이트가 있으면 오류 (UTF-8 BOM은 그대로 남김)
+    pub fn decode(self, data: &[u8]) -> Result<String> {
+        match self {
+            TextEncoding::Utf8 => match std::str::from_utf8(data) {
+                Ok(s) => Ok(s.to_string()),
+                Err(e) => Err(anyhow!("UTF-8이 아닙니다 ({}바이트째). CP949 파일이면 인코딩을 지정하세요", e.valid_up_to())),
+            },
+            TextEncoding::Cp949 => encoding_rs::EUC_KR
+                .decode_without_bom_handling_and_without_replacement(data)
+                .map(|s| s.into_owned())
+                .ok_or_else(|| anyhow!("CP949로 읽을 수 없는 바이트가 있습니다")),
+        }
+    }
+
+    /// 문자열 -> 바이트. 표현할 수 없는 글자는 '?'로 (`unencodable`로 미리 경고)
+    pub fn encode(self, text: &str) -> Vec<u8> {
+        match self {
+            TextEncoding::Utf8 => text.as_bytes().to_vec(),
+            TextEncoding::Cp949 => {
+                let (bytes, _, unmappable) = encoding_rs::EUC_KR.encode(text);
+                if !unmappable {
+                    return bytes.into_owned();
+                }
+                // encoding_rs는 "&#NNNN;"로 바꾸므로 글자 단위로 다시
+                let mut out = Vec::with_capacity(text.len());
+                for c in text.chars() {
+                    let mut buf = [0u8; 4];
+                    let (bytes, _, unmappable) = encoding_rs::EUC_KR.encode(c.encode_utf8(&mut buf));
+                    out.extend_from_slice(if unmappable { b"?" } else { &bytes });
+                }
+                out
+            }
+        }
+    }
+
+    /// 이 인코딩으로 쓸 수 없는 글자들 (없으면 빈 문자열)
+    pub fn unencodable(self, text: &str) -> String {
+        match self {
+            TextEncoding::Utf8 => String::new(),
+            TextEncoding::Cp949 if text.is_ascii() => String::new(),
+            TextEncoding::Cp949 => text
+                .chars()
+                .filter(|c| {
+                    let mut buf = [0u8; 4];
+                    encoding_rs::EUC_KR.encode(c.encode_utf8(&mut buf)).2
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 따옴표를 쓰는 방식 (csv 크레이트의 `QuoteStyle`과 같은 뜻)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Quoting {
+    #[default]
+    Necessary, // 구분자/따옴표/줄바꿈이 있는 값만
+    Always,     // 모든 값
+    NonNumeric, // 숫자가 아닌 값
+}
+
+impl Quoting {
+    pub const ALL: [Quoting; 3] = [Quoting::Necessary, Quoting::Always, Quoting::NonNumeric];
+
+    fn style(self) -> QuoteStyle {
+        match self {
+            Quoting::Necessary => QuoteStyle::Necessary,
+            Quoting::Always => QuoteStyle::Always,
+            Quoting::NonNumeric => QuoteStyle::NonNumeric,
+        }
+    }
+}
+
+/// 파일 하나의 표기 방식
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Dialect {
+    #[serde(default)]
+    pub encoding: TextEncoding,
+    pub delimiter: char,
+    #[serde(default)]
+    pub bom: bool,
+    #[serde(default)]
+    pub line_ending: LineEnding,
+    #[serde(default)]
+    pub quoting: Quoting,
+    #[serde(default = "yes")]
+    pub final_newline: bool, // 마지막 줄 끝에 줄바꿈이 있는지
+}
+
+fn yes() -> bool {
+    true
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self { encoding: TextEncoding::Utf8, delimiter: ',', bom: false, line_ending: LineEnding::Lf, quoting: Quoting::Necessary, final_newline: true }
+    }
+}
+
+/// 원본 파일의 레코드 하나: 읽은 값 + 원래 바이트 (줄바꿈과 뒤따르는 빈 줄 포함)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawRecord {
+    pub fields: Vec<String>,
+    pub bytes: Vec<u8>,
+}
+
+/// 로드한 파일의 원본 헤더와 그때 감지한 표기 방식.
+/// 표기 방식이 바뀌면(예: 구분자 변경) 원본 바이트는 쓰지 않는다.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RawSource {
+    pub header: RawRecord,
+    pub dialect: Dialect,
+}
+
+impl RawRecord {
+    /// 현재 값이 원본과 같은지. 원본에 모자란 칸은 빈 값으로 보고, 헤더보다 많은 칸은 비교하지 않는다.
+    pub fn matches(&self, fields: &[&str]) -> bool {
+        fields.iter().enumerate().all(|(i, f)| self.fields.get(i).map_or("", String::as_str) == *f)
+    }
+}
+
+impl Dialect {
+    /// csv 크레이트용 1바이트 구분자. 설정 구분자는 `Table::load`/프로젝트 파일에서 ASCII만 받는다 (그 밖은 ',')
+    pub fn delimiter_byte(&self) -> u8 {
+        if self.delimiter.is_ascii() { self.delimiter as u8 } else { b',' }
+    }
+
+    /// 화면 표시용 한 줄 요약 (예: "탭 구분 · CRLF · BOM")
+    pub fn describe(&self) -> String {
+        let mut parts = vec![match self.delimiter {
+            ',' => "쉼표 구분".to_string(),
+            '\t' => "탭 구분".to_string(),
+            c => format!("'{}' 구분", c),
+        }];
+        if self.encoding != TextEncoding::Utf8 {
+            parts.push(self.encoding.label().to_string());
+        }
+        parts.push(if self.line_ending == LineEnding::CrLf { "CRLF" } else { "LF" }.to_string());
+        if self.bom {
+            parts.push("BOM".to_string());
+        }
+        match self.quoting {
+            Quoting::Necessary => {}
+            Quoting::Always => parts.push("항상 따옴표".to_string()),
+            Quoting::NonNumeric => parts.push("문자열에 따옴표".to_string()),
+        }
+        if !self.final_newline {
+            parts.push("마지막 줄바꿈 없음".to_string());
+        }
+        parts.join(" · ")
+    }
+
+    /// (UTF-8로 바꾼) 파일 내용에서 구분자/BOM/줄바꿈을 알아낸다
+    /// (인코딩은 `TextEncoding::detect`, 따옴표 방식은 `detect_quoting`).
+    /// `hint` 구분자로 읽어 헤더가 두 컬럼 이상이고 앞쪽 레코드의 컬럼 수가 모두 같으면 그대로 쓰고,
+    /// 아니면 그런 후보 중 컬럼이 가장 많은 것을 고른다. 맞는 후보가 없으면 `hint`.
+    pub fn sniff(data: &[u8], hint: char) -> Self {
+        let bom = data.starts_with(UTF8_BOM);
+        let body = if bom { &data[UTF8_BOM.len()..] } else { data };
+        let line_ending = match body.iter().position(|&b| b == b'\n') {
+            Some(i) if i > 0 && body[i - 1] == b'\r' => LineEnding::CrLf,
+            _ => LineEnding::Lf,
+        };
+        let final_newline = body.is_empty() || body.ends_with(b"\n");
+
+        let columns = |d: char| -> Option<usize> {
+            if !d.is_ascii() {
+                return None;
+            }
+            let mut rdr = ReaderBuilder::new().flexible(true).has_headers(false).delimiter(d as u8).from_reader(body);
+            let counts: Vec<usize> = rdr.records().take(SNIFF_RECORDS).map_while(|r| r.ok()).map(|r| r.len()).collect();
+            let first = *counts.first()?;
+            (first > 1 && counts.iter().all(|&n| n == first)).then_some(first)
+        };
+        let delimiter = if columns(hint).is_some() {
+            hint
+        } else {
+            DELIMITERS
+                .iter()
+                .filter_map(|&d| columns(d).map(|n| (n, d)))
+                .max_by_key(|&(n, _)| n)
+                .map_or(hint, |(_, d)| d)
+        };
+        Self { encoding: TextEncoding::Utf8, delimiter, bom, line_ending, quoting: Quoting::Necessary, final_newline }
+    }
+
+    /// 앞쪽 레코드들을 방식마다 다시 써 보고 원본과 가장 많이 같은 방식을 고른다 (같으면 Necessary 우선)
+    pub fn detect_quoting(&self, records: &[RawRecord]) -> Quoting {
+        let sample = &records[..records.len().min(SNIFF_RECORDS)];
+        let matches = |q: Quoting| {
+            let d = Dialect { quoting: q, ..self.clone() };
+            sample
+                .iter()
+                .filter(|r| d.write_record(&r.fields).is_ok_and(|b| trim_line_end(&r.bytes) == trim_line_end(&b)))
+                .count()
+        };
+        let mut best = (matches(Quoting::Necessary), Quoting::Necessary);
+        for q in Quoting::ALL[1..].iter().copied() {
+            let n = matches(q);
+            if n > best.0 {
+                best = (n, q);
+            }
+        }
+        best.1
+    }
+
+    /// 레코드 하나를 이 방식으로 (줄바꿈 포함)
+    pub fn write_record<I, S>(&self, fields: I) -> Result<Vec<u8>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<[u8]>,
+    {
+        let mut w = self.writer(Vec::new());
+        w.write_record(fields)?;
+        Ok(w.into_inner().map_err(|e| e.into_error())?)
+    }
+
+    pub fn writer<W: std::io::Write>(&self, out: W) -> csv::Writer<W> {
+        let terminator = match self.line_ending {
+            LineEnding::Lf => Terminator::Any(b'\n'),
+            LineEnding::CrLf => Terminator::CRLF,
+        };
+        WriterBuilder::new()
+            .has_headers(false)
+            .delimiter(self.delimiter_byte())
+            .quote_style(self.quoting.style())
+            .terminator(terminator)
+            .from_writer(out)
+    }
+}
+
+/// 끝의 줄바꿈/빈 줄을 뺀 부분
+pub fn trim_line_end(bytes: &[u8]) -> &[u8] {
+    let end = bytes.iter().rposition(|&b| b != b'\n' && b != b'\r').map_or(0, |i| i + 1);
+    &bytes[..end]
+}
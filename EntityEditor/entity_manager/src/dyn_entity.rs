@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use super::dialect::RawRecord;
+
+
+/// A single table row, preserving arbitrary columns from CSV.
+#[derive(Debug, Clone)]
+pub struct DynRow {
+pub key: String, // string key for uniformity
+pub cells: HashMap<String, String>, // header(label)->raw string
+pub raw: Option<RawRecord>, // 파일에서 읽은 원본 레코드 (값이 그대로면 저장 시 그대로 씀)
+}
+
+
+impl DynRow {
+pub fn get(&self, header: &str) -> Option<&str> {
+self.cells.get(header).map(|s| s.as_str())
+}
+pub fn set(&mut self, header: &str, val: String) {
+self.cells.insert(header.to_string(), val);
+}
+}
+
+
+/// Multi-table merged entity keyed by `unique`.
+#[derive(Debug, Default, Clone)]
+pub struct DynEntity {
+pub unique: String,
+pub rows: BTreeMap<String, DynRow>, // table name -> row
+pub children: BTreeMap<String, Vec<DynRow>>, // 자식 테이블 name -> 이 엔티티의 행들 (파일 순서)
+}
+
+
+impl DynEntity {
+pub fn row(&self, table: &str) -> Option<&DynRow> {
+self.rows.get(table)
+}
+pub fn ensure_unique(&mut self) { if self.unique.is_empty() {
+if let Some(r) = self.rows.values().next() { self.unique = r.key.clone(); }
+}}
+}
+
+
+/// 복합 키 값 사이 구분자 (셀 값에 나오지 않는 제어 문자)
+pub const KEY_SEP: char = '\u{1f}';
+
+
+/// 복합 키 값들을 행 키 하나로
+pub fn join_key<S: AsRef<str>>(parts: &[S]) -> String {
+let mut key = String::new();
+for (i, p) in parts.iter().enumerate() {
+if i > 0 { key.push(KEY_SEP); }
+key.push_str(p.as_ref());
+}
+key
+}
+
+
+/// 화면/메시지용 키 (복합 키는 "1 / 2")
+pub fn display_key(key: &str) -> Cow<'_, str> {
+if key.contains(KEY_SEP) { Cow::Owned(key.replace(KEY_SEP, " / ")) } else { Cow::Borrowed(key) }
+}
+
+
+/// 키 정렬: 둘 다 숫자면 숫자로, 아니면 문자열로 (복합 키는 앞 값부터 차례로)
+pub fn compare_keys(a: &str, b: &str) -> Ordering {
+if a.contains(KEY_SEP) || b.contains(KEY_SEP) {
+return a.split(KEY_SEP).zip(b.split(KEY_SEP))
+.map(|(x, y)| compare_keys(x, y))
+.find(|o| o.is_ne())
+.unwrap_or_else(|| a.split(KEY_SEP).count().cmp(&b.split(KEY_SEP).count()));
+}
+match (a.parse::<u64>(), b.parse::<u64>()) {
+(Ok(na), Ok(nb)) => na.cmp(&nb),
+_ => a.cmp(b),
+}
+}
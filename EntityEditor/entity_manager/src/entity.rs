@@ -27,6 +27,7 @@ pub struct CharacterEntity {
     pub character_attack_info: RawDataCharacterAttackInfo,
 }
 
+#[derive(Default)]
 pub struct CharacterEntityContainer {
     entities: HashMap<u32, CharacterEntity>,
     character_info_data_path: String,
@@ -41,14 +42,7 @@ pub struct CharacterEntityContainer {
 
 impl CharacterEntityContainer {
     pub fn new() -> Self {
-        Self {
-            entities: HashMap::new(),
-            character_info_data_path: String::new(),
-            character_status_info_data_path: String::new(),
-            character_attack_info_data_path: String::new(),
-            status_key_column_override: None,
-            labels: FieldLabels::default(),
-        }
+        Self::default()
     }
 
     fn find_header_case_insensitive(headers: &csv::StringRecord, target: &str) -> Option<String> {
@@ -0,0 +1,479 @@
+//! 게임 런타임용 내보내기: 테이블(또는 키로 합친 엔티티)을 스키마 dtype에 맞춘 값으로
+//! JSON / RON / MessagePack / 패킹 바이너리로 쓴다.
+//!
+//! 출력은 항상 같은 입력에 같은 바이트가 나오도록 한다 (diff를 작게):
+//! 행은 파일 순서, 엔티티는 키 순서(`compare_keys`), 필드는 스키마 컬럼 순서.
+//!
+//! 패킹 바이너리 (리틀 엔디언):
+//! ```text
+//! "EDAT" u16 버전(=1)
+//! u32 문자열 수, [u32 바이트 길이, UTF-8]...        문자열 테이블 (처음 나온 순서)
+//! u32 테이블 수, 테이블마다:
+//!   u32 이름(문자열 번호), u32 키 컬럼 번호, u32 컬럼 수, [u32 이름, u8 타입]...
+//!   u32 행 수, 행마다: null 비트맵(ceil(컬럼 수/8) 바이트) + 값들
+//!     Int: i64 / Float: f64 / Bool: u8 / 문자열류: u32 번호 / List: u32 개수 + [u32 번호]...
+//! (엔티티 모양이면 이어서) u32 엔티티 수, 엔티티마다: u32 키, [u32 행 번호 (없으면 u32::MAX)] x 테이블 수
+//!   (자식 테이블 자리는 u32 개수 + [u32 행 번호]...)
+//! ```
+//! 타입 번호: 0 Int, 1 Float, 2 Bool, 3 Text, 4 Enum, 5 Date, 6 List, 7 Reference
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Result};
+use ron::extensions::Extensions;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::app_state::{DataSets, Table};
+use super::dyn_entity::{compare_keys, display_key};
+use super::schema::{ColumnDef, DataType};
+use super::storage::{ordered_keys, write_files, FileWrite};
+use super::value::{parse_bool, split_list, Date};
+
+pub const BINARY_MAGIC: &[u8; 4] = b"EDAT";
+pub const BINARY_VERSION: u16 = 1;
+
+/// 엔티티 모양에서 쓰는 파일 이름 (확장자 제외)
+pub const ENTITIES_FILE: &str = "entities";
+
+/// 타입 오류가 이보다 많으면 나머지는 개수만 알린다
+const MAX_REPORTED_ERRORS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    Json,
+    Ron,
+    MessagePack,
+    Binary,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [Self::Json, Self::Ron, Self::MessagePack, Self::Binary];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Json => "JSON",
+            Self::Ron => "RON",
+            Self::MessagePack => "MessagePack",
+            Self::Binary => "Binary",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Ron => "ron",
+            Self::MessagePack => "msgpack",
+            Self::Binary => "bin",
+        }
+    }
+
+    /// 명령줄 이름 (json, ron, msgpack, bin)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "ron" => Some(Self::Ron),
+            "msgpack" | "messagepack" | "mp" => Some(Self::MessagePack),
+            "bin" | "binary" => Some(Self::Binary),
+            _ => None,
+        }
+    }
+}
+
+/// 테이블마다 파일 하나 / 모든 테이블을 키로 합친 엔티티 파일 하나
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportShape {
+    #[default]
+    Tables,
+    Entities,
+}
+
+/// 프로젝트에 저장되는 내보내기 설정
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportSettings {
+    pub dir: String, // 비우면 프로젝트(또는 첫 테이블) 폴더의 `export`
+    pub formats: Vec<ExportFormat>,
+    #[serde(default)]
+    pub shape: ExportShape,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        Self { dir: String::new(), formats: vec![ExportFormat::Json], shape: ExportShape::Tables }
+    }
+}
+
+impl ExportSettings {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// dtype에 맞춘 셀 값. 빈 셀은 `None`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Cell {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Str(String),
+    List(Vec<String>),
+}
+
+/// 셀 문자열을 dtype 값으로. 형식이 맞지 않으면 Err(설명).
+pub fn typed_cell(dtype: &DataType, s: &str) -> Result<Option<Cell>, String> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    let cell = match dtype {
+        DataType::Int => Cell::Int(s.trim().parse().map_err(|_| format!("'{}'은(는) 정수가 아닙니다", s))?),
+        DataType::Float => {
+            let v: f64 = s.trim().parse().map_err(|_| format!("'{}'은(는) 수치가 아닙니다", s))?;
+            if !v.is_finite() {
+                return Err(format!("'{}'은(는) 유한한 수치가 아닙니다", s));
+            }
+            Cell::Float(v)
+        }
+        DataType::Bool => Cell::Bool(parse_bool(s).ok_or_else(|| format!("'{}'은(는) true/false가 아닙니다", s))?),
+        DataType::Date => {
+            let d = Date::parse(s).ok_or_else(|| format!("'{}'은(는) 날짜가 아닙니다", s))?;
+            Cell::Str(Date { sep: '-', ..d }.format()) // 런타임에는 ISO 형식으로
+        }
+        DataType::List(sep) => Cell::List(split_list(s, sep)),
+        DataType::Text | DataType::Enum(_) | DataType::Reference(_) => Cell::Str(s.to_string()),
+    };
+    Ok(Some(cell))
+}
+
+/// 컬럼 순서를 지키는 레코드 (serde 맵)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record(pub Vec<(String, Option<Cell>)>);
+
+impl Serialize for Record {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(Some(self.0.len()))?;
+        for (k, v) in &self.0 {
+            map.serialize_entry(k, v)?;
+        }
+        map.end()
+    }
+}
+
+/// 엔티티 안의 테이블 하나: 일반 테이블은 행 하나(없으면 None), 자식 테이블은 행 목록
+#[derive(Debug, Clone, PartialEq)]
+pub enum TablePart {
+    One(Option<Record>),
+    Many(Vec<Record>),
+}
+
+impl Serialize for TablePart {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TablePart::One(rec) => rec.serialize(s),
+            TablePart::Many(recs) => Records(recs).serialize(s),
+        }
+    }
+}
+
+/// 키 하나에 대해 테이블마다의 레코드 (`DynEntity`의 타입 버전)
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityRecord {
+    pub key: String,
+    pub tables: Vec<(String, TablePart)>,
+}
+
+impl Serialize for EntityRecord {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut map = s.serialize_map(Some(self.tables.len() + 1))?;
+        map.serialize_entry("key", &self.key)?;
+        for (name, rec) in &self.tables {
+            map.serialize_entry(name, rec)?;
+        }
+        map.end()
+    }
+}
+
+struct Records<'a, T>(&'a [T]);
+
+impl<T: Serialize> Serialize for Records<'_, T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        let mut seq = s.serialize_seq(Some(self.0.len()))?;
+        for r in self.0 {
+            seq.serialize_element(r)?;
+        }
+        seq.end()
+    }
+}
+
+/// 내보낼 컬럼: 계산 컬럼 포함 (값은 호출 전에 `computed::recompute`로 채워 둘 것)
+fn export_columns(t: &Table) -> Vec<&ColumnDef> {
+    t.schema.columns.iter().collect()
+}
+
+/// 타입 변환 오류 모음 ("table[key].column: 설명")
+#[derive(Debug, Default)]
+struct Errors(Vec<String>);
+
+impl Errors {
+    fn finish(self) -> Result<()> {
+        if self.0.is_empty() {
+            return Ok(());
+        }
+        let mut msg = format!("타입이 맞지 않는 값 {}건:\n", self.0.len());
+        for e in self.0.iter().take(MAX_REPORTED_ERRORS) {
+            msg.push_str(&format!("  {}\n", e));
+        }
+        if self.0.len() > MAX_REPORTED_ERRORS {
+            msg.push_str(&format!("  ... 외 {}건\n", self.0.len() - MAX_REPORTED_ERRORS));
+        }
+        bail!(msg.trim_end().to_string())
+    }
+}
+
+fn typed_row(t: &Table, key: &str, errors: &mut Errors) -> Record {
+    let row = &t.rows[key];
+    let cells = export_columns(t)
+        .into_iter()
+        .map(|c| {
+            let raw = t.schema.cell(key, row, &c.key);
+            let v = typed_cell(&c.dtype, raw).unwrap_or_else(|e| {
+                errors.0.push(format!("{}[{}].{}: {}", t.spec.name, display_key(key), c.key, e));
+                None
+            });
+            (c.key.clone(), v)
+        })
+        .collect();
+    Record(cells)
+}
+
+/// 파일 순서의 타입 레코드
+pub fn table_records(t: &Table) -> Result<Vec<Record>> {
+    let mut errors = Errors::default();
+    let out = ordered_keys(&t.rows, &t.order).into_iter().map(|k| typed_row(t, k, &mut errors)).collect();
+    errors.finish()?;
+    Ok(out)
+}
+
+/// 모든 키에 대해 테이블별 레코드 (없는 테이블은 None, 자식 테이블은 딸린 행 목록)
+pub fn entity_records(ds: &DataSets) -> Result<Vec<EntityRecord>> {
+    let mut errors = Errors::default();
+    let out = sorted_keys(ds)
+        .into_iter()
+        .map(|key| EntityRecord {
+            tables: ds
+                .tables
+                .iter()
+                .map(|t| {
+                    let part = if t.is_child() {
+                        TablePart::Many(t.child_keys(&key).iter().map(|k| typed_row(t, k, &mut errors)).collect())
+                    } else {
+                        TablePart::One(t.rows.contains_key(&key).then(|| typed_row(t, &key, &mut errors)))
+                    };
+                    (t.spec.name.clone(), part)
+                })
+                .collect(),
+            key,
+        })
+        .collect();
+    errors.finish()?;
+    Ok(out)
+}
+
+fn sorted_keys(ds: &DataSets) -> Vec<String> {
+    let mut keys: Vec<String> = ds.keys().into_iter().collect();
+    keys.sort_by(|a, b| compare_keys(a, b));
+    keys
+}
+
+fn serialize_text<T: Serialize>(format: ExportFormat, value: &T) -> Result<Vec<u8>> {
+    let mut bytes = match format {
+        ExportFormat::Json => serde_json::to_vec_pretty(value)?,
+        ExportFormat::Ron => {
+            let config = ron::ser::PrettyConfig::new().extensions(Extensions::IMPLICIT_SOME);
+            ron::ser::to_string_pretty(value, config)?.into_bytes()
+        }
+        ExportFormat::MessagePack => return Ok(rmp_serde::to_vec_named(value)?),
+        ExportFormat::Binary => unreachable!("바이너리는 pack_tables"),
+    };
+    bytes.push(b'\n');
+    Ok(bytes)
+}
+
+/// 한 형식으로 만든 파일들 (경로는 `dir` 아래)
+pub fn render(ds: &DataSets, format: ExportFormat, shape: ExportShape, dir: &Path) -> Result<Vec<FileWrite>> {
+    let file = |stem: &str| -> String {
+        dir.join(format!("{}.{}", stem, format.extension())).to_string_lossy().to_string()
+    };
+    let mut out = Vec::new();
+    match shape {
+        ExportShape::Tables => {
+            for t in &ds.tables {
+                let bytes = if format == ExportFormat::Binary {
+                    pack(&[t], None)?
+                } else {
+                    serialize_text(format, &Records(&table_records(t)?))?
+                };
+                out.push(FileWrite { path: file(&t.spec.name), bytes, backup: false });
+            }
+        }
+        ExportShape::Entities => {
+            let bytes = if format == ExportFormat::Binary {
+                let tables: Vec<&Table> = ds.tables.iter().collect();
+                pack(&tables, Some(&sorted_keys(ds)))?
+            } else {
+                serialize_text(format, &Records(&entity_records(ds)?))?
+            };
+            out.push(FileWrite { path: file(ENTITIES_FILE), bytes, backup: false });
+        }
+    }
+    Ok(out)
+}
+
+/// 여러 형식을 한 번에 내보낸다 (한 묶음으로 쓰고, 내용이 같은 파일은 건드리지 않음).
+/// 쓴 파일 경로를 돌려준다.
+pub fn export(ds: &DataSets, settings: &ExportSettings, dir: &Path) -> Result<Vec<String>> {
+    if settings.formats.is_empty() {
+        bail!("내보낼 형식을 하나 이상 고르세요");
+    }
+    std::fs::create_dir_all(dir)?;
+    let mut files = Vec::new();
+    for f in &settings.formats {
+        files.extend(render(ds, *f, settings.shape, dir)?);
+    }
+    write_files(&files, 0)?;
+    Ok(files.into_iter().map(|f| f.path).collect())
+}
+
+/// 설정의 폴더가 비었으면 `base` 아래 `export`
+pub fn resolve_dir(settings: &ExportSettings, base: &Path) -> PathBuf {
+    if settings.dir.is_empty() {
+        base.join("export")
+    } else if Path::new(&settings.dir).is_relative() {
+        base.join(&settings.dir)
+    } else {
+        PathBuf::from(&settings.dir)
+    }
+}
+
+// ===== 패킹 바이너리 =====
+
+fn type_tag(dtype: &DataType) -> u8 {
+    match dtype {
+        DataType::Int => 0,
+        DataType::Float => 1,
+        DataType::Bool => 2,
+        DataType::Text => 3,
+        DataType::Enum(_) => 4,
+        DataType::Date => 5,
+        DataType::List(_) => 6,
+        DataType::Reference(_) => 7,
+    }
+}
+
+/// 처음 나온 순서로 번호를 매기는 문자열 테이블
+#[derive(Default)]
+struct Strings {
+    list: Vec<String>,
+    index: HashMap<String, u32>,
+}
+
+impl Strings {
+    fn id(&mut self, s: &str) -> u32 {
+        if let Some(i) = self.index.get(s) {
+            return *i;
+        }
+        let i = self.list.len() as u32;
+        self.list.push(s.to_string());
+        self.index.insert(s.to_string(), i);
+        i
+    }
+}
+
+fn put_u32(out: &mut Vec<u8>, v: u32) {
+    out.extend_from_slice(&v.to_le_bytes());
+}
+
+fn len_u32(n: usize) -> Result<u32> {
+    u32::try_from(n).map_err(|_| anyhow::anyhow!("바이너리 형식 한도(u32)를 넘었습니다: {}", n))
+}
+
+fn pack(tables: &[&Table], entities: Option<&[String]>) -> Result<Vec<u8>> {
+    let mut strings = Strings::default();
+    let mut body = Vec::new();
+    put_u32(&mut body, len_u32(tables.len())?);
+    let mut row_index: Vec<HashMap<&str, Vec<u32>>> = Vec::new();
+    for t in tables {
+        let columns = export_columns(t);
+        let records = table_records(t)?;
+        put_u32(&mut body, strings.id(&t.spec.name));
+        let key_col = columns.iter().position(|c| c.key == t.schema.key_column).unwrap_or(0);
+        put_u32(&mut body, len_u32(key_col)?);
+        put_u32(&mut body, len_u32(columns.len())?);
+        for c in &columns {
+            put_u32(&mut body, strings.id(&c.key));
+            body.push(type_tag(&c.dtype));
+        }
+        put_u32(&mut body, len_u32(records.len())?);
+        for rec in &records {
+            let mut nulls = vec![0u8; columns.len().div_ceil(8)];
+            for (i, (_, v)) in rec.0.iter().enumerate() {
+                if v.is_none() {
+                    nulls[i / 8] |= 1 << (i % 8);
+                }
+            }
+            body.extend_from_slice(&nulls);
+            for (_, v) in &rec.0 {
+                match v {
+                    None => {}
+                    Some(Cell::Int(n)) => body.extend_from_slice(&n.to_le_bytes()),
+                    Some(Cell::Float(f)) => body.extend_from_slice(&f.to_le_bytes()),
+                    Some(Cell::Bool(b)) => body.push(u8::from(*b)),
+                    Some(Cell::Str(s)) => put_u32(&mut body, strings.id(s)),
+                    Some(Cell::List(items)) => {
+                        put_u32(&mut body, len_u32(items.len())?);
+                        for s in items {
+                            put_u32(&mut body, strings.id(s));
+                        }
+                    }
+                }
+            }
+        }
+        // 엔티티 키 -> 행 번호들 (자식 테이블이 아니면 하나)
+        let mut index: HashMap<&str, Vec<u32>> = HashMap::new();
+        for (i, k) in ordered_keys(&t.rows, &t.order).into_iter().enumerate() {
+            index.entry(t.schema.parent_of(k, &t.rows[k])).or_default().push(len_u32(i)?);
+        }
+        row_index.push(index);
+    }
+    if let Some(keys) = entities {
+        put_u32(&mut body, len_u32(keys.len())?);
+        for key in keys {
+            put_u32(&mut body, strings.id(key));
+            for (t, rows) in tables.iter().zip(&row_index) {
+                let found = rows.get(key.as_str()).map_or(&[][..], Vec::as_slice);
+                if t.is_child() {
+                    put_u32(&mut body, len_u32(found.len())?);
+                    for i in found {
+                        put_u32(&mut body, *i);
+                    }
+                } else {
+                    put_u32(&mut body, found.first().copied().unwrap_or(u32::MAX));
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(body.len() + 64);
+    out.extend_from_slice(BINARY_MAGIC);
+    out.extend_from_slice(&BINARY_VERSION.to_le_bytes());
+    put_u32(&mut out, len_u32(strings.list.len())?);
+    for s in &strings.list {
+        put_u32(&mut out, len_u32(s.len())?);
+        out.extend_from_slice(s.as_bytes());
+    }
+    out.extend_from_slice(&body);
+    Ok(out)
+}
@@ -1,6 +1,7 @@
 use super::entity::*;
 use std::error::Error;
 
+#[derive(Default)]
 pub struct EntityDataHanlder;
 
 impl EntityDataHanlder {
@@ -0,0 +1,240 @@
+use std::time::{Duration, Instant};
+
+use super::app_state::{DataSets, Table};
+use super::dyn_entity::DynRow;
+
+/// 이 시간 안에 같은 셀을 다시 고치면 하나의 되돌리기 단계로 합친다 (드래그/타이핑)
+pub const MERGE_WINDOW: Duration = Duration::from_millis(800);
+
+/// 셀 하나의 변경 (old -> new)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellEdit {
+    pub table: String, // TableSpec.name
+    pub key: String,
+    pub column: String,
+    pub old: String,
+    pub new: String,
+}
+
+impl CellEdit {
+    fn same_cell(&self, other: &CellEdit) -> bool {
+        self.table == other.table && self.key == other.key && self.column == other.column
+    }
+
+    fn write(&self, ds: &mut DataSets, value: &str) {
+        if let Some(row) = ds
+            .table_mut(&self.table)
+            .and_then(|t| t.rows.get_mut(&self.key))
+        {
+            row.set(&self.column, value.to_string());
+        }
+    }
+}
+
+/// 행 하나의 추가/삭제 (None = 행 없음)
+#[derive(Debug, Clone)]
+pub struct RowEdit {
+    pub table: String, // TableSpec.name
+    pub key: String,
+    pub before: Option<DynRow>,
+    pub after: Option<DynRow>,
+}
+
+impl RowEdit {
+    fn write(&self, ds: &mut DataSets, row: &Option<DynRow>) {
+        let Some(t) = ds.table_mut(&self.table) else { return };
+        match row {
+            Some(r) => {
+                t.rows.insert(self.key.clone(), r.clone());
+            }
+            None => {
+                t.rows.remove(&self.key);
+            }
+        }
+    }
+}
+
+/// 컬럼 구조 변경(추가/이름 변경/삭제)이나 키 문제 해결 전후의 테이블 스냅샷
+#[derive(Debug, Clone)]
+pub struct TableEdit {
+    pub before: Table,
+    pub after: Table,
+}
+
+impl TableEdit {
+    fn write(ds: &mut DataSets, state: &Table) {
+        if let Some(t) = ds.table_mut(&state.spec.name) {
+            t.schema = state.schema.clone();
+            t.rows = state.rows.clone();
+            t.order = state.order.clone();
+            t.conflicts = state.conflicts.clone();
+            // 키 컬럼 이름 변경은 키 힌트/부모 컬럼도 바꾼다
+            t.spec.key_hint = state.spec.key_hint.clone();
+            t.spec.parent = state.spec.parent.clone();
+        }
+    }
+}
+
+/// 되돌리기 한 단계. 일괄 편집은 여러 셀을 한 단계로 묶는다.
+#[derive(Debug, Clone)]
+pub struct Command {
+    pub label: String,
+    pub edits: Vec<CellEdit>,
+    pub rows: Vec<RowEdit>,     // 엔티티 추가/복제/삭제
+    pub tables: Vec<TableEdit>, // 컬럼 구조 변경
+}
+
+impl Command {
+    pub fn single(edit: CellEdit) -> Self {
+        Self {
+            label: format!("{}[{}].{} = {}", edit.table, edit.key, edit.column, edit.new),
+            edits: vec![edit],
+            rows: Vec::new(),
+            tables: Vec::new(),
+        }
+    }
+
+    pub fn edits(label: impl Into<String>, edits: Vec<CellEdit>) -> Self {
+        Self { label: label.into(), edits, rows: Vec::new(), tables: Vec::new() }
+    }
+
+    pub fn rows(label: impl Into<String>, rows: Vec<RowEdit>) -> Self {
+        Self { label: label.into(), edits: Vec::new(), rows, tables: Vec::new() }
+    }
+
+    pub fn tables(label: impl Into<String>, tables: Vec<TableEdit>) -> Self {
+        Self { label: label.into(), edits: Vec::new(), rows: Vec::new(), tables }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.edits.is_empty() && self.rows.is_empty() && self.tables.is_empty()
+    }
+
+    // 테이블 -> 행 -> 셀 순으로 적용 (undo는 역순)
+    fn undo(&self, ds: &mut DataSets) {
+        for e in self.edits.iter().rev() {
+            e.write(ds, &e.old);
+        }
+        for r in self.rows.iter().rev() {
+            r.write(ds, &r.before);
+        }
+        for t in self.tables.iter().rev() {
+            TableEdit::write(ds, &t.before);
+        }
+    }
+
+    fn redo(&self, ds: &mut DataSets) {
+        for t in &self.tables {
+            TableEdit::write(ds, &t.after);
+        }
+        for r in &self.rows {
+            r.write(ds, &r.after);
+        }
+        for e in &self.edits {
+            e.write(ds, &e.new);
+        }
+    }
+
+    /// 영향을 받은 첫 행 키 (되돌린 뒤 선택 이동용)
+    pub fn first_key(&self) -> Option<&str> {
+        self.edits
+            .first()
+            .map(|e| e.key.as_str())
+            .or_else(|| self.rows.first().map(|r| r.key.as_str()))
+    }
+}
+
+/// 명령 기반 undo/redo 스택.
+/// 편집은 이미 `DataSets`에 반영된 뒤 기록되고, undo/redo가 old/new를 다시 써 넣는다.
+#[derive(Debug, Default)]
+pub struct History {
+    undo: Vec<Command>,
+    redo: Vec<Command>,
+    last_edit_at: Option<Instant>,
+    revision: u64, // 데이터가 바뀔 때마다 증가 (계산 컬럼 갱신 판단용)
+}
+
+impl History {
+    pub fn clear(&mut self) {
+        *self = Self { revision: self.revision + 1, ..Self::default() };
+    }
+
+    /// 기록/undo/redo/clear 때마다 바뀌는 값. 같으면 그 사이 편집이 없었다.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 이미 적용된 셀 편집 기록. 직전 단계가 같은 셀이고 `MERGE_WINDOW` 이내면 합친다.
+    pub fn record(&mut self, edit: CellEdit) {
+        let now = Instant::now();
+        let recent = self.last_edit_at.is_some_and(|t| now - t < MERGE_WINDOW);
+        self.last_edit_at = Some(now);
+        self.redo.clear();
+        self.revision += 1;
+
+        if recent {
+            if let Some(last) = self.undo.last_mut().filter(|c| c.rows.is_empty() && c.tables.is_empty()) {
+                if let [prev] = last.edits.as_mut_slice() {
+                    if prev.same_cell(&edit) {
+                        prev.new = edit.new;
+                        let merged = Command::single(prev.clone());
+                        if merged.edits[0].old == merged.edits[0].new {
+                            self.undo.pop(); // 제자리로 돌아오면 단계 자체를 없앤다
+                        } else {
+                            *last = merged;
+                        }
+                        return;
+                    }
+                }
+            }
+        }
+        self.undo.push(Command::single(edit));
+    }
+
+    /// 이미 적용된 여러 셀/행 편집을 한 단계로 기록 (합치지 않음)
+    pub fn push(&mut self, cmd: Command) {
+        if cmd.is_empty() {
+            return;
+        }
+        self.undo.push(cmd);
+        self.redo.clear();
+        self.last_edit_at = None;
+        self.revision += 1;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn undo(&mut self, ds: &mut DataSets) -> Option<&Command> {
+        let cmd = self.undo.pop()?;
+        cmd.undo(ds);
+        self.redo.push(cmd);
+        self.last_edit_at = None;
+        self.revision += 1;
+        self.redo.last()
+    }
+
+    pub fn redo(&mut self, ds: &mut DataSets) -> Option<&Command> {
+        let cmd = self.redo.pop()?;
+        cmd.redo(ds);
+        self.undo.push(cmd);
+        self.last_edit_at = None;
+        self.revision += 1;
+        self.undo.last()
+    }
+
+    /// 되돌리기 가능한 단계 (오래된 것부터)
+    pub fn undo_stack(&self) -> &[Command] {
+        &self.undo
+    }
+
+    /// 다시 실행 가능한 단계 (마지막 요소가 다음 redo)
+    pub fn redo_stack(&self) -> &[Command] {
+        &self.redo
+    }
+}
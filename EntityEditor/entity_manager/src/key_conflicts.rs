@@ -0,0 +1,163 @@
+//! 키가 비었거나 앞 행과 겹쳐 `Table.rows`에 넣지 못한 행.
+//! 로드할 때 따로 모아 두고(나머지 행은 정상 로드), 번호 다시 매기기/합치기/삭제로 해결한다.
+//! 해결하지 않고 저장하면 원래 자리에 그대로 다시 쓴다 (`storage::render_table`).
+
+use std::fmt;
+
+use anyhow::{bail, Result};
+
+use super::app_state::{DataSets, Table};
+use super::children;
+use super::dyn_entity::{display_key, join_key, DynRow, KEY_SEP};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictKind {
+    Empty,     // 키 칸이 비어 있음
+    Duplicate, // 앞에 나온 행과 키가 같음
+}
+
+/// 키 문제로 `rows`에 들어가지 못한 행
+#[derive(Debug, Clone)]
+pub struct KeyConflict {
+    pub kind: ConflictKind,
+    pub line: usize,               // 파일의 줄 번호 (1부터, 헤더가 1)
+    pub first_line: Option<usize>, // 중복이면 같은 키가 처음 나온 줄
+    pub after: Option<String>,     // 파일에서 바로 앞에 있던 정상 행의 키 (None이면 헤더 바로 다음)
+    pub row: DynRow,               // row.key는 파일의 키 그대로 (빈 값일 수 있음)
+}
+
+impl fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.kind, self.first_line) {
+            (ConflictKind::Empty, _) => write!(f, "{}번째 줄: 키가 비어 있습니다", self.line),
+            (ConflictKind::Duplicate, Some(first)) => {
+                write!(f, "{}번째 줄: 키 '{}' 중복 ({}번째 줄과 같음)", self.line, display_key(&self.row.key), first)
+            }
+            (ConflictKind::Duplicate, None) => {
+                write!(f, "{}번째 줄: 키 '{}' 중복", self.line, display_key(&self.row.key))
+            }
+        }
+    }
+}
+
+/// 합칠 때 두 행의 값이 다르면 어느 쪽을 쓸지 (빈 칸은 언제나 다른 쪽 값으로 채움)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePrefer {
+    Existing,  // 먼저 나온(로드된) 행
+    Duplicate, // 중복 행
+}
+
+fn take(t: &mut Table, index: usize) -> Result<KeyConflict> {
+    if index >= t.conflicts.len() {
+        bail!("{}: 키 문제 {}번이 없습니다", t.spec.name, index);
+    }
+    Ok(t.conflicts.remove(index))
+}
+
+/// 새 키를 주고 정상 행으로 옮긴다. 파일에서의 자리는 유지 (앞에 있던 행 바로 뒤).
+/// 복합 키는 값들을 '/'로 이어 쓴다 (예: "1 / 3").
+pub fn renumber(t: &mut Table, index: usize, new_key: &str) -> Result<()> {
+    let columns: Vec<String> = t.schema.key_columns().map(str::to_string).collect();
+    let parts: Vec<&str> = if columns.len() > 1 {
+        new_key.split([KEY_SEP, '/']).map(str::trim).collect()
+    } else {
+        vec![new_key.trim()]
+    };
+    if parts.len() != columns.len() || parts.iter().any(|p| p.is_empty()) {
+        bail!("새 키가 비어 있습니다 (키 컬럼: {})", columns.join(" / "));
+    }
+    let new_key = join_key(&parts);
+    if t.rows.contains_key(&new_key) {
+        bail!("{}: 키 '{}'이(가) 이미 있습니다", t.spec.name, display_key(&new_key));
+    }
+    let c = take(t, index)?;
+    let mut row = c.row;
+    row.key = new_key.clone();
+    for (col, part) in columns.iter().zip(&parts) {
+        row.set(col, part.to_string());
+    }
+    let at = match &c.after {
+        Some(prev) => t.order.iter().position(|k| k == prev).map_or(t.order.len(), |i| i + 1),
+        None => 0,
+    };
+    // 같은 자리에 남은 다른 키 문제 행들은 이 행 뒤에 오도록
+    for other in t.conflicts.iter_mut().filter(|o| o.after == c.after && o.line > c.line) {
+        other.after = Some(new_key.clone());
+    }
+    t.order.insert(at, new_key.clone());
+    t.rows.insert(new_key, row);
+    Ok(())
+}
+
+/// 중복 행을 같은 키의 행에 합치고 없앤다. 값이 바뀐 칸 수를 돌려준다.
+pub fn merge(t: &mut Table, index: usize, prefer: MergePrefer) -> Result<usize> {
+    match t.conflicts.get(index) {
+        Some(c) if c.kind == ConflictKind::Duplicate && t.rows.contains_key(&c.row.key) => {}
+        Some(_) => bail!("{}: 합칠 행이 없습니다 (키가 비었거나 원래 행이 삭제됨)", t.spec.name),
+        None => bail!("{}: 키 문제 {}번이 없습니다", t.spec.name, index),
+    }
+    let c = take(t, index)?;
+    let columns: Vec<String> = t
+        .schema
+        .columns
+        .iter()
+        .filter(|col| !col.is_computed() && !t.schema.is_key_column(&col.key))
+        .map(|col| col.key.clone())
+        .collect();
+    let row = t.rows.get_mut(&c.row.key).expect("checked above");
+    let mut changed = 0;
+    for col in &columns {
+        let mine = row.get(col).unwrap_or("");
+        let dup = c.row.get(col).unwrap_or("");
+        let use_dup = !dup.is_empty() && (mine.is_empty() || (prefer == MergePrefer::Duplicate && mine != dup));
+        if use_dup {
+            row.set(col, dup.to_string());
+            changed += 1;
+        }
+    }
+    Ok(changed)
+}
+
+/// 테이블의 키 문제 행을 모두 새 키(`DataSets::next_free_key`)로 옮긴다. 매긴 키들을 돌려준다.
+/// 자식/복합 키 테이블은 엔티티(부모 값)와 나머지 키 값은 그대로 두고 그 안에서 다음 번호를 매긴다.
+/// 부모 값이나 번호 외 키 값이 빈 행은 어디 것인지 모르므로 목록에 남긴다. 실패하면 테이블은 그대로.
+pub fn renumber_all(ds: &mut DataSets, table: &str) -> Result<Vec<String>> {
+    let Some(before) = ds.table(table).cloned() else { return Ok(Vec::new()) };
+    let result = renumber_each(ds, table);
+    if result.is_err() {
+        if let Some(t) = ds.table_mut(table) {
+            *t = before;
+        }
+    }
+    result
+}
+
+fn renumber_each(ds: &mut DataSets, table: &str) -> Result<Vec<String>> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while let Some(c) = ds.table(table).and_then(|t| t.conflicts.get(i)) {
+        let key = match ds.table(table) {
+            // 복합 키는 부모 컬럼이 없어도 마지막 키 컬럼에 번호를 매긴다
+            Some(t) if t.is_child() || !t.schema.sub_keys.is_empty() => {
+                let parent = t.schema.parent_of(&c.row.key, &c.row);
+                let row = children::copy_row(t, &c.row, parent)?;
+                let blank = |col: &str| row.get(col).unwrap_or("").trim().is_empty();
+                if parent.trim().is_empty() || t.schema.key_columns().any(blank) {
+                    i += 1;
+                    continue;
+                }
+                row.key
+            }
+            _ => ds.next_free_key(),
+        };
+        let Some(t) = ds.table_mut(table) else { break };
+        renumber(t, i, &key)?;
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+/// 키 문제 행을 버린다
+pub fn delete(t: &mut Table, index: usize) -> Result<()> {
+    take(t, index).map(|_| ())
+}
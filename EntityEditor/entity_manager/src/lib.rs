@@ -0,0 +1,45 @@
+//! CSV 게임 데이터 테이블 코어: 로드/저장, 스키마 추론과 사이드카, 검증, 편집 이력.
+//! EntityEditor(GUI)와 `entity-cli`(헤드리스), 게임 빌드 스크립트가 함께 쓴다.
+//!
+//! ```no_run
+//! use entity_manager::{DataSets, TableSpec};
+//!
+//! let specs = vec![TableSpec::new("info", "Info", "data/character_info.csv", "CharacterUnique")];
+//! let ds = DataSets::load(&specs)?;
+//! let info = ds.table("info").unwrap();
+//! println!("{} 행, 키 컬럼 {}", info.rows.len(), info.schema.key_column);
+//! ds.save_all(entity_manager::backup::DEFAULT_KEEP)?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+pub mod entity;
+pub mod handler;
+pub mod raw_data;
+pub mod dyn_entity;
+pub mod app_state;
+pub mod storage;
+pub mod key_conflicts;
+pub mod children;
+pub mod dialect;
+pub mod schema;
+pub mod project;
+pub mod value;
+pub mod references;
+pub mod validation;
+pub mod history;
+pub mod query;
+pub mod bulk;
+pub mod computed;
+pub mod changes;
+pub mod watch;
+pub mod merge;
+pub mod backup;
+pub mod convert;
+pub mod export;
+pub mod codegen;
+
+pub use app_state::{DataSets, Table, TableSpec};
+pub use dyn_entity::{DynEntity, DynRow};
+pub use schema::{ColumnDef, ColumnRef, ColumnRules, DataType, TableSchema};
+pub use storage::{load_table, save_table};
+pub use validation::{validate, Diagnostic, Severity};
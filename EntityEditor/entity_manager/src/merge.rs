@@ -0,0 +1,165 @@
+use std::collections::BTreeSet;
+
+use super::app_state::Table;
+use super::dyn_entity::DynRow;
+use super::schema::TableSchema;
+
+/// 양쪽이 서로 다르게 바꾼 셀. 행 단위 충돌(한쪽 삭제, 다른 쪽 수정)은 `column`이 비어 있다.
+#[derive(Debug, Clone)]
+pub struct Conflict {
+    pub key: String,
+    pub column: String,
+    pub base: Option<String>, // None = 행 없음
+    pub disk: Option<String>,
+    pub mine: Option<String>,
+    pub take_disk: bool,          // 사용자가 고른 쪽 (기본: 내 값)
+    pub disk_row: Option<DynRow>, // 행 충돌에서 디스크 쪽을 고르면 넣을 행 (None = 삭제)
+}
+
+/// 3-way 병합 결과: 충돌이 없는 부분은 이미 합쳐져 있고, 충돌은 내 값으로 채워져 있다.
+#[derive(Debug, Clone)]
+pub struct MergeResult {
+    pub merged: Table,
+    pub conflicts: Vec<Conflict>,
+    pub from_disk: usize, // 디스크 쪽 변경을 가져온 셀/행 수
+}
+
+impl MergeResult {
+    /// 충돌마다 고른 쪽을 반영한 최종 테이블
+    pub fn resolve(&self) -> Table {
+        let mut t = self.merged.clone();
+        for c in self.conflicts.iter().filter(|c| c.take_disk) {
+            if c.column.is_empty() {
+                match &c.disk_row {
+                    Some(r) => t.rows.insert(c.key.clone(), r.clone()),
+                    None => t.rows.remove(&c.key),
+                };
+            } else if let (Some(row), Some(v)) = (t.rows.get_mut(&c.key), &c.disk) {
+                row.set(&c.column, v.clone());
+            }
+        }
+        t
+    }
+}
+
+/// 기준(로드 당시) / 디스크(지금 파일) / 내 메모리 상태를 셀 단위로 합친다.
+/// - 한쪽만 바꾼 셀은 바꾼 쪽, 둘 다 같게 바꿨으면 그 값, 다르게 바꿨으면 충돌
+/// - 행 추가/삭제도 같은 규칙 (삭제 vs 수정은 행 충돌)
+/// - 컬럼: 내 스키마 기준 + 디스크에서 새로 생긴 컬럼 추가, 디스크에서 지운 컬럼은 내가 안 건드렸으면 삭제
+/// - 행 순서는 디스크 파일 순서를 따른다
+pub fn merge_table(base: &Table, disk: &Table, mine: &Table) -> MergeResult {
+    let mut merged = mine.clone();
+    let mut conflicts = Vec::new();
+    let mut from_disk = 0;
+
+    let has = |t: &Table, c: &str| t.schema.columns.iter().any(|d| d.key == c);
+    for col in &disk.schema.columns {
+        if !has(base, &col.key) && !has(mine, &col.key) {
+            merged.schema.columns.push(col.clone());
+        }
+    }
+    let untouched = |c: &str| {
+        mine.rows
+            .iter()
+            .all(|(k, r)| base.rows.get(k).is_none_or(|b| b.get(c) == r.get(c)))
+    };
+    merged.schema.columns.retain(|c| {
+        let dropped_on_disk = has(base, &c.key) && !has(disk, &c.key) && !c.is_computed();
+        !(dropped_on_disk && untouched(&c.key))
+    });
+    let columns: Vec<String> = merged
+        .schema
+        .columns
+        .iter()
+        .filter(|c| !c.is_computed() && !merged.schema.is_key_column(&c.key))
+        .map(|c| c.key.clone())
+        .collect();
+    // 삭제 vs 수정 판단은 기준에 있던 컬럼으로만 (새로 생긴 컬럼에 값이 채워진 것은 수정이 아님)
+    let base_columns: Vec<String> = columns.iter().filter(|c| has(base, c)).cloned().collect();
+
+    let keys: BTreeSet<&String> = base.rows.keys().chain(disk.rows.keys()).chain(mine.rows.keys()).collect();
+    for key in keys {
+        let (b, d, m) = (base.rows.get(key), disk.rows.get(key), mine.rows.get(key));
+        match (b, d, m) {
+            (_, Some(d), Some(m)) => {
+                let row = merged.rows.get_mut(key).expect("mine row");
+                for c in &columns {
+                    let bv = b.and_then(|b| b.get(c));
+                    let (dv, mv) = (d.get(c), m.get(c));
+                    if dv.is_none() || dv == mv || dv == bv {
+                        continue; // 디스크에 없는 컬럼이거나, 같거나, 디스크가 안 바꿈
+                    }
+                    if mv == bv {
+                        row.set(c, dv.unwrap_or("").to_string());
+                        from_disk += 1;
+                    } else {
+                        conflicts.push(Conflict {
+                            key: key.clone(),
+                            column: c.clone(),
+                            base: bv.map(str::to_string),
+                            disk: dv.map(str::to_string),
+                            mine: mv.map(str::to_string),
+                            take_disk: false,
+                            disk_row: None,
+                        });
+                    }
+                }
+            }
+            // 디스크에만 새로 생긴 행
+            (None, Some(d), None) => {
+                merged.rows.insert(key.clone(), d.clone());
+                from_disk += 1;
+            }
+            // 내가 지운 행: 디스크가 안 바꿨으면 그대로 삭제, 바꿨으면 충돌
+            (Some(b), Some(d), None) if changed(b, d, &base_columns) => {
+                let mut disk_row = d.clone();
+                fill_row(&mut disk_row, &merged.schema);
+                conflicts.push(row_conflict(key, Some(b), Some(disk_row), None));
+            }
+            // 디스크에서 지운 행: 내가 안 바꿨으면 삭제, 바꿨으면 충돌
+            (Some(b), None, Some(m)) => {
+                if !changed(b, m, &base_columns) {
+                    merged.rows.remove(key);
+                    from_disk += 1;
+                } else {
+                    conflicts.push(row_conflict(key, Some(b), None, Some(m)));
+                }
+            }
+            _ => {}
+        }
+    }
+    // 내가 추가한 행에는 디스크에서 새로 생긴 컬럼이 없으므로 기본값으로 채운다
+    for row in merged.rows.values_mut() {
+        fill_row(row, &merged.schema);
+    }
+    merged.order = disk.order.clone();
+    MergeResult { merged, conflicts, from_disk }
+}
+
+/// 행을 합친 스키마에 맞춘다 (없는 컬럼은 기본값)
+fn fill_row(row: &mut DynRow, schema: &TableSchema) {
+    for c in &schema.columns {
+        if row.get(&c.key).is_none() {
+            row.set(&c.key, c.dtype.default_value());
+        }
+    }
+}
+
+/// 기준 행 `b`에서 바뀐 셀이 있나. 저장되는 컬럼만 보고 (계산 컬럼 값은 디스크에서 읽은 행에 없다),
+/// `row`에 없는 컬럼(그쪽에서 지운 컬럼)은 안 바꾼 것으로 본다.
+fn changed(b: &DynRow, row: &DynRow, columns: &[String]) -> bool {
+    columns.iter().any(|c| row.get(c).is_some_and(|v| b.get(c) != Some(v)))
+}
+
+fn row_conflict(key: &str, b: Option<&DynRow>, d: Option<DynRow>, m: Option<&DynRow>) -> Conflict {
+    let describe = |exists: bool| exists.then(|| "(행 있음)".to_string());
+    Conflict {
+        key: key.to_string(),
+        column: String::new(),
+        base: describe(b.is_some()),
+        disk: describe(d.is_some()),
+        mine: describe(m.is_some()),
+        take_disk: false,
+        disk_row: d,
+    }
+}
@@ -0,0 +1,161 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::app_state::TableSpec;
+use super::backup::DEFAULT_KEEP;
+use super::export::ExportSettings;
+
+/// 최근 프로젝트 목록 최대 길이
+const MAX_RECENT: usize = 10;
+
+/// 상태 테이블 이름: 상태 키컬럼 모드가 이 테이블의 키 힌트를 덮어쓴다
+pub const STATUS_TABLE: &str = "status";
+
+// ===== 상태 키 컬럼 모드 =====
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum StatusKeyMode {
+    #[default]
+    Auto,               // 자동 추론(권장)
+    Unique,             // "Unique"
+    CharacterUnique,    // "CharacterUnique"
+    Custom(String),     // 임의 헤더명
+}
+
+impl StatusKeyMode {
+    pub fn as_hint(&self) -> &str {
+        match self {
+            StatusKeyMode::Auto => "CharacterUnique", // 기본 힌트(없으면 첫 컬럼 사용)
+            StatusKeyMode::Unique => "Unique",
+            StatusKeyMode::CharacterUnique => "CharacterUnique",
+            StatusKeyMode::Custom(s) => s.as_str(),
+        }
+    }
+    pub fn label(&self) -> String {
+        match self {
+            StatusKeyMode::Auto => "Auto (alias-like)".to_string(),
+            StatusKeyMode::Unique => "Unique".to_string(),
+            StatusKeyMode::CharacterUnique => "CharacterUnique".to_string(),
+            StatusKeyMode::Custom(s) => format!("Custom: {}", s),
+        }
+    }
+
+    /// 로드에 쓸 테이블 설정 (상태 테이블의 키 힌트만 이 모드로 바꿈)
+    pub fn apply(&self, specs: &[TableSpec]) -> Vec<TableSpec> {
+        specs
+            .iter()
+            .map(|spec| {
+                let mut spec = spec.clone();
+                if spec.name == STATUS_TABLE {
+                    spec.key_hint = self.as_hint().to_string();
+                }
+                spec
+            })
+            .collect()
+    }
+}
+
+/// 이름을 붙여 저장한 엔티티 필터 식 (`query` 모듈 문법)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NamedFilter {
+    pub name: String,
+    pub query: String,
+}
+
+/// 프로젝트 파일(JSON): 테이블 목록 + 상태 키 모드 + 저장된 필터 + 백업 보관 개수 + 내보내기 설정.
+/// 테이블 경로는 프로젝트 파일 위치 기준 상대 경로로 저장한다.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub tables: Vec<TableSpec>,
+    #[serde(default)]
+    pub status_key_mode: StatusKeyMode,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters: Vec<NamedFilter>,
+    #[serde(default = "default_backups")]
+    pub backups: usize, // 테이블마다 남길 백업 수 (0 = 백업 안 함)
+    #[serde(default, skip_serializing_if = "ExportSettings::is_default")]
+    pub export: ExportSettings,
+}
+
+fn default_backups() -> usize {
+    DEFAULT_KEEP
+}
+
+impl ProjectFile {
+    /// 읽은 뒤 상대 경로를 프로젝트 폴더 기준 경로로 바꿔 돌려준다.
+    pub fn load(path: &str) -> Result<Self> {
+        let text = fs::read_to_string(path).with_context(|| format!("open {}", path))?;
+        let mut project: ProjectFile =
+            serde_json::from_str(&text).with_context(|| format!("parse {}", path))?;
+
+        let base = project_dir(path);
+        for spec in &mut project.tables {
+            if Path::new(&spec.path).is_relative() {
+                spec.path = base.join(&spec.path).to_string_lossy().to_string();
+            }
+        }
+        Ok(project)
+    }
+
+    /// 프로젝트 폴더 아래 경로는 상대 경로로 바꿔 기록한다.
+    pub fn save(&self, path: &str) -> Result<()> {
+        let base = project_dir(path);
+        let mut out = self.clone();
+        for spec in &mut out.tables {
+            if let Ok(rel) = Path::new(&spec.path).strip_prefix(&base) {
+                spec.path = rel.to_string_lossy().replace('\\', "/");
+            }
+        }
+        let text = serde_json::to_string_pretty(&out)?;
+        fs::write(path, text).with_context(|| format!("write {}", path))?;
+        Ok(())
+    }
+}
+
+/// 프로젝트 파일이 있는 폴더 (상대 경로의 기준)
+pub fn project_dir(path: &str) -> PathBuf {
+    Path::new(path)
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default()
+}
+
+/// 최근에 연 프로젝트 목록 (사용자 홈의 `.entity_editor_recent.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentProjects {
+    pub paths: Vec<String>,
+}
+
+impl RecentProjects {
+    fn file_path() -> PathBuf {
+        let home = std::env::var_os("APPDATA")
+            .or_else(|| std::env::var_os("HOME"))
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        home.join(".entity_editor_recent.json")
+    }
+
+    /// 없거나 깨진 파일이면 빈 목록
+    pub fn load() -> Self {
+        fs::read_to_string(Self::file_path())
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(Self::file_path(), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// 맨 앞에 추가 (중복 제거, 최대 `MAX_RECENT`개)
+    pub fn push(&mut self, path: &str) {
+        self.paths.retain(|p| p != path);
+        self.paths.insert(0, path.to_string());
+        self.paths.truncate(MAX_RECENT);
+    }
+}
@@ -0,0 +1,713 @@
+//! 엔티티 필터/일괄 편집/계산 컬럼에 쓰는 작은 식 언어.
+//!
+//! ```text
+//! status.Health > 500 && info.Name ~ "orc"
+//! (attack.AttackPower + attack.DefensePower) * 2 >= 1000
+//! !(info.Rarity == "N") || key() < 100
+//! ```
+//! - 컬럼: `테이블.컬럼` 또는 `컬럼`(기본 테이블 → 앞쪽 테이블 순으로 찾음), 공백이 있으면 `` `컬럼 이름` ``
+//! - 연산: `+ - * / %`, `== != < <= > >=`, `~`(대소문자 무시 포함), `!~`, `&& || !` (`and`/`or`/`not`)
+//! - 함수: `key() len(x) lower(x) upper(x) abs(x) round(x) floor(x) ceil(x) min(a, b) max(a, b)`
+//! - 자식 테이블(엔티티마다 여러 행) 컬럼은 필터에서만: 자식 행 중 하나라도 식을 만족하면 일치.
+//!   자식 테이블을 여럿 쓰면 행 조합마다 평가하므로 엔티티당 `MAX_CHILD_COMBINATIONS`개까지만
+
+use std::{cmp::Ordering, fmt};
+
+use super::app_state::DataSets;
+use super::schema::DataType;
+use super::value;
+
+// ===== 값 =====
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null, // 행/컬럼 없음
+    Num(f64),
+    Str(String),
+    Bool(bool),
+}
+
+impl Value {
+    /// 셀 문자열: 숫자로 읽히면 Num, 아니면 Str
+    pub fn from_cell(s: &str) -> Self {
+        match s.trim().parse::<f64>() {
+            Ok(n) if !s.trim().is_empty() => Value::Num(n),
+            _ => Value::Str(s.to_string()),
+        }
+    }
+
+    pub fn truthy(&self) -> bool {
+        match self {
+            Value::Null => false,
+            Value::Num(n) => *n != 0.0,
+            Value::Str(s) => !s.is_empty(),
+            Value::Bool(b) => *b,
+        }
+    }
+
+    /// 컬럼 타입에 맞는 셀 문자열로. Int는 반올림, Bool은 `like`(기존 값) 표기 유지, Null은 빈 값.
+    pub fn to_cell(&self, dtype: &DataType, like: &str) -> Result<String, String> {
+        let text = self.to_string();
+        match dtype {
+            _ if *self == Value::Null => Ok(String::new()),
+            DataType::Int => match self {
+                Value::Num(n) => Ok((n.round() as i64).to_string()),
+                _ => text.trim().parse::<i64>().map(|n| n.to_string()).map_err(|_| format!("'{}'은(는) Int가 아닙니다", text)),
+            },
+            DataType::Float => match self {
+                Value::Num(_) => Ok(text),
+                _ => text.trim().parse::<f64>().map(|_| text.clone()).map_err(|_| format!("'{}'은(는) Float가 아닙니다", text)),
+            },
+            DataType::Bool => {
+                let b = match self {
+                    Value::Str(s) => value::parse_bool(s).ok_or_else(|| format!("'{}'은(는) Bool이 아닙니다", s))?,
+                    _ => self.truthy(),
+                };
+                Ok(value::format_bool(b, like))
+            }
+            DataType::Date => match value::Date::parse(&text) {
+                Some(d) if d.is_valid() => Ok(text),
+                _ => Err(format!("'{}'은(는) 날짜가 아닙니다", text)),
+            },
+            DataType::Enum(allowed) if !allowed.contains(&text) => {
+                Err(format!("'{}'은(는) 허용 값({})이 아닙니다", text, allowed.join("/")))
+            }
+            _ => Ok(text),
+        }
+    }
+
+    fn is_blank(&self) -> bool {
+        match self {
+            Value::Null => true,
+            Value::Str(s) => s.trim().is_empty(),
+            _ => false,
+        }
+    }
+
+    fn as_num(&self) -> Option<f64> {
+        match self {
+            Value::Num(n) => Some(*n),
+            Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+            Value::Str(s) => s.trim().parse().ok(),
+            Value::Null => None,
+        }
+    }
+
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::Null => "null",
+            Value::Num(_) => "숫자",
+            Value::Str(_) => "문자열",
+            Value::Bool(_) => "bool",
+        }
+    }
+}
+
+/// 셀에 다시 쓸 문자열. 숫자는 소수 10자리에서 반올림해 부동소수 잡음을 없앤다 (110.00000000000001 -> 110).
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => Ok(()),
+            Value::Num(n) => {
+                let s = format!("{:.10}", n);
+                let s = s.trim_end_matches('0').trim_end_matches('.');
+                write!(f, "{}", if s == "-0" { "0" } else { s })
+            }
+            Value::Str(s) => write!(f, "{}", s),
+            Value::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+// ===== 오류 =====
+
+/// 파싱/검사 오류: 원문 위치(문자 단위)와 메시지
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub pos: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(pos: usize, message: impl Into<String>) -> Self {
+        Self { pos, message: message.into() }
+    }
+
+    /// 원문 아래에 ^로 위치를 표시한 여러 줄 설명
+    pub fn render(&self, src: &str) -> String {
+        format!("{}\n{}\n{}^", self.message, src, " ".repeat(self.pos))
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}번째 글자: {}", self.pos + 1, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalError(pub String);
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+// ===== 토큰 =====
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Num(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+    Eof,
+}
+
+const OPS: [&str; 20] = [
+    "&&", "||", "==", "!=", "<=", ">=", "!~", "<", ">", "~", "!", "+", "-", "*", "/", "%", "(", ")", ",", ".",
+];
+
+fn lex(src: &str) -> Result<Vec<(Tok, usize)>, ParseError> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text
+                .parse()
+                .map_err(|_| ParseError::new(start, format!("잘못된 숫자 '{}'", text)))?;
+            out.push((Tok::Num(n), start));
+        } else if c == '"' || c == '\'' {
+            i += 1;
+            let mut s = String::new();
+            loop {
+                match chars.get(i) {
+                    None => return Err(ParseError::new(start, "닫히지 않은 문자열")),
+                    Some('\\') if i + 1 < chars.len() => {
+                        s.push(chars[i + 1]);
+                        i += 2;
+                    }
+                    Some(&q) if q == c => {
+                        i += 1;
+                        break;
+                    }
+                    Some(&ch) => {
+                        s.push(ch);
+                        i += 1;
+                    }
+                }
+            }
+            out.push((Tok::Str(s), start));
+        } else if c == '`' {
+            i += 1;
+            let begin = i;
+            while i < chars.len() && chars[i] != '`' {
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(ParseError::new(start, "닫히지 않은 `이름`"));
+            }
+            out.push((Tok::Ident(chars[begin..i].iter().collect()), start));
+            i += 1;
+        } else if c.is_alphanumeric() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let tok = match word.as_str() {
+                "and" => Tok::Op("&&"),
+                "or" => Tok::Op("||"),
+                "not" => Tok::Op("!"),
+                _ => Tok::Ident(word),
+            };
+            out.push((tok, start));
+        } else {
+            let rest: String = chars[i..chars.len().min(i + 2)].iter().collect();
+            let Some(op) = OPS.iter().find(|op| rest.starts_with(**op)) else {
+                // 흔한 실수: = 하나, & 하나, | 하나
+                let hint = match c {
+                    '=' => " ('=='를 쓰세요)",
+                    '&' => " ('&&'를 쓰세요)",
+                    '|' => " ('||'를 쓰세요)",
+                    _ => "",
+                };
+                return Err(ParseError::new(start, format!("알 수 없는 문자 '{}'{}", c, hint)));
+            };
+            i += op.chars().count();
+            out.push((Tok::Op(op), start));
+        }
+    }
+    out.push((Tok::Eof, chars.len()));
+    Ok(out)
+}
+
+// ===== 구문 트리 =====
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Lit(Value),
+    Column { table: Option<String>, column: String, pos: usize },
+    Not(Box<Expr>),
+    Neg(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Call { name: String, args: Vec<Expr>, pos: usize },
+}
+
+struct Parser {
+    toks: Vec<(Tok, usize)>,
+    i: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Tok {
+        &self.toks[self.i].0
+    }
+
+    fn pos(&self) -> usize {
+        self.toks[self.i].1
+    }
+
+    fn next(&mut self) -> (Tok, usize) {
+        let t = self.toks[self.i].clone();
+        if self.i + 1 < self.toks.len() {
+            self.i += 1;
+        }
+        t
+    }
+
+    fn eat(&mut self, op: &str) -> bool {
+        if matches!(self.peek(), Tok::Op(o) if *o == op) {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, op: &str) -> Result<(), ParseError> {
+        if self.eat(op) {
+            Ok(())
+        } else {
+            Err(ParseError::new(self.pos(), format!("'{}'이(가) 필요합니다", op)))
+        }
+    }
+
+    fn binary_level(
+        &mut self,
+        ops: &[&'static str],
+        next: fn(&mut Self) -> Result<Expr, ParseError>,
+    ) -> Result<Expr, ParseError> {
+        let mut lhs = next(self)?;
+        while let Tok::Op(op) = *self.peek() {
+            let Some(op) = ops.iter().find(|o| **o == op) else { break };
+            self.next();
+            let rhs = next(self)?;
+            lhs = Expr::Binary(op, Box::new(lhs), Box::new(rhs));
+        }
+        Ok(lhs)
+    }
+
+    fn or(&mut self) -> Result<Expr, ParseError> {
+        self.binary_level(&["||"], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Expr, ParseError> {
+        self.binary_level(&["&&"], Self::not)
+    }
+
+    fn not(&mut self) -> Result<Expr, ParseError> {
+        if self.eat("!") {
+            Ok(Expr::Not(Box::new(self.not()?)))
+        } else {
+            self.cmp()
+        }
+    }
+
+    fn cmp(&mut self) -> Result<Expr, ParseError> {
+        let lhs = self.add()?;
+        if let Tok::Op(op) = *self.peek() {
+            if ["==", "!=", "<", "<=", ">", ">=", "~", "!~"].contains(&op) {
+                self.next();
+                let rhs = self.add()?;
+                return Ok(Expr::Binary(op, Box::new(lhs), Box::new(rhs)));
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn add(&mut self) -> Result<Expr, ParseError> {
+        self.binary_level(&["+", "-"], Self::mul)
+    }
+
+    fn mul(&mut self) -> Result<Expr, ParseError> {
+        self.binary_level(&["*", "/", "%"], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ParseError> {
+        if self.eat("-") {
+            Ok(Expr::Neg(Box::new(self.unary()?)))
+        } else {
+            self.primary()
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, ParseError> {
+        let (tok, pos) = self.next();
+        match tok {
+            Tok::Num(n) => Ok(Expr::Lit(Value::Num(n))),
+            Tok::Str(s) => Ok(Expr::Lit(Value::Str(s))),
+            Tok::Ident(w) if w == "true" => Ok(Expr::Lit(Value::Bool(true))),
+            Tok::Ident(w) if w == "false" => Ok(Expr::Lit(Value::Bool(false))),
+            Tok::Ident(name) => {
+                if self.eat("(") {
+                    let mut args = Vec::new();
+                    if !self.eat(")") {
+                        loop {
+                            args.push(self.or()?);
+                            if self.eat(")") {
+                                break;
+                            }
+                            self.expect(",")?;
+                        }
+                    }
+                    Ok(Expr::Call { name, args, pos })
+                } else if self.eat(".") {
+                    match self.next() {
+                        (Tok::Ident(column), _) => Ok(Expr::Column { table: Some(name), column, pos }),
+                        (_, p) => Err(ParseError::new(p, "'.' 뒤에 컬럼 이름이 필요합니다")),
+                    }
+                } else {
+                    Ok(Expr::Column { table: None, column: name, pos })
+                }
+            }
+            Tok::Op("(") => {
+                let e = self.or()?;
+                self.expect(")")?;
+                Ok(e)
+            }
+            Tok::Eof => Err(ParseError::new(pos, "식이 끝났습니다 — 값이 필요합니다")),
+            Tok::Op(op) => Err(ParseError::new(pos, format!("여기에 '{}'은(는) 올 수 없습니다", op))),
+        }
+    }
+}
+
+/// 식 파싱. 빈 문자열은 오류.
+pub fn parse(src: &str) -> Result<Expr, ParseError> {
+    let toks = lex(src)?;
+    let mut p = Parser { toks, i: 0 };
+    let e = p.or()?;
+    if *p.peek() != Tok::Eof {
+        let hint = match p.peek() {
+            Tok::Ident(_) | Tok::Num(_) | Tok::Str(_) => " (연산자가 빠졌나요?)",
+            _ => "",
+        };
+        return Err(ParseError::new(p.pos(), format!("해석할 수 없는 나머지{}", hint)));
+    }
+    Ok(e)
+}
+
+// ===== 평가 =====
+
+/// 식이 읽는 엔티티 하나: 키 + 기본 테이블(테이블 이름 없이 쓴 컬럼을 먼저 찾는 곳)
+/// + 자식 테이블마다 지금 읽을 행 키 (`filter_keys`가 행마다 바꿔 가며 평가)
+pub struct Scope<'a> {
+    pub ds: &'a DataSets,
+    pub key: &'a str,
+    pub default_table: Option<&'a str>,
+    pub child_rows: &'a [(&'a str, &'a str)],
+}
+
+impl Scope<'_> {
+    fn column(&self, table: Option<&str>, column: &str) -> Result<Value, EvalError> {
+        let (t, col) = resolve_column(self.ds, table, column, self.default_table)
+            .map_err(|e| EvalError(e.message))?;
+        let t = self.ds.table(t).ok_or_else(|| EvalError(format!("테이블 '{}' 없음", t)))?;
+        let key = if t.is_child() {
+            match self.child_rows.iter().find(|(name, _)| *name == t.spec.name) {
+                Some((_, row)) => *row,
+                None => return Err(EvalError(child_table_message(&t.spec.name))),
+            }
+        } else {
+            self.key
+        };
+        Ok(match t.rows.get(key).and_then(|r| r.get(col)) {
+            Some(v) => Value::from_cell(v),
+            None => Value::Null,
+        })
+    }
+}
+
+/// (테이블 이름, 헤더명)으로 해석. 컬럼은 대소문자 무시.
+fn resolve_column<'a>(
+    ds: &'a DataSets,
+    table: Option<&str>,
+    column: &str,
+    default_table: Option<&str>,
+) -> Result<(&'a str, &'a str), ParseError> {
+    let find = |name: &str| {
+        let t = ds.table(name)?;
+        t.schema.find(column).map(|c| (t.spec.name.as_str(), c.key.as_str()))
+    };
+    match table {
+        Some(name) => {
+            if ds.table(name).is_none() {
+                let names: Vec<&str> = ds.tables.iter().map(|t| t.spec.name.as_str()).collect();
+                return Err(ParseError::new(0, format!("알 수 없는 테이블 '{}' (가능: {})", name, names.join(", "))));
+            }
+            find(name).ok_or_else(|| ParseError::new(0, format!("테이블 '{}'에 컬럼 '{}' 없음", name, column)))
+        }
+        None => default_table
+            .and_then(find)
+            .or_else(|| ds.tables.iter().find_map(|t| find(&t.spec.name)))
+            .ok_or_else(|| ParseError::new(0, format!("어느 테이블에도 컬럼 '{}' 없음", column))),
+    }
+}
+
+fn child_table_message(table: &str) -> String {
+    format!("'{}'은(는) 엔티티마다 여러 행인 테이블이라 필터에서만 쓸 수 있습니다", table)
+}
+
+const FUNCS: [(&str, usize); 10] = [
+    ("key", 0),
+    ("len", 1),
+    ("lower", 1),
+    ("upper", 1),
+    ("abs", 1),
+    ("round", 1),
+    ("floor", 1),
+    ("ceil", 1),
+    ("min", 2),
+    ("max", 2),
+];
+
+impl Expr {
+    /// 컬럼/함수 이름을 스키마와 맞춰 보고 첫 오류를 돌려준다 (행을 읽기 전에 한 번)
+    pub fn check(&self, ds: &DataSets, default_table: Option<&str>) -> Result<(), ParseError> {
+        match self {
+            Expr::Lit(_) => Ok(()),
+            Expr::Column { table, column, pos } => resolve_column(ds, table.as_deref(), column, default_table)
+                .map(|_| ())
+                .map_err(|e| ParseError::new(*pos, e.message)),
+            Expr::Not(e) | Expr::Neg(e) => e.check(ds, default_table),
+            Expr::Binary(_, a, b) => {
+                a.check(ds, default_table)?;
+                b.check(ds, default_table)
+            }
+            Expr::Call { name, args, pos } => {
+                let Some((_, arity)) = FUNCS.iter().find(|(f, _)| f == name) else {
+                    let names: Vec<&str> = FUNCS.iter().map(|(f, _)| *f).collect();
+                    return Err(ParseError::new(*pos, format!("알 수 없는 함수 '{}' (가능: {})", name, names.join(", "))));
+                };
+                if args.len() != *arity {
+                    return Err(ParseError::new(*pos, format!("{}()는 인자 {}개가 필요합니다", name, arity)));
+                }
+                args.iter().try_for_each(|a| a.check(ds, default_table))
+            }
+        }
+    }
+
+    /// `check` + 다른 자식 테이블 컬럼 금지 (행마다 값 하나가 필요한 일괄 편집/계산 컬럼용).
+    /// 기본 테이블은 지금 계산하는 그 행을 읽으므로 자식 테이블이어도 된다.
+    pub fn check_single_row(&self, ds: &DataSets, default_table: Option<&str>) -> Result<(), ParseError> {
+        self.check(ds, default_table)?;
+        let mut tables = Vec::new();
+        self.child_tables(ds, default_table, &mut tables);
+        match tables.iter().find(|(name, _)| Some(*name) != default_table) {
+            Some((table, pos)) => Err(ParseError::new(*pos, child_table_message(table))),
+            None => Ok(()),
+        }
+    }
+
+    /// 식이 읽는 자식 테이블 이름 (처음 나온 위치와 함께, 중복 없이)
+    fn child_tables<'a>(&self, ds: &'a DataSets, default_table: Option<&str>, out: &mut Vec<(&'a str, usize)>) {
+        match self {
+            Expr::Lit(_) => {}
+            Expr::Column { table, column, pos } => {
+                let Ok((name, _)) = resolve_column(ds, table.as_deref(), column, default_table) else { return };
+                if ds.table(name).is_some_and(|t| t.is_child()) && !out.iter().any(|(n, _)| *n == name) {
+                    out.push((name, *pos));
+                }
+            }
+            Expr::Not(e) | Expr::Neg(e) => e.child_tables(ds, default_table, out),
+            Expr::Binary(_, a, b) => {
+                a.child_tables(ds, default_table, out);
+                b.child_tables(ds, default_table, out);
+            }
+            Expr::Call { args, .. } => args.iter().for_each(|a| a.child_tables(ds, default_table, out)),
+        }
+    }
+
+    pub fn eval(&self, scope: &Scope) -> Result<Value, EvalError> {
+        match self {
+            Expr::Lit(v) => Ok(v.clone()),
+            Expr::Column { table, column, .. } => scope.column(table.as_deref(), column),
+            Expr::Not(e) => Ok(Value::Bool(!e.eval(scope)?.truthy())),
+            Expr::Neg(e) => match e.eval(scope)? {
+                Value::Null => Ok(Value::Null),
+                v => num(&v, "-").map(|n| Value::Num(-n)),
+            },
+            Expr::Binary("&&", a, b) => Ok(Value::Bool(a.eval(scope)?.truthy() && b.eval(scope)?.truthy())),
+            Expr::Binary("||", a, b) => Ok(Value::Bool(a.eval(scope)?.truthy() || b.eval(scope)?.truthy())),
+            Expr::Binary(op, a, b) => binary(op, a.eval(scope)?, b.eval(scope)?),
+            Expr::Call { name, args, .. } => {
+                let args = args.iter().map(|a| a.eval(scope)).collect::<Result<Vec<_>, _>>()?;
+                call(name, &args, scope)
+            }
+        }
+    }
+}
+
+fn num(v: &Value, op: &str) -> Result<f64, EvalError> {
+    v.as_num()
+        .ok_or_else(|| EvalError(format!("'{}'에 {} '{}'을(를) 쓸 수 없습니다", op, v.type_name(), v)))
+}
+
+fn binary(op: &str, a: Value, b: Value) -> Result<Value, EvalError> {
+    match op {
+        "+" | "-" | "*" | "/" | "%" => {
+            // 빈 셀은 산술에서 "값 없음"으로 본다 (결과도 빈 값)
+            if a.is_blank() || b.is_blank() {
+                return Ok(Value::Null);
+            }
+            // 문자열 + 는 이어 붙이기
+            if op == "+" && (a.as_num().is_none() || b.as_num().is_none()) {
+                return Ok(Value::Str(format!("{}{}", a, b)));
+            }
+            let (x, y) = (num(&a, op)?, num(&b, op)?);
+            Ok(Value::Num(match op {
+                "+" => x + y,
+                "-" => x - y,
+                "*" => x * y,
+                "/" | "%" if y == 0.0 => return Err(EvalError("0으로 나눌 수 없습니다".to_string())),
+                "/" => x / y,
+                _ => x % y,
+            }))
+        }
+        "~" | "!~" => {
+            let hit = a.to_string().to_lowercase().contains(&b.to_string().to_lowercase());
+            Ok(Value::Bool(hit == (op == "~")))
+        }
+        _ => {
+            if a == Value::Null || b == Value::Null {
+                let both = a == Value::Null && b == Value::Null;
+                return Ok(Value::Bool(match op {
+                    "==" => both,
+                    "!=" => !both,
+                    _ => false,
+                }));
+            }
+            // 둘 다 숫자로 읽히면 숫자 비교, 아니면 문자열 비교
+            let ord = match (a.as_num(), b.as_num()) {
+                (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+                _ => a.to_string().cmp(&b.to_string()),
+            };
+            Ok(Value::Bool(match op {
+                "==" => ord == Ordering::Equal,
+                "!=" => ord != Ordering::Equal,
+                "<" => ord == Ordering::Less,
+                "<=" => ord != Ordering::Greater,
+                ">" => ord == Ordering::Greater,
+                _ => ord != Ordering::Less,
+            }))
+        }
+    }
+}
+
+fn call(name: &str, args: &[Value], scope: &Scope) -> Result<Value, EvalError> {
+    let unary_num = |f: fn(f64) -> f64| -> Result<Value, EvalError> {
+        match &args[0] {
+            Value::Null => Ok(Value::Null),
+            v => Ok(Value::Num(f(num(v, name)?))),
+        }
+    };
+    match (name, args) {
+        ("key", []) => Ok(Value::from_cell(scope.key)),
+        ("len", [v]) => Ok(Value::Num(v.to_string().chars().count() as f64)),
+        ("lower", [v]) => Ok(Value::Str(v.to_string().to_lowercase())),
+        ("upper", [v]) => Ok(Value::Str(v.to_string().to_uppercase())),
+        ("abs", [_]) => unary_num(f64::abs),
+        ("round", [_]) => unary_num(f64::round),
+        ("floor", [_]) => unary_num(f64::floor),
+        ("ceil", [_]) => unary_num(f64::ceil),
+        ("min", [a, b]) => Ok(Value::Num(num(a, name)?.min(num(b, name)?))),
+        ("max", [a, b]) => Ok(Value::Num(num(a, name)?.max(num(b, name)?))),
+        _ => Err(EvalError(format!("함수 '{}' 호출 형식이 잘못되었습니다", name))),
+    }
+}
+
+// ===== 필터 =====
+
+/// 필터 결과: 일치한 키 + 평가 중 오류가 난 행 수와 첫 오류
+#[derive(Debug, Default)]
+pub struct FilterResult {
+    pub keys: Vec<String>,
+    pub errors: usize,
+    pub first_error: Option<String>,
+}
+
+/// 엔티티 하나에서 평가할 자식 행 조합의 상한 (테이블마다 행 수를 곱한 값).
+/// 넘으면 그 엔티티는 평가하지 않고 오류로 센다 — 자식 테이블 두 개에 각 100행이면 이미 10000번.
+pub const MAX_CHILD_COMBINATIONS: usize = 10_000;
+
+/// `keys` 중 식이 참인 것만 (순서 유지). 오류가 난 행은 불일치로 센다.
+/// 자식 테이블 컬럼이 있으면 그 엔티티의 자식 행 조합 중 하나라도 참이면 일치 (자식 행이 없으면 빈 값으로 한 번).
+pub fn filter_keys(ds: &DataSets, expr: &Expr, keys: &[String]) -> FilterResult {
+    let mut tables = Vec::new();
+    expr.child_tables(ds, None, &mut tables);
+    let mut out = FilterResult::default();
+    for k in keys {
+        match matches_any_child_row(ds, expr, k, &tables) {
+            Ok(true) => out.keys.push(k.clone()),
+            Ok(false) => {}
+            Err(e) => {
+                out.errors += 1;
+                out.first_error.get_or_insert_with(|| format!("키 {}: {}", k, e));
+            }
+        }
+    }
+    out
+}
+
+fn matches_any_child_row(ds: &DataSets, expr: &Expr, key: &str, tables: &[(&str, usize)]) -> Result<bool, EvalError> {
+    let rows: Vec<(&str, Vec<String>)> = tables
+        .iter()
+        .map(|(name, _)| {
+            let keys = ds.table(name).map(|t| t.child_keys(key)).unwrap_or_default();
+            (*name, if keys.is_empty() { vec![String::new()] } else { keys })
+        })
+        .collect();
+    let combinations = rows.iter().fold(1usize, |n, (_, keys)| n.saturating_mul(keys.len()));
+    if combinations > MAX_CHILD_COMBINATIONS {
+        return Err(EvalError(format!(
+            "자식 행 조합이 너무 많습니다 ({}개, 최대 {}) — 자식 테이블을 하나씩 나눠 필터하세요",
+            combinations, MAX_CHILD_COMBINATIONS
+        )));
+    }
+    // 자식 테이블마다 행 하나씩 고른 조합을 차례로 (마지막 테이블이 가장 빨리 바뀜)
+    let mut pick = vec![0; rows.len()];
+    loop {
+        let bound: Vec<(&str, &str)> = rows.iter().zip(&pick).map(|((name, keys), &i)| (*name, keys[i].as_str())).collect();
+        if expr.eval(&Scope { ds, key, default_table: None, child_rows: &bound })?.truthy() {
+            return Ok(true);
+        }
+        let Some(i) = (0..rows.len()).rev().find(|&i| pick[i] + 1 < rows[i].1.len()) else { return Ok(false) };
+        pick[i] += 1;
+        pick[i + 1..].iter_mut().for_each(|p| *p = 0);
+    }
+}
@@ -0,0 +1,113 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use super::app_state::DataSets;
+use super::schema::{ColumnRef, DataType};
+
+/// 참조 대상 컬럼의 값 -> 행 키 색인.
+/// 폼의 참조 선택기/이동 링크와 무결성 검사가 함께 쓴다.
+#[derive(Debug, Default)]
+pub struct RefIndex {
+    targets: HashMap<ColumnRef, BTreeMap<String, String>>,
+}
+
+impl RefIndex {
+    /// 스키마에 선언된 모든 Reference 컬럼의 대상만 색인
+    pub fn build(ds: &DataSets) -> Self {
+        let mut targets = HashMap::new();
+        for t in &ds.tables {
+            for col in &t.schema.columns {
+                let DataType::Reference(r) = &col.dtype else { continue };
+                if targets.contains_key(r) {
+                    continue;
+                }
+                if let Some(values) = index_target(ds, r) {
+                    targets.insert(r.clone(), values);
+                }
+            }
+        }
+        Self { targets }
+    }
+
+    /// 대상 값 목록(값 -> 행 키). 대상 테이블/컬럼이 없으면 None.
+    pub fn values(&self, r: &ColumnRef) -> Option<&BTreeMap<String, String>> {
+        self.targets.get(r)
+    }
+
+    /// 참조 값이 가리키는 행 키
+    pub fn resolve(&self, r: &ColumnRef, value: &str) -> Option<&str> {
+        self.values(r)?.get(value).map(|k| k.as_str())
+    }
+}
+
+/// 대상 컬럼 이름. 비어 있으면 대상 테이블의 키 컬럼.
+pub fn target_column<'a>(ds: &'a DataSets, r: &'a ColumnRef) -> Option<&'a str> {
+    let table = ds.table(&r.table)?;
+    if r.column.is_empty() {
+        Some(table.schema.key_column.as_str())
+    } else {
+        table.schema.find(&r.column).map(|c| c.key.as_str())
+    }
+}
+
+fn index_target(ds: &DataSets, r: &ColumnRef) -> Option<BTreeMap<String, String>> {
+    let table = ds.table(&r.table)?;
+    let column = target_column(ds, r)?;
+    let mut values = BTreeMap::new();
+    for (key, row) in &table.rows {
+        if let Some(v) = row.get(column) {
+            if !v.is_empty() {
+                values.entry(v.to_string()).or_insert_with(|| key.clone());
+            }
+        }
+    }
+    Some(values)
+}
+
+/// 대상이 없는 참조 하나
+#[derive(Debug, Clone)]
+pub struct DanglingRef {
+    pub table: String,  // 참조하는 쪽 테이블
+    pub key: String,    // 참조하는 쪽 행 키
+    pub column: String, // 참조하는 쪽 컬럼
+    pub value: String,
+    pub target: ColumnRef,
+}
+
+impl fmt::Display for DanglingRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let column = if self.target.column.is_empty() { "<key>" } else { &self.target.column };
+        write!(
+            f,
+            "{}[{}].{} = '{}' → {}.{} 없음",
+            self.table, self.key, self.column, self.value, self.target.table, column
+        )
+    }
+}
+
+/// 모든 Reference 컬럼을 검사해 끊어진 참조를 모은다. 빈 값은 "참조 없음"으로 본다.
+pub fn check_references(ds: &DataSets) -> Vec<DanglingRef> {
+    let index = RefIndex::build(ds);
+    let mut out = Vec::new();
+    for t in &ds.tables {
+        for col in &t.schema.columns {
+            let DataType::Reference(r) = &col.dtype else { continue };
+            for (key, row) in &t.rows {
+                let value = row.get(&col.key).unwrap_or("");
+                if value.is_empty() || index.resolve(r, value).is_some() {
+                    continue;
+                }
+                out.push(DanglingRef {
+                    table: t.spec.name.clone(),
+                    key: key.clone(),
+                    column: col.key.clone(),
+                    value: value.to_string(),
+                    target: r.clone(),
+                });
+            }
+        }
+    }
+    out
+}
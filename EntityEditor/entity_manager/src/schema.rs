@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use super::dialect::{Dialect, RawSource};
+use super::dyn_entity::{join_key, DynRow};
+
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DataType {
+Int,
+Float,
+Text,
+Bool, // true/false, 1/0, yes/no (원본 표기 유지)
+Enum(Vec<String>), // 허용 값 목록
+Date, // YYYY-MM-DD (구분자 '-', '/', '.' 허용)
+List(String), // 구분자로 이어진 목록 (예: "a;b;c")
+Reference(ColumnRef), // 다른 테이블의 컬럼 값
+}
+
+
+/// 다른 테이블의 컬럼을 가리키는 참조 (예: info.CharacterUnique)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ColumnRef {
+pub table: String, // TableSpec.name
+pub column: String, // 헤더명 (비우면 대상 테이블의 키 컬럼)
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ColumnDef {
+pub key: String, // canonical key (case-insensitive match)
+pub label: String, // display label (from header)
+pub dtype: DataType, // inferred or overridden
+#[serde(default, skip_serializing_if = "ColumnRules::is_empty")]
+pub rules: ColumnRules, // validation rules (사이드카에서 편집)
+#[serde(default, skip_serializing_if = "Option::is_none")]
+pub formula: Option<String>, // 계산 컬럼 식 (query 문법). 있으면 읽기 전용이고 데이터 파일에 쓰지 않음
+#[serde(default, skip_serializing_if = "Vec::is_empty")]
+pub aliases: Vec<String>, // 이전 헤더명 (이름 변경 시 기록, 생성 코드의 serde alias)
+}
+
+
+impl ColumnDef {
+pub fn is_computed(&self) -> bool { self.formula.is_some() }
+}
+
+
+/// 컬럼 검증 규칙. 빈 값은 `required`일 때만 오류.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ColumnRules {
+pub required: bool, // 빈 값 금지
+pub min: Option<f64>, // 수치 하한 (포함)
+pub max: Option<f64>, // 수치 상한 (포함)
+pub regex: Option<String>, // 전체 일치해야 하는 정규식
+pub unique: bool, // 테이블 안에서 중복 금지
+pub allowed: Vec<String>, // 허용 값 목록 (비우면 제한 없음)
+}
+
+
+impl ColumnRules {
+pub fn is_empty(&self) -> bool { *self == ColumnRules::default() }
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSchema {
+pub name: String, // e.g., "character_info"
+pub key_column: String, // e.g., "CharacterUnique"
+#[serde(default, skip_serializing_if = "Vec::is_empty")]
+pub sub_keys: Vec<String>, // 복합 키의 나머지 컬럼 (예: ["SkillSlot"]). 행 키는 `key_of`
+#[serde(default, skip_serializing_if = "Option::is_none")]
+pub parent_column: Option<String>, // 자식 테이블: 이 컬럼 값(엔티티 키)마다 여러 행
+pub columns: Vec<ColumnDef>, // includes key column too
+#[serde(default)]
+pub dialect: Dialect, // 데이터 파일 표기 방식 (로드할 때 감지해서 기록)
+#[serde(skip)]
+pub source: Option<RawSource>, // 원본 헤더 줄과 로드 때의 표기 방식 (행 원본 바이트를 다시 써도 되는지 판단)
+}
+
+
+impl DataType {
+/// 타입 선택용 기본값 목록 (파라미터는 빈 값/기본 구분자)
+pub fn templates() -> Vec<DataType> {
+vec![
+DataType::Int,
+DataType::Float,
+DataType::Text,
+DataType::Bool,
+DataType::Enum(Vec::new()),
+DataType::Date,
+DataType::List(";".to_string()),
+DataType::Reference(ColumnRef::default()),
+]
+}
+pub fn name(&self) -> &'static str {
+match self {
+DataType::Int => "Int",
+DataType::Float => "Float",
+DataType::Text => "Text",
+DataType::Bool => "Bool",
+DataType::Enum(_) => "Enum",
+DataType::Date => "Date",
+DataType::List(_) => "List",
+DataType::Reference(_) => "Reference",
+}
+}
+/// 새 행/새 컬럼의 기본 셀 값
+pub fn default_value(&self) -> String {
+match self {
+DataType::Int | DataType::Float => "0".to_string(),
+DataType::Bool => "false".to_string(),
+DataType::Enum(values) => values.first().cloned().unwrap_or_default(),
+_ => String::new(),
+}
+}
+/// 파라미터를 무시하고 같은 종류인지
+pub fn same_kind(&self, other: &DataType) -> bool {
+std::mem::discriminant(self) == std::mem::discriminant(other)
+}
+}
+
+
+impl TableSchema {
+pub fn find(&self, key: &str) -> Option<&ColumnDef> {
+self.columns.iter().find(|c| c.key.eq_ignore_ascii_case(key))
+}
+
+
+/// 키 컬럼 + 복합 키의 나머지 컬럼
+pub fn key_columns(&self) -> impl Iterator<Item = &str> {
+std::iter::once(self.key_column.as_str()).chain(self.sub_keys.iter().map(String::as_str))
+}
+
+
+pub fn is_key_column(&self, column: &str) -> bool {
+self.key_columns().any(|k| k == column)
+}
+
+
+/// 한 엔티티에 여러 행이 있는 테이블인지
+pub fn is_child(&self) -> bool {
+self.parent_column.is_some()
+}
+
+
+/// 셀 값으로 만든 행 키 (복합 키면 `join_key`)
+pub fn key_of(&self, row: &DynRow) -> String {
+let parts: Vec<&str> = self.key_columns().map(|c| row.get(c).unwrap_or("")).collect();
+join_key(&parts)
+}
+
+
+/// 행이 속한 엔티티 키 (자식 테이블이 아니면 행 키)
+pub fn parent_of<'a>(&self, key: &'a str, row: &'a DynRow) -> &'a str {
+match &self.parent_column {
+Some(p) => row.get(p).unwrap_or(""),
+None => key,
+}
+}
+
+
+/// 저장/내보내기할 셀 값. 단일 키 컬럼은 행 키(맵의 키)를 쓰고, 복합 키는 셀 값 그대로.
+pub fn cell<'a>(&self, key: &'a str, row: &'a DynRow, column: &str) -> &'a str {
+if column == self.key_column && self.sub_keys.is_empty() { key } else { row.get(column).unwrap_or("") }
+}
+
+
+/// 사이드카(수동 편집) 스키마를 추론 결과 위에 덮어쓴다.
+/// - 사이드카에 있는 컬럼: dtype/label/순서를 사이드카 기준으로
+/// - 파일에만 있는 새 컬럼: 추론 결과 그대로 뒤에 붙임
+/// - 파일에서 사라진 컬럼: 무시 (계산 컬럼은 파일에 없으므로 사이드카 그대로 유지)
+/// - 표기 방식(`dialect`)은 파일에서 감지한 쪽이 우선 (사이드카 값은 기록용)
+pub fn apply_overrides(&mut self, sidecar: &TableSchema) {
+let mut inferred = std::mem::take(&mut self.columns);
+for o in &sidecar.columns {
+if let Some(pos) = inferred.iter().position(|c| c.key == o.key) {
+let mut c = inferred.remove(pos);
+c.dtype = o.dtype.clone();
+c.label = o.label.clone();
+c.rules = o.rules.clone();
+c.formula = o.formula.clone();
+c.aliases = o.aliases.clone();
+self.columns.push(c);
+} else if o.is_computed() {
+self.columns.push(o.clone());
+}
+}
+self.columns.extend(inferred);
+}
+}
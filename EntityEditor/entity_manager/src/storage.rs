@@ -0,0 +1,470 @@
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Result};
+use csv::{ReaderBuilder, StringRecord};
+
+use super::backup;
+use super::dialect::{trim_line_end, Dialect, RawRecord, RawSource, TextEncoding, UTF8_BOM};
+use super::dyn_entity::{compare_keys, join_key, DynRow};
+use super::key_conflicts::{ConflictKind, KeyConflict};
+use super::schema::{ColumnDef, ColumnRules, DataType, TableSchema};
+use super::value::Date;
+
+/// 아주 가벼운 타입 추론: 전부 Int면 Int, 전부 수치면 Float,
+/// 전부 true/false면 Bool, 전부 날짜면 Date, 그 외 Text
+/// (Enum/List/Reference는 추론하지 않음 — 스키마 편집으로 지정)
+fn infer_dtype(samples: &[&str]) -> DataType {
+    let is_int = |s: &str| s.parse::<i64>().is_ok();
+    let is_float = |s: &str| s.parse::<f64>().is_ok();
+    let is_bool = |s: &str| s.eq_ignore_ascii_case("true") || s.eq_ignore_ascii_case("false");
+
+    if !samples.is_empty() && samples.iter().all(|s| is_int(s)) {
+        DataType::Int
+    } else if samples.iter().all(|s| s.is_empty() || is_float(s)) {
+        DataType::Float
+    } else if samples.iter().all(|s| s.is_empty() || is_bool(s)) {
+        DataType::Bool
+    } else if samples.iter().all(|s| s.is_empty() || Date::parse(s).is_some()) {
+        DataType::Date
+    } else {
+        DataType::Text
+    }
+}
+
+/// `load_table` 결과: (스키마, key->행, 파일의 행 순서, 키 문제 행)
+pub type LoadedTable = (TableSchema, BTreeMap<String, DynRow>, Vec<String>, Vec<KeyConflict>);
+
+/// CSV를 헤더/미지의 컬럼까지 보존하여 읽기.
+/// 표기 방식은 파일에서 감지하고(`delimiter`는 힌트, `Dialect::sniff`), 행마다 원본 바이트(UTF-8)를 남긴다.
+/// `encoding`이 None이면 감지 (ASCII뿐이면 사이드카에 기록된 인코딩)
+/// 키가 비었거나 앞 행과 겹치는 행은 `rows`에 넣지 않고 `KeyConflict`로 돌려준다 (먼저 나온 행이 남음).
+/// `key_hint`가 "CharacterUnique+SkillSlot"처럼 '+'로 이어져 있으면 복합 키 (모든 컬럼이 헤더에 있어야 함)
+pub fn load_table(
+    path: &str,
+    key_hint: &str,
+    delimiter: char,
+    encoding: Option<TextEncoding>,
+) -> Result<LoadedTable> {
+    let bytes = fs::read(path).with_context(|| format!("open {}", path))?;
+    let encoding_known = encoding.is_some() || !bytes.is_ascii();
+    let (mut schema, rows_by_key, order, conflicts) = parse_table(path, &bytes, key_hint, delimiter, encoding)?;
+
+    // 사이드카 스키마가 있으면 추론 결과보다 우선
+    if let Some(sidecar) = load_schema_sidecar(path)? {
+        schema.apply_overrides(&sidecar);
+        if !encoding_known {
+            schema.dialect.encoding = sidecar.dialect.encoding;
+        }
+    }
+
+    Ok((schema, rows_by_key, order, conflicts))
+}
+
+/// 사이드카 없이 파일 내용만으로 읽기 (`load_table`에서 사이드카 적용 전 단계)
+fn parse_table(
+    path: &str,
+    bytes: &[u8],
+    key_hint: &str,
+    delimiter: char,
+    encoding: Option<TextEncoding>,
+) -> Result<LoadedTable> {
+    let encoding = encoding.unwrap_or_else(|| TextEncoding::detect(bytes));
+    let text = encoding.decode(bytes).with_context(|| format!("read {}", path))?;
+    let data = text.as_bytes();
+    let mut dialect = Dialect { encoding, ..Dialect::sniff(data, delimiter) };
+    let body = if dialect.bom { &data[UTF8_BOM.len()..] } else { data };
+
+    // 레코드와 시작 위치 -> 원본 바이트는 다음 레코드 시작까지 (빈 줄 포함)
+    let mut rdr = ReaderBuilder::new()
+        .flexible(true)
+        .has_headers(false)
+        .delimiter(dialect.delimiter_byte())
+        .from_reader(body);
+    let mut records: Vec<(StringRecord, usize)> = Vec::new();
+    let mut rec = StringRecord::new();
+    while rdr.read_record(&mut rec).with_context(|| format!("read {}", path))? {
+        // CRLF면 위치가 앞 줄의 '\n'을 가리킬 수 있으므로 줄바꿈은 건너뛴다
+        let mut start = rec.position().map_or(0, |p| p.byte() as usize);
+        while matches!(body.get(start), Some(b'\r' | b'\n')) {
+            start += 1;
+        }
+        records.push((rec.clone(), start));
+    }
+    let raw: Vec<RawRecord> = records
+        .iter()
+        .enumerate()
+        .map(|(i, (rec, start))| {
+            let end = records.get(i + 1).map_or(body.len(), |(_, next)| *next);
+            RawRecord { fields: rec.iter().map(str::to_string).collect(), bytes: body[*start..end].to_vec() }
+        })
+        .collect();
+    dialect.quoting = dialect.detect_quoting(&raw);
+    let mut raw = raw.into_iter();
+    let source = raw.next().map(|header| RawSource { header, dialect: dialect.clone() });
+
+    let headers: StringRecord = records.first().map(|(r, _)| r.clone()).unwrap_or_default();
+
+    // 키 컬럼 선택: 정확 일치 > 대소문자 무시 일치 > 첫 컬럼
+    let find_header = |hint: &str| {
+        headers
+            .iter()
+            .find(|h| *h == hint)
+            .or_else(|| headers.iter().find(|h| h.eq_ignore_ascii_case(hint)))
+    };
+    let mut key_cols: Vec<&str> = Vec::new();
+    if key_hint.contains('+') {
+        for part in key_hint.split('+').map(str::trim) {
+            match find_header(part) {
+                Some(h) if !key_cols.contains(&h) => key_cols.push(h),
+                Some(_) => bail!("{}: 복합 키에 컬럼 '{}'이(가) 두 번 있습니다", path, part),
+                None => bail!("{}: 복합 키 컬럼 '{}'이(가) 헤더에 없습니다", path, part),
+            }
+        }
+    } else {
+        key_cols.push(find_header(key_hint).unwrap_or_else(|| headers.get(0).unwrap_or("id")));
+    }
+    let key_col = key_cols[0];
+
+    // dtype 추론을 위한 샘플 수집
+    let mut col_samples: HashMap<String, Vec<String>> = HashMap::new();
+
+    // key -> DynRow
+    let mut rows_by_key: BTreeMap<String, DynRow> = BTreeMap::new();
+    let mut order: Vec<String> = Vec::new();
+
+    // key 컬럼(들)의 인덱스
+    let key_idx: Vec<usize> = key_cols
+        .iter()
+        .map(|k| headers.iter().position(|h| h == *k).unwrap_or(0))
+        .collect();
+
+    // 키가 비었거나 겹치는 행은 덮어쓰지 않고 따로 (줄 번호는 레코드 시작 위치로 센다)
+    let mut first_lines: HashMap<String, usize> = HashMap::new();
+    let mut conflicts: Vec<KeyConflict> = Vec::new();
+    let (mut line, mut counted) = (1, 0);
+
+    for ((rec, start), raw) in records.iter().skip(1).zip(raw) {
+        line += body[counted..*start].iter().filter(|&&b| b == b'\n').count();
+        counted = *start;
+
+        let mut cells = HashMap::new();
+
+        for (i, h) in headers.iter().enumerate() {
+            let v = rec.get(i).unwrap_or("").to_string();
+            cells.insert(h.to_string(), v.clone());
+
+            col_samples.entry(h.to_string()).or_default();
+            if col_samples[&h.to_string()].len() < 8 {
+                col_samples.get_mut(h).unwrap().push(v);
+            }
+        }
+
+        let parts: Vec<&str> = key_idx.iter().map(|&i| rec.get(i).unwrap_or("")).collect();
+        let key = join_key(&parts);
+        let row = DynRow {
+            key: key.clone(),
+            cells,
+            raw: Some(raw),
+        };
+        let kind = if parts.iter().any(|p| p.trim().is_empty()) {
+            ConflictKind::Empty
+        } else if first_lines.contains_key(&key) {
+            ConflictKind::Duplicate
+        } else {
+            first_lines.insert(key.clone(), line);
+            order.push(key.clone());
+            rows_by_key.insert(key, row);
+            continue;
+        };
+        conflicts.push(KeyConflict {
+            kind,
+            line,
+            first_line: first_lines.get(&key).copied(),
+            after: order.last().cloned(),
+            row,
+        });
+    }
+
+    // 스키마 구성
+    let columns: Vec<ColumnDef> = headers
+        .iter()
+        .map(|h: &str| {
+            let samples: Vec<&str> = col_samples
+                .get(h)
+                .map(|v| v.iter().map(|s| s.as_str()).collect())
+                .unwrap_or_default();
+            let dtype = if samples.is_empty() {
+                DataType::Text
+            } else {
+                infer_dtype(&samples)
+            };
+            ColumnDef {
+                key: h.to_string(),
+                label: h.to_string(),
+                dtype,
+                rules: ColumnRules::default(),
+                formula: None,
+                aliases: Vec::new(),
+            }
+        })
+        .collect();
+
+    let schema = TableSchema {
+        name: Path::new(path)
+            .file_stem()
+            .unwrap()
+            .to_string_lossy()
+            .to_string(),
+        key_column: key_col.to_string(),
+        sub_keys: key_cols[1..].iter().map(|k| k.to_string()).collect(),
+        parent_column: None,
+        columns,
+        dialect,
+        source,
+    };
+
+    Ok((schema, rows_by_key, order, conflicts))
+}
+
+/// 데이터 파일 옆의 스키마 사이드카 경로 (예: character_info.csv -> character_info.schema.json)
+pub fn schema_sidecar_path(path: &str) -> PathBuf {
+    let p = Path::new(path);
+    let stem = p.file_stem().unwrap_or_default().to_string_lossy();
+    p.with_file_name(format!("{}.schema.json", stem))
+}
+
+/// 사이드카 스키마 읽기. 파일이 없으면 None.
+pub fn load_schema_sidecar(path: &str) -> Result<Option<TableSchema>> {
+    let sidecar = schema_sidecar_path(path);
+    if !sidecar.exists() {
+        return Ok(None);
+    }
+    let text = fs::read_to_string(&sidecar)
+        .with_context(|| format!("open {}", sidecar.display()))?;
+    let schema = serde_json::from_str(&text)
+        .with_context(|| format!("parse {}", sidecar.display()))?;
+    Ok(Some(schema))
+}
+
+pub fn save_schema_sidecar(path: &str, schema: &TableSchema) -> Result<()> {
+    write_files(&[render_schema_sidecar(path, schema)?], 0)
+}
+
+/// 저장할 때 사이드카도 쓸지. 이미 있으면 계속 맞춰 쓰고, 없으면 `bytes`(저장할 데이터 파일)를
+/// 사이드카 없이 다시 읽었을 때 컬럼(순서/dtype/규칙/계산식)이나 인코딩이 달라지는 경우만.
+pub fn needs_schema_sidecar(path: &str, schema: &TableSchema, bytes: &[u8]) -> bool {
+    if schema_sidecar_path(path).exists() {
+        return true;
+    }
+    let key_hint = schema.key_columns().collect::<Vec<_>>().join("+");
+    match parse_table(path, bytes, &key_hint, schema.dialect.delimiter, None) {
+        Ok((inferred, ..)) => inferred.columns != schema.columns || inferred.dialect.encoding != schema.dialect.encoding,
+        Err(_) => true,
+    }
+}
+
+/// 사이드카 스키마 내용 (아직 쓰지 않음)
+pub fn render_schema_sidecar(path: &str, schema: &TableSchema) -> Result<FileWrite> {
+    let sidecar = schema_sidecar_path(path);
+    Ok(FileWrite {
+        path: sidecar.to_string_lossy().to_string(),
+        bytes: serde_json::to_string_pretty(schema)?.into_bytes(),
+        backup: false,
+    })
+}
+
+/// 저장 순서: `order`(로드 당시 행 순서)에 있고 아직 메모리에 있는 key,
+/// 그 다음 `order`에 없는 새 key (key 순). 중복은 한 번만.
+pub fn ordered_keys<'a>(rows: &'a BTreeMap<String, DynRow>, order: &'a [String]) -> Vec<&'a String> {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut keys: Vec<&String> = order
+        .iter()
+        .filter(|k| rows.contains_key(*k) && seen.insert(k.as_str()))
+        .collect();
+    let mut new_keys: Vec<&String> = rows.keys().filter(|k| !seen.contains(k.as_str())).collect();
+    new_keys.sort_by(|a, b| compare_keys(a, b));
+    keys.extend(new_keys);
+    keys
+}
+
+/// 테이블 하나를 파일 하나로 저장 (`render_table` + `write_files`, 백업 없음)
+pub fn save_table(
+    path: &str,
+    schema: &TableSchema,
+    rows: &BTreeMap<String, DynRow>,
+    order: &[String],
+    conflicts: &[KeyConflict],
+    include_computed: bool,
+) -> Result<()> {
+    let bytes = render_table(schema, rows, order, conflicts, include_computed)?;
+    write_files(&[FileWrite { path: path.to_string(), bytes, backup: false }], 0)
+}
+
+/// 스키마의 컬럼 순서대로 헤더를 쓰고, 메모리의 행을 기록한 파일 내용.
+/// - 컬럼 추가/이름 변경/순서/삭제는 스키마 그대로 반영
+/// - 계산 컬럼은 `include_computed`(명시적 내보내기)일 때만 기록
+/// - 기존 행 순서 보존, 삭제된 key는 빠지고 새 key는 끝에 추가 (`ordered_keys`)
+/// - 표기 방식은 `schema.dialect`. 컬럼 구성과 값이 로드 때와 같은 줄은 원본 바이트 그대로
+/// - 인코딩에 없는 글자는 '?'로 저장된다 (검증에서 경고)
+/// - 해결하지 않은 키 문제 행(`conflicts`)은 원래 자리(앞 행 뒤, 앞 행이 없어졌으면 끝)에
+pub fn render_table<'a>(
+    schema: &'a TableSchema,
+    rows: &'a BTreeMap<String, DynRow>,
+    order: &[String],
+    conflicts: &'a [KeyConflict],
+    include_computed: bool,
+) -> Result<Vec<u8>> {
+    let dialect = &schema.dialect;
+    let columns: Vec<&str> = schema
+        .columns
+        .iter()
+        .filter(|c| include_computed || !c.is_computed())
+        .map(|c| c.key.as_str())
+        .collect();
+
+    // 줄마다 원본 또는 새로 쓴 바이트
+    let mut lines: Vec<Cow<[u8]>> = Vec::new();
+    // 원본 바이트는 UTF-8로 들고 있으므로 인코딩만 바뀐 경우는 그대로 쓸 수 있다
+    let reuse = schema.source.as_ref().filter(|s| {
+        Dialect { encoding: dialect.encoding, ..s.dialect.clone() } == *dialect && s.header.fields == columns
+    });
+    lines.push(match reuse {
+        Some(s) => Cow::Borrowed(&s.header.bytes),
+        None => Cow::Owned(dialect.write_record(&columns)?),
+    });
+    let row_line = |key: &str, row: &'a DynRow| -> Result<Cow<'a, [u8]>> {
+        let fields: Vec<&str> = columns
+            .iter()
+            .map(|&c| schema.cell(key, row, c))
+            .collect();
+        Ok(match &row.raw {
+            Some(raw) if reuse.is_some() && raw.matches(&fields) => Cow::Borrowed(&raw.bytes),
+            _ => Cow::Owned(dialect.write_record(&fields)?),
+        })
+    };
+    let keys = ordered_keys(rows, order);
+    let present: HashSet<&str> = keys.iter().map(|k| k.as_str()).collect();
+    for c in conflicts.iter().filter(|c| c.after.is_none()) {
+        lines.push(row_line(&c.row.key, &c.row)?);
+    }
+    for key in keys {
+        lines.push(row_line(key, &rows[key])?);
+        for c in conflicts.iter().filter(|c| c.after.as_ref() == Some(key)) {
+            lines.push(row_line(&c.row.key, &c.row)?);
+        }
+    }
+    for c in conflicts.iter().filter(|c| c.after.as_deref().is_some_and(|a| !present.contains(a))) {
+        lines.push(row_line(&c.row.key, &c.row)?);
+    }
+
+    let mut out = Vec::<u8>::new();
+    let last = lines.len() - 1;
+    for (i, line) in lines.iter().enumerate() {
+        if i < last || dialect.final_newline {
+            out.extend_from_slice(line);
+            if !line.ends_with(b"\n") {
+                out.extend_from_slice(dialect.line_ending.as_str().as_bytes()); // 원래 마지막 줄이었던 행
+            }
+        } else {
+            out.extend_from_slice(trim_line_end(line));
+        }
+    }
+
+    // 모두 UTF-8 문자열에서 온 바이트. 다른 인코딩이면 여기서 바꾼다 (BOM은 UTF-8일 때만)
+    let mut bytes = match dialect.encoding {
+        TextEncoding::Utf8 => out,
+        enc => enc.encode(&String::from_utf8(out)?),
+    };
+    if dialect.bom && dialect.encoding == TextEncoding::Utf8 {
+        bytes.splice(0..0, UTF8_BOM.iter().copied());
+    }
+    Ok(bytes)
+}
+
+/// 묶음 저장할 파일 하나
+#[derive(Debug, Clone)]
+pub struct FileWrite {
+    pub path: String,
+    pub bytes: Vec<u8>,
+    pub backup: bool, // 덮어쓴 원본을 백업 폴더에 남길지 (데이터 파일만)
+}
+
+fn tmp_path(path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.tmp~", path))
+}
+
+fn old_path(path: &str) -> PathBuf {
+    PathBuf::from(format!("{}.old~", path))
+}
+
+/// 여러 파일을 한 묶음으로 안전하게 쓴다.
+/// 1) 모두 `<파일>.tmp~`에 쓰고 디스크까지 flush — 하나라도 실패하면 임시 파일만 지우고 원본은 그대로
+/// 2) 원본을 `<파일>.old~`로 옮기고 임시 파일을 제자리로 rename — 중간에 실패하면 앞서 바꾼 파일까지 되돌림
+/// 3) 모두 끝나면 `.old~`는 백업 폴더로 보내거나(`backup`, 최대 `keep_backups`개) 지운다
+///
+/// 내용이 디스크와 같은 파일은 건드리지 않는다 (쓸데없는 백업이 쌓이지 않게).
+pub fn write_files(files: &[FileWrite], keep_backups: usize) -> Result<()> {
+    let files: Vec<&FileWrite> = files
+        .iter()
+        .filter(|f| !fs::read(&f.path).is_ok_and(|cur| cur == f.bytes))
+        .collect();
+    let cleanup = || {
+        for f in &files {
+            let _ = fs::remove_file(tmp_path(&f.path));
+        }
+    };
+
+    for f in &files {
+        let written = File::create(tmp_path(&f.path))
+            .and_then(|mut file| file.write_all(&f.bytes).and_then(|_| file.sync_all()));
+        if let Err(e) = written {
+            cleanup();
+            return Err(e).with_context(|| format!("write {}", f.path));
+        }
+    }
+
+    let mut done: Vec<(&FileWrite, bool)> = Vec::new(); // (파일, 원본이 있었나)
+    let rollback = |done: &[(&FileWrite, bool)]| {
+        for (f, had) in done.iter().rev() {
+            if *had {
+                let _ = fs::rename(old_path(&f.path), &f.path);
+            } else {
+                let _ = fs::remove_file(&f.path);
+            }
+        }
+    };
+    for f in files.iter().copied() {
+        let had = Path::new(&f.path).exists();
+        if had {
+            if let Err(e) = fs::rename(&f.path, old_path(&f.path)) {
+                rollback(&done);
+                cleanup();
+                return Err(e).with_context(|| format!("replace {}", f.path));
+            }
+        }
+        if let Err(e) = fs::rename(tmp_path(&f.path), &f.path) {
+            if had {
+                let _ = fs::rename(old_path(&f.path), &f.path);
+            }
+            rollback(&done);
+            cleanup();
+            return Err(e).with_context(|| format!("replace {}", f.path));
+        }
+        done.push((f, had));
+    }
+
+    // 저장은 끝났으므로 백업 실패는 원본 조각만 지우고 넘어간다
+    for (f, had) in done {
+        let old = old_path(&f.path);
+        if had && !(f.backup && keep_backups > 0 && backup::archive(&f.path, &old, keep_backups).is_ok()) {
+            let _ = fs::remove_file(&old);
+        }
+    }
+    Ok(())
+}
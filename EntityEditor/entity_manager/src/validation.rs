@@ -0,0 +1,215 @@
+use std::{collections::HashMap, fmt};
+
+use regex::Regex;
+
+use super::app_state::{DataSets, Table};
+use super::computed::{self, ERROR_PREFIX};
+use super::dyn_entity::display_key;
+use super::references::check_references;
+use super::schema::{ColumnDef, DataType};
+use super::value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,   // 저장 전 확인 필요
+    Warning, // 타입과 맞지 않는 값 등 (저장은 막지 않음)
+}
+
+/// 검증 결과 한 건: 어느 셀에서 무엇이 잘못됐는지
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub table: String,  // TableSpec.name
+    pub key: String,    // 행 키 (컬럼 전체 문제면 빈 문자열)
+    pub column: String, // 헤더명
+    pub message: String,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let icon = match self.severity {
+            Severity::Error => "❌",
+            Severity::Warning => "⚠️",
+        };
+        write!(f, "{} {}[{}].{}: {}", icon, self.table, display_key(&self.key), self.column, self.message)
+    }
+}
+
+pub fn has_errors(diags: &[Diagnostic]) -> bool {
+    diags.iter().any(|d| d.severity == Severity::Error)
+}
+
+/// 모든 테이블에 대해 키 중복·빈 키/컬럼 규칙/타입/참조/계산식을 검사. 오류가 먼저 오도록 정렬.
+pub fn validate(ds: &DataSets) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+    let broken = computed::check(ds);
+    for t in &ds.tables {
+        for col in &t.schema.columns {
+            if !col.is_computed() {
+                validate_column(t, col, &mut out);
+            } else if !broken.iter().any(|(bt, bc, _)| *bt == t.spec.name && *bc == col.key) {
+                computed_errors(t, col, &mut out); // 식 자체가 틀렸으면 행별 경고는 생략
+            }
+        }
+    }
+    for (table, column, e) in broken {
+        out.push(Diagnostic {
+            severity: Severity::Error,
+            table,
+            key: String::new(),
+            column,
+            message: format!("계산식 오류: {}", e),
+        });
+    }
+    for t in &ds.tables {
+        for c in &t.conflicts {
+            out.push(Diagnostic {
+                severity: Severity::Error,
+                table: t.spec.name.clone(),
+                key: c.row.key.clone(),
+                column: t.schema.key_column.clone(),
+                message: c.to_string(),
+            });
+        }
+    }
+    for d in check_references(ds) {
+        let target = if d.target.column.is_empty() { "<key>" } else { &d.target.column };
+        out.push(Diagnostic {
+            severity: Severity::Error,
+            message: format!("'{}' → {}.{} 없음 (끊어진 참조)", d.value, d.target.table, target),
+            table: d.table,
+            key: d.key,
+            column: d.column,
+        });
+    }
+    out.extend(encoding_warnings(&ds.tables));
+    out.sort_by_key(|d| d.severity);
+    out
+}
+
+/// 파일 인코딩(예: CP949)으로 쓸 수 없는 헤더/값. 저장하면 그 글자는 '?'로 바뀐다.
+/// 계산 컬럼은 데이터 파일에 쓰지 않으므로 제외.
+pub fn encoding_warnings(tables: &[Table]) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+    for t in tables {
+        let encoding = t.schema.dialect.encoding;
+        let mut check = |key: &str, column: &str, v: &str, what: &str| {
+            let bad = encoding.unencodable(v);
+            if !bad.is_empty() {
+                out.push(Diagnostic {
+                    severity: Severity::Warning,
+                    table: t.spec.name.clone(),
+                    key: key.to_string(),
+                    column: column.to_string(),
+                    message: format!("{}'{}'은(는) {}로 저장할 수 없습니다 (저장하면 '?'로 바뀜)", what, bad, encoding.label()),
+                });
+            }
+        };
+        for col in t.schema.columns.iter().filter(|c| !c.is_computed()) {
+            check("", &col.key, &col.key, "헤더의 ");
+            for (key, row) in &t.rows {
+                check(key, &col.key, row.get(&col.key).unwrap_or(""), "");
+            }
+        }
+    }
+    out
+}
+
+fn validate_column(t: &Table, col: &ColumnDef, out: &mut Vec<Diagnostic>) {
+    let rules = &col.rules;
+    let mut push = |severity, key: &str, message: String| {
+        out.push(Diagnostic {
+            severity,
+            table: t.spec.name.clone(),
+            key: key.to_string(),
+            column: col.key.clone(),
+            message,
+        })
+    };
+
+    // 정규식은 전체 일치로 검사
+    let regex = match rules.regex.as_deref().filter(|r| !r.is_empty()) {
+        Some(pat) => match Regex::new(&format!("^(?:{})$", pat)) {
+            Ok(re) => Some(re),
+            Err(e) => {
+                push(Severity::Error, "", format!("잘못된 정규식 '{}': {}", pat, e));
+                None
+            }
+        },
+        None => None,
+    };
+
+    let mut seen: HashMap<&str, &str> = HashMap::new(); // 값 -> 처음 나온 키
+    for (key, row) in &t.rows {
+        let v = row.get(&col.key).unwrap_or("");
+        if v.trim().is_empty() {
+            if rules.required {
+                push(Severity::Error, key, "값이 필요합니다".to_string());
+            }
+            continue;
+        }
+
+        if let Some(msg) = type_mismatch(&col.dtype, v) {
+            push(Severity::Warning, key, msg);
+        }
+
+        if rules.min.is_some() || rules.max.is_some() {
+            match v.trim().parse::<f64>() {
+                Ok(n) => {
+                    if let Some(min) = rules.min.filter(|m| n < *m) {
+                        push(Severity::Error, key, format!("{} < 최솟값 {}", v, min));
+                    }
+                    if let Some(max) = rules.max.filter(|m| n > *m) {
+                        push(Severity::Error, key, format!("{} > 최댓값 {}", v, max));
+                    }
+                }
+                Err(_) => push(Severity::Error, key, format!("'{}'은(는) 숫자가 아닙니다", v)),
+            }
+        }
+
+        if let Some(re) = &regex {
+            if !re.is_match(v) {
+                push(Severity::Error, key, format!("'{}'이(가) 형식과 맞지 않습니다", v));
+            }
+        }
+
+        if !rules.allowed.is_empty() && !rules.allowed.iter().any(|a| a == v) {
+            push(Severity::Error, key, format!("'{}'은(는) 허용되지 않은 값입니다", v));
+        }
+
+        if rules.unique {
+            if let Some(first) = seen.get(v) {
+                push(Severity::Error, key, format!("'{}' 중복 (키 {}와 같음)", v, first));
+            } else {
+                seen.insert(v, key);
+            }
+        }
+    }
+}
+
+/// 계산 컬럼은 규칙 대신 평가 실패한 행만 경고
+fn computed_errors(t: &Table, col: &ColumnDef, out: &mut Vec<Diagnostic>) {
+    for (key, row) in &t.rows {
+        let Some(msg) = row.get(&col.key).and_then(|v| v.strip_prefix(ERROR_PREFIX)) else { continue };
+        out.push(Diagnostic {
+            severity: Severity::Warning,
+            table: t.spec.name.clone(),
+            key: key.clone(),
+            column: col.key.clone(),
+            message: format!("계산 실패:{}", msg),
+        });
+    }
+}
+
+/// dtype으로 해석할 수 없는 값이면 경고 메시지
+fn type_mismatch(dtype: &DataType, v: &str) -> Option<String> {
+    let ok = match dtype {
+        DataType::Int => v.trim().parse::<i64>().is_ok(),
+        DataType::Float => v.trim().parse::<f64>().is_ok(),
+        DataType::Bool => value::parse_bool(v).is_some(),
+        DataType::Date => value::Date::parse(v).is_some(),
+        DataType::Enum(allowed) => allowed.iter().any(|a| a == v),
+        DataType::Text | DataType::List(_) | DataType::Reference(_) => true,
+    };
+    (!ok).then(|| format!("'{}'은(는) {} 값이 아닙니다", v, dtype.name()))
+}
@@ -0,0 +1,104 @@
+//! 셀 문자열 <-> 타입 값 변환 규칙.
+//! 저장 시 원본 표기(대소문자, 0/1, 날짜 구분자 등)를 최대한 유지해
+//! `save_table`을 거쳐도 값이 바뀌지 않도록 한다.
+
+const TRUE_WORDS: [&str; 4] = ["true", "1", "yes", "y"];
+const FALSE_WORDS: [&str; 4] = ["false", "0", "no", "n"];
+
+pub fn parse_bool(s: &str) -> Option<bool> {
+    let t = s.trim();
+    if TRUE_WORDS.iter().any(|w| t.eq_ignore_ascii_case(w)) {
+        Some(true)
+    } else if FALSE_WORDS.iter().any(|w| t.eq_ignore_ascii_case(w)) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// `like`(기존 값)의 표기를 따라 bool을 문자열로. 기존 값이 없으면 "true"/"false".
+pub fn format_bool(b: bool, like: &str) -> String {
+    let t = like.trim();
+    let (yes, no) = if t == "1" || t == "0" {
+        ("1", "0")
+    } else if t.eq_ignore_ascii_case("yes") || t.eq_ignore_ascii_case("no") {
+        ("yes", "no")
+    } else if t.eq_ignore_ascii_case("y") || t.eq_ignore_ascii_case("n") {
+        ("y", "n")
+    } else {
+        ("true", "false")
+    };
+    let word = if b { yes } else { no };
+
+    // 대소문자 스타일 유지: TRUE / True / true
+    if !t.is_empty() && t.chars().all(|c| !c.is_ascii_lowercase()) {
+        word.to_ascii_uppercase()
+    } else if t.chars().next().is_some_and(|c| c.is_ascii_uppercase()) {
+        let mut w = word.to_string();
+        w[..1].make_ascii_uppercase();
+        w
+    } else {
+        word.to_string()
+    }
+}
+
+/// 날짜 값 (연, 월, 일) + 원본 구분자
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub sep: char,
+}
+
+impl Date {
+    pub fn parse(s: &str) -> Option<Self> {
+        let t = s.trim();
+        let sep = t.chars().find(|c| matches!(c, '-' | '/' | '.'))?;
+        let mut parts = t.split(sep);
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        let d = Date { year, month, day, sep };
+        d.is_valid().then_some(d)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month) && self.day >= 1 && self.day <= days_in_month(self.year, self.month)
+    }
+
+    /// 월이 바뀌어 일이 넘치면 말일로 맞춘다
+    pub fn clamp_day(&mut self) {
+        self.month = self.month.clamp(1, 12);
+        self.day = self.day.clamp(1, days_in_month(self.year, self.month));
+    }
+
+    pub fn format(&self) -> String {
+        format!("{:04}{sep}{:02}{sep}{:02}", self.year, self.month, self.day, sep = self.sep)
+    }
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if (year % 4 == 0 && year % 100 != 0) || year % 400 == 0 => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// 빈 문자열은 빈 목록. 항목 앞뒤 공백은 그대로 둔다(원본 유지).
+pub fn split_list(s: &str, sep: &str) -> Vec<String> {
+    if s.is_empty() || sep.is_empty() {
+        return if s.is_empty() { Vec::new() } else { vec![s.to_string()] };
+    }
+    s.split(sep).map(|x| x.to_string()).collect()
+}
+
+pub fn join_list(items: &[String], sep: &str) -> String {
+    items.join(sep)
+}
@@ -0,0 +1,95 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    collections::HashMap,
+    fs,
+    hash::{Hash, Hasher},
+    time::{Duration, Instant, SystemTime},
+};
+
+use super::app_state::DataSets;
+
+/// 디스크 확인 간격
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 파일 상태 요약. mtime/크기가 같으면 내용도 같다고 보고, 다르면 해시로 확인한다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStamp {
+    pub modified: Option<SystemTime>,
+    pub len: u64,
+    pub hash: u64,
+}
+
+impl FileStamp {
+    /// 파일이 없으면 None
+    pub fn read(path: &str) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        let bytes = fs::read(path).ok()?;
+        Some(Self { modified: meta.modified().ok(), len: meta.len(), hash: hash_bytes(&bytes) })
+    }
+
+    fn same_meta(&self, path: &str) -> Option<bool> {
+        let meta = fs::metadata(path).ok()?;
+        Some(meta.len() == self.len && meta.modified().ok() == self.modified)
+    }
+}
+
+fn hash_bytes(bytes: &[u8]) -> u64 {
+    let mut h = DefaultHasher::new();
+    bytes.hash(&mut h);
+    h.finish()
+}
+
+/// 로드한 데이터 파일들을 주기적으로 확인해 외부 변경(다른 사람/스크립트/git)을 찾는다.
+#[derive(Debug, Default)]
+pub struct FileWatcher {
+    stamps: HashMap<String, Option<FileStamp>>, // 테이블 이름 -> 마지막으로 안 상태
+    last_poll: Option<Instant>,
+}
+
+impl FileWatcher {
+    /// 지금 디스크 상태를 기준으로 삼는다 (로드/저장/병합 직후)
+    pub fn reset(&mut self, ds: &DataSets) {
+        self.stamps = ds
+            .tables
+            .iter()
+            .map(|t| (t.spec.name.clone(), FileStamp::read(&t.spec.path)))
+            .collect();
+        self.last_poll = Some(Instant::now());
+    }
+
+    /// 테이블 하나만 다시 기준으로 (다시 로드/병합/무시한 경우)
+    pub fn accept(&mut self, table: &str, path: &str) {
+        self.stamps.insert(table.to_string(), FileStamp::read(path));
+    }
+
+    /// 간격이 지났으면 확인. `force`면 바로 확인 (저장 직전).
+    /// 내용이 바뀐 테이블 이름을 돌려준다 (mtime만 바뀐 경우는 제외).
+    pub fn poll(&mut self, ds: &DataSets, force: bool) -> Vec<String> {
+        let due = self.last_poll.is_none_or(|t| t.elapsed() >= POLL_INTERVAL);
+        if !force && !due {
+            return Vec::new();
+        }
+        self.last_poll = Some(Instant::now());
+
+        let mut changed = Vec::new();
+        for t in &ds.tables {
+            let Some(known) = self.stamps.get(&t.spec.name) else { continue };
+            let same = match known {
+                Some(stamp) if stamp.same_meta(&t.spec.path) == Some(true) => true,
+                Some(stamp) => match FileStamp::read(&t.spec.path) {
+                    Some(now) if now.hash == stamp.hash => {
+                        // 내용은 그대로 (touch 등): 새 mtime만 기억
+                        self.stamps.insert(t.spec.name.clone(), Some(now));
+                        true
+                    }
+                    _ => false,
+                },
+                None => FileStamp::read(&t.spec.path).is_none(),
+            };
+            if !same {
+                changed.push(t.spec.name.clone());
+            }
+        }
+        changed
+    }
+}
@@ -0,0 +1,239 @@
+// 복합 키와 자식 테이블 (캐릭터 하나에 스킬 여러 개)
+
+mod common;
+
+use std::fs;
+
+use common::{copy_fixture, fixture, info_spec, scratch_dir, skill_spec};
+use entity_manager::changes::Pending;
+use entity_manager::children;
+use entity_manager::codegen::{self, CodegenOptions};
+use entity_manager::dyn_entity::{display_key, join_key};
+use entity_manager::export::{render, ExportFormat, ExportShape};
+use entity_manager::history::{Command, History};
+use entity_manager::key_conflicts::{self, ConflictKind};
+use entity_manager::query;
+use entity_manager::{computed, save_table, DataSets, DataType, Table, TableSpec};
+
+fn sets(dir: &std::path::Path) -> DataSets {
+    DataSets::load(&[
+        info_spec(&copy_fixture(dir, "character_info.csv")),
+        skill_spec(&copy_fixture(dir, "character_skill.csv")),
+    ])
+    .unwrap()
+}
+
+fn names(t: &Table, parent: &str) -> Vec<String> {
+    t.child_keys(parent).iter().map(|k| t.rows[k].get("SkillName").unwrap().to_string()).collect()
+}
+
+#[test]
+fn composite_key_rows_group_under_entities() {
+    let t = Table::load(&skill_spec(&fixture("character_skill.csv"))).unwrap();
+    assert_eq!(t.schema.key_column, "CharacterUnique");
+    assert_eq!(t.schema.sub_keys, ["SkillSlot"]);
+    assert_eq!(t.schema.parent_column.as_deref(), Some("CharacterUnique")); // 복합 키면 첫 키 컬럼이 부모
+    assert_eq!(t.rows.len(), 6);
+    assert_eq!(display_key(&t.order[0]), "1 / 1");
+    assert_eq!(names(&t, "2"), ["망치질", "방패 막기", "전투 함성"]);
+
+    let dir = scratch_dir("child_merged");
+    let ds = sets(&dir);
+    assert_eq!(ds.keys().into_iter().collect::<Vec<_>>(), ["1", "2", "3"]);
+    let e = ds.merged().into_iter().find(|e| e.unique == "1").unwrap();
+    assert!(e.rows.contains_key("info") && !e.rows.contains_key("skill"));
+    assert_eq!(e.children["skill"].len(), 2);
+    assert_eq!(e.children["skill"][1].get("SkillName"), Some("독화살"));
+
+    assert!(Table::load(&TableSpec::new("bad", "Bad", &fixture("character_skill.csv"), "CharacterUnique+Nope")).is_err());
+}
+
+#[test]
+fn add_move_rekey_and_remove_keep_file_order() {
+    let dir = scratch_dir("child_edit");
+    let spec = skill_spec(&copy_fixture(&dir, "character_skill.csv"));
+    let mut t = Table::load(&spec).unwrap();
+
+    let key = children::add_child(&mut t, "1").unwrap();
+    assert_eq!(key, join_key(&["1", "3"])); // 다음 슬롯, 마지막 형제 행 뒤
+    t.rows.get_mut(&key).unwrap().set("SkillName", "속사".into());
+    assert!(children::move_child(&mut t, &key, true));
+    assert!(!children::move_child(&mut t, &join_key(&["1", "1"]), true)); // 이미 맨 위
+    assert_eq!(names(&t, "1"), ["화살 세례", "속사", "독화살"]);
+
+    assert!(children::set_key_cell(&mut t, &key, "SkillSlot", "2").is_err()); // 이미 있는 키
+    let key = children::set_key_cell(&mut t, &key, "SkillSlot", "5").unwrap();
+    assert_eq!(t.rows[&key].key, key);
+    children::remove_child(&mut t, &join_key(&["1", "1"])).unwrap();
+    save_table(&spec.path, &t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+
+    let text = fs::read_to_string(&spec.path).unwrap();
+    assert_eq!(
+        text,
+        "CharacterUnique,SkillSlot,SkillName,Level\n1,5,속사,0\n1,2,독화살,1\n3,1,돌진,2\n2,1,망치질,5\n2,2,방패 막기,2\n2,3,전투 함성,1\n"
+    );
+}
+
+#[test]
+fn entity_operations_cover_child_rows() {
+    let dir = scratch_dir("child_entities");
+    let mut ds = sets(&dir);
+
+    let created = ds.create_entity("4");
+    assert_eq!(created.len(), 1); // 자식 테이블에는 빈 행을 만들지 않음
+    assert!(ds.table("skill").unwrap().child_keys("4").is_empty());
+
+    let copied = ds.duplicate_entity("2", "5");
+    assert_eq!(copied.len(), 4); // info 1 + 스킬 3
+    let skill = ds.table("skill").unwrap();
+    assert_eq!(names(skill, "5"), ["망치질", "방패 막기", "전투 함성"]);
+    assert_eq!(skill.rows[&join_key(&["5", "3"])].get("CharacterUnique"), Some("5"));
+
+    let mut history = History::default();
+    history.push(Command::rows("삭제", ds.delete_entity("2")));
+    assert!(ds.table("skill").unwrap().child_keys("2").is_empty());
+    assert!(!ds.keys().contains("2"));
+    history.undo(&mut ds);
+    assert_eq!(names(ds.table("skill").unwrap(), "2"), ["망치질", "방패 막기", "전투 함성"]);
+}
+
+#[test]
+fn parent_column_without_composite_key() {
+    let dir = scratch_dir("child_parent");
+    let path = dir.join("drops.csv").to_string_lossy().to_string();
+    fs::write(&path, "DropId,Owner,Item\n10,1,포션\n11,2,방패\n12,1,화살\n").unwrap();
+    let mut spec = TableSpec::new("drop", "Drop", &path, "DropId");
+    spec.parent = Some("owner".into()); // 대소문자 무시
+    let mut ds = DataSets::load(&[info_spec(&copy_fixture(&dir, "character_info.csv")), spec.clone()]).unwrap();
+
+    let t = ds.table_mut("drop").unwrap();
+    assert_eq!(t.schema.parent_column.as_deref(), Some("Owner"));
+    assert_eq!(t.child_keys("1"), ["10", "12"]);
+    let key = children::add_child(t, "3").unwrap();
+    assert_eq!(key, "13"); // 부모가 키에 없으면 테이블 전체에서 다음 번호
+    let moved = children::set_key_cell(t, "11", "Owner", "3").unwrap();
+    assert_eq!(moved, "11"); // 부모만 바뀌면 행 키는 그대로
+    assert_eq!(t.child_keys("3"), ["11", "13"]);
+    assert!(t.remove_column("Owner").is_err());
+
+    spec.parent = Some("Missing".into());
+    assert!(Table::load(&spec).is_err());
+}
+
+#[test]
+fn composite_key_conflicts_are_renumbered_within_the_entity() {
+    let dir = scratch_dir("child_conflicts");
+    let path = dir.join("skills.csv").to_string_lossy().to_string();
+    fs::write(&path, "CharacterUnique,SkillSlot,SkillName\n1,1,a\n1,1,b\n1,,c\n").unwrap();
+    let mut ds = DataSets::load(&[skill_spec(&path)]).unwrap();
+    let t = ds.table("skill").unwrap();
+    let kinds: Vec<_> = t.conflicts.iter().map(|c| c.kind).collect();
+    assert_eq!(kinds, [ConflictKind::Duplicate, ConflictKind::Empty]);
+    assert_eq!(t.conflicts[0].to_string(), "3번째 줄: 키 '1 / 1' 중복 (2번째 줄과 같음)");
+
+    let keys = key_conflicts::renumber_all(&mut ds, "skill").unwrap();
+    assert_eq!(keys, [join_key(&["1", "2"]), join_key(&["1", "3"])]);
+    assert_eq!(names(ds.table("skill").unwrap(), "1"), ["a", "b", "c"]);
+}
+
+#[test]
+fn renumber_all_leaves_rows_without_a_parent_and_fails_cleanly() {
+    let dir = scratch_dir("child_conflicts_parent");
+    let path = dir.join("skills.csv").to_string_lossy().to_string();
+    fs::write(&path, "CharacterUnique,SkillSlot,SkillName\n1,1,a\n1,1,dup\n,2,noparent\n").unwrap();
+    let mut ds = DataSets::load(&[skill_spec(&path)]).unwrap();
+    let keys = key_conflicts::renumber_all(&mut ds, "skill").unwrap();
+    assert_eq!(keys, [join_key(&["1", "2"])]);
+    let t = ds.table("skill").unwrap();
+    assert_eq!(t.conflicts.len(), 1); // 어느 엔티티 행인지 모르므로 남김
+    assert_eq!(t.conflicts[0].row.get("SkillName"), Some("noparent"));
+
+    // 부모가 마지막 키 컬럼이면 새 번호를 매길 곳이 없다: 실패하고 아무것도 바꾸지 않음
+    let path = dir.join("owned.csv").to_string_lossy().to_string();
+    fs::write(&path, "Slot,Owner,Name\n1,1,a\n2,1,b\n2,1,dup\n").unwrap();
+    let mut spec = TableSpec::new("owned", "Owned", &path, "Slot+Owner");
+    spec.parent = Some("Owner".into());
+    let mut ds = DataSets::load(&[spec]).unwrap();
+    assert!(key_conflicts::renumber_all(&mut ds, "owned").is_err());
+    let t = ds.table("owned").unwrap();
+    assert_eq!((t.rows.len(), t.conflicts.len()), (2, 1));
+}
+
+#[test]
+fn export_and_codegen_treat_child_tables_as_lists() {
+    let dir = scratch_dir("child_export");
+    let ds = sets(&dir);
+    let files = render(&ds, ExportFormat::Json, ExportShape::Entities, dir.as_path()).unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&files[0].bytes).unwrap();
+    assert_eq!(v[1]["key"], "2");
+    assert_eq!(v[1]["skill"].as_array().unwrap().len(), 3);
+    assert_eq!(v[1]["skill"][2]["SkillName"], "전투 함성");
+    assert_eq!(v[1]["info"]["Name"], "드워프");
+
+    let src = codegen::rust::generate(&ds, &CodegenOptions::default());
+    assert!(src.contains("pub character_skill: BTreeMap<i64, Vec<CharacterSkill>>,"), "{}", src);
+    assert!(src.contains("rows.entry(row.character_unique).or_insert_with(Vec::new).push(row);"));
+    let cs = codegen::csharp::generate(&ds, &CodegenOptions::default()).unwrap();
+    assert!(cs.contains("public static Dictionary<long, List<CharacterSkill>> LoadCsv(string path) => Cells.Group("));
+    let cpp = codegen::cpp::generate(&ds, &CodegenOptions::default()).unwrap();
+    assert!(cpp.contains("using Rows = std::map<Key, std::vector<CharacterSkill>>;"));
+}
+
+#[test]
+fn filters_match_when_any_child_row_matches() {
+    let dir = scratch_dir("child_filter");
+    let ds = sets(&dir);
+    let keys: Vec<String> = ds.keys().into_iter().collect();
+    let filter = |src: &str| {
+        let expr = query::parse(src).unwrap();
+        expr.check(&ds, None).unwrap();
+        let result = query::filter_keys(&ds, &expr, &keys);
+        assert_eq!(result.errors, 0, "{:?}", result.first_error);
+        result.keys
+    };
+    assert_eq!(filter("skill.Level >= 5"), ["2"]);
+    assert_eq!(filter("skill.SkillName ~ \"화살\""), ["1"]);
+    // 같은 행이 두 조건을 함께 만족해야 한다
+    assert_eq!(filter("skill.SkillName ~ \"화살\" && skill.Level == 1"), ["1"]);
+    assert!(filter("skill.SkillName == \"독화살\" && skill.Level == 3").is_empty());
+    assert_eq!(filter("info.Health > 400 && skill.Level >= 2"), ["2", "3"]);
+    assert_eq!(filter("!(skill.Level > 1)"), ["1", "2"]); // 조건을 만족하지 않는 행이 하나라도 있으면
+
+    // 엔티티마다 값 하나가 필요한 일괄 편집/계산 컬럼에는 쓸 수 없다
+    let err = query::parse("skill.Level * 2").unwrap().check_single_row(&ds, Some("info")).unwrap_err();
+    assert!(err.message.contains("필터에서만"), "{}", err.message);
+    assert!(query::parse("Level * 2").unwrap().check_single_row(&ds, Some("skill")).is_ok());
+}
+
+#[test]
+fn computed_columns_in_child_tables_read_their_own_row() {
+    let dir = scratch_dir("child_computed");
+    let mut ds = sets(&dir);
+    ds.table_mut("skill").unwrap().add_computed_column("Power", DataType::Int, "Level * info.Health").unwrap();
+    ds.table_mut("info").unwrap().add_computed_column("Bad", DataType::Int, "skill.Level").unwrap();
+    let errors = computed::check(&ds);
+    assert_eq!(errors.len(), 1);
+    assert_eq!((errors[0].0.as_str(), errors[0].1.as_str()), ("info", "Bad"));
+
+    computed::recompute(&mut ds);
+    let skill = ds.table("skill").unwrap();
+    assert_eq!(skill.rows[&join_key(&["2", "1"])].get("Power"), Some("3050")); // 5 * 610
+    assert!(ds.table("info").unwrap().rows["2"].get("Bad").unwrap().starts_with(computed::ERROR_PREFIX));
+}
+
+#[test]
+fn child_row_changes_point_at_their_entity() {
+    let dir = scratch_dir("child_pending");
+    let base = sets(&dir);
+    let mut ds = sets(&dir);
+    let slot = join_key(&["2", "3"]);
+    ds.table_mut("skill").unwrap().rows.get_mut(&slot).unwrap().set("Level", "9".into());
+    children::remove_child(ds.table_mut("skill").unwrap(), &join_key(&["3", "1"])).unwrap();
+    assert_eq!(ds.entity_key("skill", &slot), "2");
+    assert_eq!(ds.entity_key("info", "1"), "1");
+
+    let pending = Pending::diff(&base, &ds);
+    assert_eq!(pending.changes.len(), 2);
+    assert!(pending.key_changed("2") && pending.key_changed("3") && !pending.key_changed("1"));
+    assert!(!pending.key_changed(&slot)); // 좌측 목록에 없는 행 키는 기록하지 않음
+}
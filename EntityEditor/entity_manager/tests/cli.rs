@@ -0,0 +1,168 @@
+// 명령줄 도구(entity-cli): 종료 코드(0 통과 / 1 실패 / 2 사용법·입출력 오류)와 명령별 출력
+
+mod common;
+
+use std::{fs, path::Path, process::Command};
+
+use common::{copy_fixture, fixture, info_spec, scratch_dir};
+use entity_manager::storage::{save_schema_sidecar, schema_sidecar_path};
+use entity_manager::{DataType, Table};
+
+/// 명령을 실행하고 (종료 코드, 표준 출력, 표준 오류)
+fn run(args: &[&str]) -> (i32, String, String) {
+    let out = Command::new(env!("CARGO_BIN_EXE_entity-cli")).args(args).output().unwrap();
+    let text = |b: Vec<u8>| String::from_utf8(b).unwrap();
+    (out.status.code().unwrap(), text(out.stdout), text(out.stderr))
+}
+
+fn path(dir: &Path, name: &str) -> String {
+    dir.join(name).to_string_lossy().to_string()
+}
+
+/// 사이드카로 스키마를 바꿔 둔다 (파일 추론과 다르게)
+fn override_schema(path: &str, edit: impl FnOnce(&mut Table)) {
+    let mut t = Table::load(&info_spec(path)).unwrap();
+    edit(&mut t);
+    save_schema_sidecar(path, &t.schema).unwrap();
+}
+
+#[test]
+fn exit_codes_separate_failures_from_usage_errors() {
+    let info = fixture("character_info.csv");
+
+    let (code, out, _) = run(&["validate", &info]);
+    assert_eq!((code, out.as_str()), (0, "테이블 1개, 오류 0건, 경고 0건\n"));
+    assert_eq!(run(&["help"]).0, 0);
+    assert_eq!(run(&["validate", &fixture("key_conflicts.csv")]).0, 1);
+
+    let (code, _, err) = run(&[]);
+    assert_eq!(code, 2);
+    assert!(err.starts_with("사용법:"));
+    for args in [
+        &["nope"][..],
+        &["validate"],
+        &["validate", &info, "--bogus"],
+        &["validate", &fixture("missing.csv")],
+        &["export", &info, "--format", "xml"],
+        &["codegen", &info],
+        &["convert", &info],
+    ] {
+        let (code, out, err) = run(args);
+        assert_eq!(code, 2, "{:?}", args);
+        assert!(out.is_empty() && err.starts_with("❌ "), "{:?}: {}", args, err);
+    }
+}
+
+#[test]
+fn validate_prints_diagnostics_and_strict_fails_on_warnings() {
+    let (code, out, _) = run(&["validate", &fixture("key_conflicts.csv")]);
+    assert_eq!(code, 1);
+    assert_eq!(
+        out,
+        "❌ key_conflicts[].CharacterUnique: 4번째 줄: 키가 비어 있습니다\n\
+         ❌ key_conflicts[2].CharacterUnique: 5번째 줄: 키 '2' 중복 (3번째 줄과 같음)\n\
+         ❌ key_conflicts[1].CharacterUnique: 7번째 줄: 키 '1' 중복 (2번째 줄과 같음)\n\
+         테이블 1개, 오류 3건, 경고 0건\n"
+    );
+
+    // 이름을 정수 컬럼으로 바꾸면 행마다 형식 경고
+    let dir = scratch_dir("cli_validate");
+    let info = copy_fixture(&dir, "character_info.csv");
+    override_schema(&info, |t| t.schema.columns[1].dtype = DataType::Int);
+    let (code, out, _) = run(&["validate", &info]);
+    assert_eq!(code, 0);
+    assert!(out.ends_with("테이블 1개, 오류 0건, 경고 3건\n"));
+    assert_eq!(out.lines().filter(|l| l.starts_with("⚠️") && l.contains(".Name:")).count(), 3);
+    assert_eq!(run(&["validate", &info, "--strict"]).0, 1);
+}
+
+#[test]
+fn convert_round_trips_and_writes_the_sidecar_only_when_asked() {
+    let dir = scratch_dir("cli_convert");
+    let info = copy_fixture(&dir, "character_info.csv");
+    let json = path(&dir, "info.json");
+    let (code, out, _) = run(&["convert", &info, &json]);
+    assert_eq!((code, out), (0, format!("{} -> {} (3행)\n", info, json)));
+    assert!(fs::read_to_string(&json).unwrap().contains("\"CharacterUnique\": 3"));
+
+    let back = path(&dir, "back.csv");
+    assert_eq!(run(&["convert", &json, &back]).0, 0);
+    assert_eq!(fs::read(&back).unwrap(), fs::read(&info).unwrap());
+    assert!(!schema_sidecar_path(&back).exists());
+
+    // --schema: 되돌린 파일 옆에 dtype/규칙을 담은 사이드카
+    let tsv = path(&dir, "back.txt");
+    assert_eq!(run(&["convert", &json, &tsv, "--schema", "--delimiter", "\\t"]).0, 0);
+    assert!(fs::read_to_string(&tsv).unwrap().starts_with("CharacterUnique\tName\t"));
+    let sidecar = fs::read_to_string(schema_sidecar_path(&tsv)).unwrap();
+    assert!(sidecar.contains("\"dtype\": \"Float\""));
+}
+
+#[test]
+fn diff_lists_changes_and_fails_when_files_differ() {
+    let (base, disk) = (fixture("merge_base.csv"), fixture("merge_disk.csv"));
+    let (code, out, _) = run(&["diff", &base, &disk]);
+    assert_eq!(code, 1);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 10);
+    assert_eq!(lines[0], "merge_disk.Note 컬럼 삭제");
+    assert!(lines.contains(&"merge_disk[3].Hp: '30' → '33'"));
+    assert!(lines.contains(&"merge_disk[7] 행 추가"));
+    assert_eq!(lines[9], "변경 9건");
+
+    assert_eq!(run(&["diff", &base, &base]), (0, "변경 0건\n".to_string(), String::new()));
+}
+
+#[test]
+fn fmt_check_reports_and_fmt_rewrites_in_schema_order() {
+    let dir = scratch_dir("cli_fmt");
+    let info = copy_fixture(&dir, "character_info.csv");
+    assert_eq!(run(&["fmt", &info, "--check"]), (0, String::new(), String::new()));
+
+    // 사이드카 컬럼 순서가 파일과 다르면 정리 대상
+    override_schema(&info, |t| t.schema.columns.swap(1, 2));
+    let original = fs::read(&info).unwrap();
+    let (code, out, _) = run(&["fmt", &info, "--check"]);
+    assert_eq!((code, out), (1, format!("정리 필요: {}\n", info)));
+    assert_eq!(fs::read(&info).unwrap(), original, "--check는 쓰지 않는다");
+
+    let (code, out, _) = run(&["fmt", &info]);
+    assert_eq!((code, out), (0, format!("정리함: {}\n", info)));
+    assert!(fs::read_to_string(&info).unwrap().starts_with("CharacterUnique,Class,Name,"));
+    assert_eq!(run(&["fmt", &info, "--check"]).0, 0);
+}
+
+#[test]
+fn export_writes_each_requested_format() {
+    let dir = scratch_dir("cli_export");
+    let info = copy_fixture(&dir, "character_info.csv");
+    let out_dir = path(&dir, "out");
+    let (code, out, _) = run(&["export", &info, "--format", "json,ron", "--out", &out_dir]);
+    assert_eq!(code, 0);
+    let written: Vec<&str> = out.lines().collect();
+    assert_eq!(written, [path(Path::new(&out_dir), "character_info.json"), path(Path::new(&out_dir), "character_info.ron")]);
+    let json = fs::read_to_string(written[0]).unwrap();
+    assert!(json.contains("\"Speed\": 1.25"));
+    assert!(Path::new(written[1]).exists());
+}
+
+#[test]
+fn codegen_writes_source_and_check_detects_stale_output() {
+    let dir = scratch_dir("cli_codegen");
+    let info = copy_fixture(&dir, "character_info.csv");
+    let rs = path(&dir, "game_data.rs");
+    let (code, out, _) = run(&["codegen", &info, "--out", &rs]);
+    assert_eq!((code, out), (0, format!("생성함: {}\n", rs)));
+    let source = fs::read_to_string(&rs).unwrap();
+    assert!(source.contains("pub struct CharacterInfo {"));
+    assert_eq!(run(&["codegen", &info, "--out", &rs, "--check"]), (0, String::new(), String::new()));
+
+    fs::write(&rs, source + "// 손으로 고침\n").unwrap();
+    let (code, out, _) = run(&["codegen", &info, "--out", &rs, "--check"]);
+    assert_eq!((code, out), (1, format!("다시 생성 필요: {}\n", rs)));
+
+    let cs = path(&dir, "GameData.cs");
+    assert_eq!(run(&["codegen", &info, "--out", &cs, "--lang", "csharp"]).0, 0);
+    assert!(fs::read_to_string(&cs).unwrap().contains("CharacterInfo"));
+    assert_eq!(run(&["codegen", &info, "--out", &cs, "--lang", "go"]).0, 2);
+}
@@ -0,0 +1,168 @@
+// 스키마 -> 게임 코드 생성
+
+mod common;
+
+use std::fs;
+
+use common::{attack_spec, copy_fixture, fixture, info_spec, legacy_spec, scratch_dir, skill_spec};
+use entity_manager::codegen::template::{render, Scope};
+use entity_manager::codegen::{build_models, cpp, csharp, encoding_warnings, pascal_case, snake_case, split_words, CodegenOptions, FieldKind};
+use entity_manager::{ColumnRef, DataSets, DataType, Table};
+
+/// Class는 Enum, 공격 테이블 키는 info 참조, Class -> Job 이름 변경
+fn typed_sets(dir: &std::path::Path) -> DataSets {
+    let mut ds = DataSets::load(&[
+        info_spec(&copy_fixture(dir, "character_info.csv")),
+        attack_spec(&copy_fixture(dir, "character_attack_info.txt")),
+    ])
+    .unwrap();
+    let class = ds.tables[0].schema.columns.iter_mut().find(|c| c.key == "Class").unwrap();
+    class.dtype = DataType::Enum(vec!["Warrior".into(), "Archer".into(), "Shield-Bearer".into()]);
+    ds.rename_column("info", "Class", "Job").unwrap();
+    ds.tables[1].schema.columns[0].dtype =
+        DataType::Reference(ColumnRef { table: "info".into(), column: String::new() });
+    ds
+}
+
+#[test]
+fn names_follow_language_conventions() {
+    assert_eq!(split_words("HPMax2"), ["HP", "Max2"]);
+    assert_eq!(pascal_case("character_status_info", "T"), "CharacterStatusInfo");
+    assert_eq!(snake_case("CharacterUnique", "f"), "character_unique");
+    assert_eq!(snake_case("2ndSkill", "field"), "field2nd_skill");
+    assert_eq!(snake_case("이름", "field"), "field"); // 영문이 없으면 대체 이름
+}
+
+#[test]
+fn models_resolve_enums_references_and_keys() {
+    let dir = scratch_dir("codegen_models");
+    let ds = typed_sets(&dir);
+    let models = build_models(&ds, &CodegenOptions { base_dir: Some(dir.clone()), ..Default::default() });
+    let info = &models[0];
+    assert_eq!(info.type_name, "CharacterInfo"); // 스키마 이름 = 파일 이름
+    assert_eq!(info.file, "character_info.csv");
+    let job = info.fields.iter().find(|f| f.header == "Job").unwrap();
+    assert_eq!(job.aliases, ["Class"]);
+    let FieldKind::Enum { type_name, variants } = &job.kind else { panic!("{:?}", job.kind) };
+    assert_eq!(type_name, "CharacterInfoJob");
+    assert_eq!(variants[2].ident, "ShieldBearer");
+    assert_eq!(variants[2].value, "Shield-Bearer");
+    assert_eq!(models[1].key_field().kind, FieldKind::Int); // info 키가 정수
+}
+
+#[test]
+fn rust_output_maps_headers_and_aliases() {
+    let dir = scratch_dir("codegen_rust");
+    let ds = typed_sets(&dir);
+    let src = entity_manager::codegen::rust::generate(&ds, &CodegenOptions::default());
+    assert!(src.contains("#[serde(rename = \"Job\", alias = \"Class\", default, deserialize_with = \"de_enum\")]"));
+    assert!(src.contains("    pub job: CharacterInfoJob,"));
+    assert!(src.contains("    #[serde(rename = \"Shield-Bearer\")]\n    ShieldBearer,"));
+    assert!(src.contains("pub const DELIMITER: u8 = b'\\t';"));
+    assert!(src.contains("pub character_attack_info: BTreeMap<i64, CharacterAttackInfo>,"));
+    assert!(src.contains("fn de_bool")); // Playable
+    assert!(!src.contains("fn de_list")); // 목록 컬럼 없음
+}
+
+/// 생성한 Rust 코드 전체를 고정해 둔다. 의도한 변경이면 `UPDATE_GOLDEN=1 cargo test`로 다시 쓴다.
+#[test]
+fn rust_output_matches_golden_file() {
+    let dir = scratch_dir("codegen_golden");
+    let mut ds = typed_sets(&dir);
+    ds.tables.push(Table::load(&skill_spec(&copy_fixture(&dir, "character_skill.csv"))).unwrap());
+    let src = entity_manager::codegen::rust::generate(&ds, &CodegenOptions { base_dir: Some(dir), ..Default::default() });
+    let path = format!("{}/tests/golden/game_data.rs", env!("CARGO_MANIFEST_DIR"));
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, &src).unwrap();
+    }
+    assert!(src == fs::read_to_string(&path).unwrap(), "생성 코드가 {}와 다릅니다 (UPDATE_GOLDEN=1로 다시 쓰기)", path);
+}
+
+/// 위 골든 파일을 그대로 컴파일해 픽스처를 읽어 본다
+#[allow(dead_code)]
+mod generated {
+    include!("golden/game_data.rs");
+}
+
+#[test]
+fn golden_rust_loader_reads_the_fixtures() {
+    use generated::{CharacterInfoJob, GameData};
+    let data = GameData::load(fixture("")).unwrap();
+    assert_eq!(data.character_info.len(), 3);
+    let orc = &data.character_info[&3];
+    assert_eq!((orc.name.as_str(), orc.job, orc.health, orc.playable), ("오크 전사", CharacterInfoJob::Warrior, 500, true));
+    assert_eq!(data.character_info[&1].job, CharacterInfoJob::Archer); // 옛 헤더 Class를 별칭으로 읽음
+    assert_eq!(data.character_attack_info[&2].defense_power, 40); // 탭 구분
+    let skills: Vec<_> = data.character_skill[&2].iter().map(|s| (s.skill_slot, s.skill_name.as_str())).collect();
+    assert_eq!(skills, [(1, "망치질"), (2, "방패 막기"), (3, "전투 함성")]);
+}
+
+#[test]
+fn template_sections_repeat_and_drop_standalone_lines() {
+    let item = |name: &str| {
+        let mut s = Scope::new();
+        s.set("name", name);
+        s
+    };
+    let mut scope = Scope::new();
+    scope.set("title", "T").set("items", vec![item("a"), item("b")]).set("none", Vec::new());
+    let out = render("{{! 주석 }}\n{{title}}:\n{{#items}}\n  {{name}}{{^last}},{{/last}}\n{{/items}}\n{{^none}}\nempty\n{{/none}}\n", &scope).unwrap();
+    assert_eq!(out, "T:\n  a,\n  b\nempty\n");
+
+    assert!(render("{{missing}}", &scope).unwrap_err().to_string().contains("missing"));
+    assert!(render("{{#items}}x", &scope).is_err());
+    assert!(render("{{/items}}", &scope).is_err());
+}
+
+#[test]
+fn csharp_output_maps_headers_aliases_and_keys() {
+    let dir = scratch_dir("codegen_csharp");
+    let src = csharp::generate(&typed_sets(&dir), &CodegenOptions::default()).unwrap();
+    assert!(src.contains("namespace GameTables"));
+    assert!(src.contains("Job = CharacterInfoJobValues.Parse(row.Get(new[] { \"Job\", \"Class\" })),"));
+    assert!(src.contains("public static readonly string[] Names = { \"Warrior\", \"Archer\", \"Shield-Bearer\" };"));
+    assert!(src.contains("public const char Delimiter = '\\t';"));
+    assert!(src.contains("public Dictionary<long, CharacterAttackInfo> CharacterAttackInfo"));
+    assert!(!src.contains("{{"));
+}
+
+#[test]
+fn cpp_output_maps_headers_aliases_and_keys() {
+    let dir = scratch_dir("codegen_cpp");
+    let src = cpp::generate(&typed_sets(&dir), &CodegenOptions::default()).unwrap();
+    assert!(src.contains("namespace game_tables {"));
+    assert!(src.contains("r.job = parse_character_info_job(row.get({\"Job\", \"Class\"}));"));
+    assert!(src.contains("if (s == \"Shield-Bearer\") return CharacterInfoJob::ShieldBearer;"));
+    assert!(src.contains("using Key = std::int64_t;"));
+    assert!(src.contains("using Rows = std::map<Key, CharacterAttackInfo>;"));
+    assert!(src.contains("CharacterAttackInfo::Rows character_attack_info;"));
+}
+
+#[test]
+fn member_names_avoid_keywords_and_generated_members() {
+    let dir = scratch_dir("codegen_names");
+    let path = dir.join("items.csv");
+    std::fs::write(&path, "Id,Key,class,File\n1,a,b,c\n").unwrap();
+    let ds = DataSets::load(&[entity_manager::TableSpec::new("items", "Items", &path.to_string_lossy(), "Id")]).unwrap();
+    let options = CodegenOptions::default();
+    let cs = csharp::generate(&ds, &options).unwrap();
+    assert!(cs.contains("public string Key_;") && cs.contains("public string Class;") && cs.contains("public string File_;"));
+    let hpp = cpp::generate(&ds, &options).unwrap();
+    assert!(hpp.contains("std::string key_{};") && hpp.contains("std::string class_{};"));
+}
+
+#[test]
+fn custom_templates_see_the_same_models() {
+    let ds = DataSets::load(&[info_spec(&fixture("character_info.csv"))]).unwrap();
+    let template = "{{#tables}}{{class}}:{{#fields}}{{name}}{{#is_key}}*{{/is_key}}{{^last}},{{/last}}{{/fields}}{{/tables}}";
+    let out = csharp::generate_with(&ds, &CodegenOptions::default(), template).unwrap();
+    assert_eq!(out, "CharacterInfo:CharacterUnique*,Name,Class,Health,Speed,Playable,Released");
+}
+
+#[test]
+fn non_utf8_tables_are_reported() {
+    let ds = DataSets::load(&[info_spec(&fixture("character_info.csv")), legacy_spec(&fixture("legacy_cp949.csv"))]).unwrap();
+    let warnings = encoding_warnings(&build_models(&ds, &CodegenOptions::default()));
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].starts_with("legacy: 데이터 파일이 CP949입니다"), "{}", warnings[0]);
+}
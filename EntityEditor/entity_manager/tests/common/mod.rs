@@ -0,0 +1,53 @@
+#![allow(dead_code)] // 테스트 파일마다 쓰는 도우미가 다름
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use entity_manager::TableSpec;
+
+/// 읽기 전용 픽스처 경로
+pub fn fixture(name: &str) -> String {
+    format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+/// 테스트마다 비어 있는 임시 폴더 (이전 실행의 잔여물은 지움)
+pub fn scratch_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("entity_manager_{}_{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// 픽스처를 임시 폴더로 복사하고 그 경로를 돌려준다 (저장 테스트용)
+pub fn copy_fixture(dir: &Path, name: &str) -> String {
+    let dst = dir.join(name);
+    fs::copy(fixture(name), &dst).unwrap();
+    dst.to_string_lossy().to_string()
+}
+
+pub fn info_spec(path: &str) -> TableSpec {
+    TableSpec::new("info", "Info", path, "CharacterUnique")
+}
+
+pub fn attack_spec(path: &str) -> TableSpec {
+    let mut spec = TableSpec::new("attack", "Attack", path, "CharacterUnique");
+    spec.delimiter = '\t';
+    spec
+}
+
+/// ';' 구분자, BOM, CRLF, 항상 따옴표 (dialect_semicolon.csv)
+pub fn semicolon_spec(path: &str) -> TableSpec {
+    TableSpec::new("semi", "Semi", path, "Id")
+}
+
+/// CP949 인코딩 (legacy_cp949.csv)
+pub fn legacy_spec(path: &str) -> TableSpec {
+    TableSpec::new("legacy", "Legacy", path, "Id")
+}
+
+/// 복합 키 자식 테이블 (character_skill.csv)
+pub fn skill_spec(path: &str) -> TableSpec {
+    TableSpec::new("skill", "Skill", path, "CharacterUnique+SkillSlot")
+}
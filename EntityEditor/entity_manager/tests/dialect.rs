@@ -0,0 +1,137 @@
+// CSV 표기 방식 감지와 보존
+
+mod common;
+
+use std::fs;
+
+use common::{copy_fixture, fixture, info_spec, legacy_spec, scratch_dir, semicolon_spec};
+use entity_manager::dialect::{Dialect, LineEnding, Quoting, TextEncoding};
+use entity_manager::project::ProjectFile;
+use entity_manager::storage::{load_schema_sidecar, render_table};
+use entity_manager::validation::encoding_warnings;
+use entity_manager::{save_table, Table};
+
+#[test]
+fn sniffs_delimiter_bom_line_ending_and_quoting() {
+    let t = Table::load(&semicolon_spec(&fixture("dialect_semicolon.csv"))).unwrap();
+    let d = &t.schema.dialect;
+    assert_eq!(d.delimiter, ';'); // 지정한 ','가 맞지 않으면 후보에서 고른다
+    assert!(d.bom);
+    assert_eq!(d.line_ending, LineEnding::CrLf);
+    assert_eq!(d.quoting, Quoting::Always);
+    assert!(!d.final_newline);
+    assert_eq!(t.schema.key_column, "Id");
+    assert_eq!(t.rows["1"].get("Memo"), Some("a;b"));
+    assert_eq!(t.rows["3"].get("Memo"), Some("x \"y\""));
+}
+
+#[test]
+fn edit_rewrites_only_that_row_in_the_same_dialect() {
+    let dir = scratch_dir("dialect_edit");
+    let spec = semicolon_spec(&copy_fixture(&dir, "dialect_semicolon.csv"));
+    let mut t = Table::load(&spec).unwrap();
+    t.rows.get_mut("1").unwrap().set("Name", "홉고블린".into());
+    save_table(&spec.path, &t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+
+    let before = fs::read_to_string(fixture("dialect_semicolon.csv")).unwrap();
+    let after = fs::read_to_string(&spec.path).unwrap();
+    assert_eq!(after, before.replace("\"고블린\"", "\"홉고블린\""));
+}
+
+#[test]
+fn changed_dialect_rewrites_every_row() {
+    let mut t = Table::load(&semicolon_spec(&fixture("dialect_semicolon.csv"))).unwrap();
+    t.schema.dialect = Dialect::default();
+    let bytes = render_table(&t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+    assert_eq!(String::from_utf8(bytes).unwrap(), "Id,Name,Memo\n1,고블린,a;b\n2,오크,\n3,트롤,\"x \"\"y\"\"\"\n");
+}
+
+#[test]
+fn dialect_is_recorded_in_sidecar() {
+    let dir = scratch_dir("dialect_sidecar");
+    let spec = info_spec(&copy_fixture(&dir, "character_info.csv"));
+    let t = Table::load(&spec).unwrap();
+    t.save_schema().unwrap();
+    let saved = load_schema_sidecar(&spec.path).unwrap().unwrap();
+    assert_eq!(saved.dialect, t.schema.dialect);
+    assert_eq!(saved.dialect.delimiter, ',');
+}
+
+#[test]
+fn cp949_file_is_detected_and_decoded() {
+    let t = Table::load(&legacy_spec(&fixture("legacy_cp949.csv"))).unwrap();
+    assert_eq!(t.schema.dialect.encoding, TextEncoding::Cp949);
+    assert_eq!(t.rows["1"].get("Desc"), Some("작고, 재빠름"));
+    assert_eq!(t.rows["2"].get("Desc"), Some("똠방각하")); // EUC-KR에 없는 CP949 확장 글자
+}
+
+#[test]
+fn edits_are_saved_in_the_file_encoding() {
+    let dir = scratch_dir("cp949_edit");
+    let spec = legacy_spec(&copy_fixture(&dir, "legacy_cp949.csv"));
+    let mut t = Table::load(&spec).unwrap();
+    t.rows.get_mut("2").unwrap().set("Name", "홉고블린".into());
+    assert!(encoding_warnings(std::slice::from_ref(&t)).is_empty());
+    save_table(&spec.path, &t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+
+    let bytes = fs::read(&spec.path).unwrap();
+    assert!(std::str::from_utf8(&bytes).is_err());
+    let text = TextEncoding::Cp949.decode(&bytes).unwrap();
+    assert_eq!(text, "Id,Name,Desc\r\n1,고블린,\"작고, 재빠름\"\r\n2,홉고블린,똠방각하\r\n");
+}
+
+#[test]
+fn unrepresentable_values_warn_and_save_as_question_marks() {
+    let mut t = Table::load(&legacy_spec(&fixture("legacy_cp949.csv"))).unwrap();
+    t.rows.get_mut("1").unwrap().set("Name", "고블린🎃".into());
+    let warnings = encoding_warnings(std::slice::from_ref(&t));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!((warnings[0].key.as_str(), warnings[0].column.as_str()), ("1", "Name"));
+    assert!(warnings[0].message.contains("🎃"), "{}", warnings[0].message);
+
+    let bytes = render_table(&t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+    let text = TextEncoding::Cp949.decode(&bytes).unwrap();
+    assert!(text.contains("1,고블린?,"), "{}", text);
+}
+
+#[test]
+fn encoding_setting_overrides_detection_and_sidecar_keeps_it_for_ascii_files() {
+    let dir = scratch_dir("cp949_ascii");
+    let path = dir.join("plain.csv").to_string_lossy().to_string();
+    fs::write(&path, "Id,Name\n1,goblin\n").unwrap();
+
+    let mut spec = legacy_spec(&path);
+    spec.encoding = Some(TextEncoding::Cp949);
+    let t = Table::load(&spec).unwrap();
+    assert_eq!(t.schema.dialect.encoding, TextEncoding::Cp949);
+    t.save_schema().unwrap();
+
+    // ASCII뿐이라 감지로는 알 수 없으므로 사이드카에 기록된 인코딩을 쓴다
+    let mut t = Table::load(&legacy_spec(&path)).unwrap();
+    assert_eq!(t.schema.dialect.encoding, TextEncoding::Cp949);
+    t.rows.get_mut("1").unwrap().set("Name", "고블린".into());
+    save_table(&path, &t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), TextEncoding::Cp949.encode("Id,Name\n1,고블린\n"));
+}
+
+#[test]
+fn non_ascii_delimiter_is_rejected_instead_of_falling_back() {
+    let dir = scratch_dir("delimiter_ascii");
+    let path = copy_fixture(&dir, "character_info.csv");
+    let mut spec = info_spec(&path);
+    spec.delimiter = '→';
+    let err = Table::load(&spec).unwrap_err().to_string();
+    assert!(err.contains("구분자 '→'은(는) ASCII 문자가 아닙니다"), "{}", err);
+
+    // 프로젝트 파일에서 읽을 때도
+    let project = dir.join("project.json").to_string_lossy().to_string();
+    let json = |d: &str| {
+        let spec = r#""name": "info", "display_name": "Info", "path": "character_info.csv", "key_column": "CharacterUnique""#;
+        format!(r#"{{"tables": [{{{}, "delimiter": "{}"}}]}}"#, spec, d)
+    };
+    fs::write(&project, json("→")).unwrap();
+    let err = format!("{:#}", ProjectFile::load(&project).unwrap_err());
+    assert!(err.contains("ASCII 문자가 아닙니다"), "{}", err);
+    fs::write(&project, json(";")).unwrap();
+    assert_eq!(ProjectFile::load(&project).unwrap().tables[0].delimiter, ';');
+}
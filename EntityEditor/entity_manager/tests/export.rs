@@ -0,0 +1,115 @@
+// 게임 런타임용 내보내기
+
+mod common;
+
+use std::fs;
+
+use common::{attack_spec, copy_fixture, fixture, info_spec, scratch_dir};
+use entity_manager::export::{
+    export, render, table_records, typed_cell, Cell, ExportFormat, ExportSettings, ExportShape, BINARY_MAGIC,
+};
+use entity_manager::{DataSets, DataType, Table};
+
+fn fixture_sets() -> DataSets {
+    DataSets::load(&[
+        info_spec(&fixture("character_info.csv")),
+        attack_spec(&fixture("character_attack_info.txt")),
+    ])
+    .unwrap()
+}
+
+#[test]
+fn cells_follow_schema_dtypes() {
+    assert_eq!(typed_cell(&DataType::Int, "42"), Ok(Some(Cell::Int(42))));
+    assert_eq!(typed_cell(&DataType::Float, "1.50"), Ok(Some(Cell::Float(1.5))));
+    assert_eq!(typed_cell(&DataType::Bool, "Yes"), Ok(Some(Cell::Bool(true))));
+    assert_eq!(typed_cell(&DataType::Date, "2024/1/2"), Ok(Some(Cell::Str("2024-01-02".into()))));
+    assert_eq!(
+        typed_cell(&DataType::List(";".into()), "a;b"),
+        Ok(Some(Cell::List(vec!["a".into(), "b".into()])))
+    );
+    assert_eq!(typed_cell(&DataType::Int, ""), Ok(None));
+    assert!(typed_cell(&DataType::Int, "abc").is_err());
+}
+
+#[test]
+fn json_records_keep_file_and_column_order() {
+    let ds = fixture_sets();
+    let files = render(&ds, ExportFormat::Json, ExportShape::Tables, "out".as_ref()).unwrap();
+    assert_eq!(files.len(), 2);
+    let text = String::from_utf8(files[0].bytes.clone()).unwrap();
+    let first = text.find("\"CharacterUnique\": 3").unwrap();
+    assert!(first < text.find("\"CharacterUnique\": 1").unwrap()); // 파일 순서
+    assert!(text.find("\"Name\"").unwrap() < text.find("\"Class\"").unwrap()); // 컬럼 순서
+    assert!(text.contains("\"Speed\": 1.25"));
+    assert!(text.contains("\"Playable\": true"));
+}
+
+#[test]
+fn ron_and_messagepack_carry_the_same_records() {
+    let ds = fixture_sets();
+    let ron = render(&ds, ExportFormat::Ron, ExportShape::Tables, "out".as_ref()).unwrap();
+    let text = String::from_utf8(ron[1].bytes.clone()).unwrap();
+    assert!(text.starts_with("#![enable(implicit_some)]"));
+    assert!(text.contains("\"AttackPower\": 45"));
+
+    let mp = render(&ds, ExportFormat::MessagePack, ExportShape::Tables, "out".as_ref()).unwrap();
+    let decoded: Vec<serde_json::Value> = rmp_serde::from_slice(&mp[1].bytes).unwrap();
+    assert_eq!(decoded.len(), 3);
+    assert_eq!(decoded[2]["DefensePower"], 25);
+}
+
+#[test]
+fn entities_merge_tables_by_key() {
+    let dir = scratch_dir("export_entities");
+    let mut ds = DataSets::load(&[
+        info_spec(&copy_fixture(&dir, "character_info.csv")),
+        attack_spec(&copy_fixture(&dir, "character_attack_info.txt")),
+    ])
+    .unwrap();
+    ds.tables[1].rows.remove("2");
+    let files = render(&ds, ExportFormat::Json, ExportShape::Entities, dir.as_path()).unwrap();
+    assert_eq!(files.len(), 1);
+    assert!(files[0].path.ends_with("entities.json"));
+    let v: serde_json::Value = serde_json::from_slice(&files[0].bytes).unwrap();
+    assert_eq!(v[0]["key"], "1"); // 키 순서
+    assert_eq!(v[1]["info"]["Name"], "드워프");
+    assert!(v[1]["attack"].is_null()); // 이 테이블에는 없는 키
+}
+
+#[test]
+fn binary_starts_with_header_and_string_table() {
+    let ds = fixture_sets();
+    let files = render(&ds, ExportFormat::Binary, ExportShape::Tables, "out".as_ref()).unwrap();
+    let bytes = &files[1].bytes;
+    assert_eq!(&bytes[0..4], BINARY_MAGIC);
+    assert_eq!(u16::from_le_bytes([bytes[4], bytes[5]]), 1);
+    let strings = u32::from_le_bytes(bytes[6..10].try_into().unwrap());
+    assert_eq!(strings, 4); // attack + 컬럼 이름 3개 (값은 모두 정수)
+    let first_len = u32::from_le_bytes(bytes[10..14].try_into().unwrap()) as usize;
+    assert_eq!(&bytes[14..14 + first_len], b"attack");
+}
+
+#[test]
+fn output_is_deterministic_and_rewrites_nothing_when_unchanged() {
+    let dir = scratch_dir("export_repeat");
+    let ds = fixture_sets();
+    let settings = ExportSettings { dir: String::new(), formats: ExportFormat::ALL.to_vec(), shape: ExportShape::Tables };
+    let out = dir.join("export");
+    let files = export(&ds, &settings, &out).unwrap();
+    assert_eq!(files.len(), 8);
+    let before: Vec<_> = files.iter().map(|f| fs::metadata(f).unwrap().modified().unwrap()).collect();
+    let again = render(&ds, ExportFormat::Binary, ExportShape::Tables, &out).unwrap();
+    assert_eq!(again[0].bytes, fs::read(&files[6]).unwrap());
+    export(&ds, &settings, &out).unwrap();
+    let after: Vec<_> = files.iter().map(|f| fs::metadata(f).unwrap().modified().unwrap()).collect();
+    assert_eq!(before, after);
+}
+
+#[test]
+fn type_errors_name_the_cell() {
+    let mut t = Table::load(&info_spec(&fixture("character_info.csv"))).unwrap();
+    t.rows.get_mut("2").unwrap().set("Health", "많음".into());
+    let err = table_records(&t).unwrap_err().to_string();
+    assert!(err.contains("info[2].Health"), "{err}");
+}
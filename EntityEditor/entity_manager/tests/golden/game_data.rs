@@ -0,0 +1,192 @@
+// @generated by entity_manager::codegen::rust — 직접 고치지 말고 스키마를 고친 뒤 다시 생성하세요.
+// CharacterInfo <- character_info.csv
+// CharacterAttackInfo <- character_attack_info.txt
+// CharacterSkill <- character_skill.csv
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+pub enum CharacterInfoJob {
+    #[default]
+    #[serde(rename = "Warrior")]
+    Warrior,
+    #[serde(rename = "Archer")]
+    Archer,
+    #[serde(rename = "Shield-Bearer")]
+    ShieldBearer,
+}
+
+/// `info` 테이블 한 행 (키: `CharacterUnique`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterInfo {
+    #[serde(rename = "CharacterUnique", deserialize_with = "de_parse")]
+    pub character_unique: i64,
+    #[serde(rename = "Name", default)]
+    pub name: String,
+    #[serde(rename = "Job", alias = "Class", default, deserialize_with = "de_enum")]
+    pub job: CharacterInfoJob,
+    #[serde(rename = "Health", default, deserialize_with = "de_parse")]
+    pub health: i64,
+    #[serde(rename = "Speed", default, deserialize_with = "de_parse")]
+    pub speed: f64,
+    #[serde(rename = "Playable", default, deserialize_with = "de_bool")]
+    pub playable: bool,
+    #[serde(rename = "Released", default)]
+    pub released: String,
+}
+
+impl CharacterInfo {
+    /// 데이터 파일 (생성 시점의 경로)
+    pub const FILE: &str = "character_info.csv";
+    pub const DELIMITER: u8 = b',';
+
+    /// 키 -> 행. 같은 키가 여러 번 나오면 마지막 행이 남는다.
+    pub fn load(path: impl AsRef<Path>) -> Result<BTreeMap<i64, Self>, csv::Error> {
+        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_path(path)?)
+    }
+
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<BTreeMap<i64, Self>, csv::Error> {
+        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_reader(reader))
+    }
+
+    fn read<R: std::io::Read>(mut reader: csv::Reader<R>) -> Result<BTreeMap<i64, Self>, csv::Error> {
+        let mut rows = BTreeMap::new();
+        for row in reader.deserialize() {
+            let row: Self = row?;
+            rows.insert(row.character_unique, row);
+        }
+        Ok(rows)
+    }
+}
+
+/// `attack` 테이블 한 행 (키: `CharacterUnique`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterAttackInfo {
+    /// 참조: info.<key>
+    #[serde(rename = "CharacterUnique", deserialize_with = "de_parse")]
+    pub character_unique: i64,
+    #[serde(rename = "AttackPower", default, deserialize_with = "de_parse")]
+    pub attack_power: i64,
+    #[serde(rename = "DefensePower", default, deserialize_with = "de_parse")]
+    pub defense_power: i64,
+}
+
+impl CharacterAttackInfo {
+    /// 데이터 파일 (생성 시점의 경로)
+    pub const FILE: &str = "character_attack_info.txt";
+    pub const DELIMITER: u8 = b'\t';
+
+    /// 키 -> 행. 같은 키가 여러 번 나오면 마지막 행이 남는다.
+    pub fn load(path: impl AsRef<Path>) -> Result<BTreeMap<i64, Self>, csv::Error> {
+        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_path(path)?)
+    }
+
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<BTreeMap<i64, Self>, csv::Error> {
+        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_reader(reader))
+    }
+
+    fn read<R: std::io::Read>(mut reader: csv::Reader<R>) -> Result<BTreeMap<i64, Self>, csv::Error> {
+        let mut rows = BTreeMap::new();
+        for row in reader.deserialize() {
+            let row: Self = row?;
+            rows.insert(row.character_unique, row);
+        }
+        Ok(rows)
+    }
+}
+
+/// `skill` 테이블 한 행 (키: `CharacterUnique`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CharacterSkill {
+    #[serde(rename = "CharacterUnique", deserialize_with = "de_parse")]
+    pub character_unique: i64,
+    #[serde(rename = "SkillSlot", default, deserialize_with = "de_parse")]
+    pub skill_slot: i64,
+    #[serde(rename = "SkillName", default)]
+    pub skill_name: String,
+    #[serde(rename = "Level", default, deserialize_with = "de_parse")]
+    pub level: i64,
+}
+
+impl CharacterSkill {
+    /// 데이터 파일 (생성 시점의 경로)
+    pub const FILE: &str = "character_skill.csv";
+    pub const DELIMITER: u8 = b',';
+
+    /// `CharacterUnique` -> 행들 (파일 순서)
+    pub fn load(path: impl AsRef<Path>) -> Result<BTreeMap<i64, Vec<Self>>, csv::Error> {
+        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_path(path)?)
+    }
+
+    pub fn from_reader<R: std::io::Read>(reader: R) -> Result<BTreeMap<i64, Vec<Self>>, csv::Error> {
+        Self::read(csv::ReaderBuilder::new().delimiter(Self::DELIMITER).flexible(true).from_reader(reader))
+    }
+
+    fn read<R: std::io::Read>(mut reader: csv::Reader<R>) -> Result<BTreeMap<i64, Vec<Self>>, csv::Error> {
+        let mut rows = BTreeMap::new();
+        for row in reader.deserialize() {
+            let row: Self = row?;
+            rows.entry(row.character_unique).or_insert_with(Vec::new).push(row);
+        }
+        Ok(rows)
+    }
+}
+
+/// 모든 테이블
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GameData {
+    pub character_info: BTreeMap<i64, CharacterInfo>,
+    pub character_attack_info: BTreeMap<i64, CharacterAttackInfo>,
+    pub character_skill: BTreeMap<i64, Vec<CharacterSkill>>,
+}
+
+impl GameData {
+    /// `dir` 기준으로 각 테이블의 `FILE`을 읽는다
+    pub fn load(dir: impl AsRef<Path>) -> Result<Self, csv::Error> {
+        let dir = dir.as_ref();
+        Ok(Self {
+            character_info: CharacterInfo::load(dir.join(CharacterInfo::FILE))?,
+            character_attack_info: CharacterAttackInfo::load(dir.join(CharacterAttackInfo::FILE))?,
+            character_skill: CharacterSkill::load(dir.join(CharacterSkill::FILE))?,
+        })
+    }
+}
+
+fn de_parse<'de, D, T>(d: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: std::str::FromStr + Default,
+    T::Err: std::fmt::Display,
+{
+    let s = String::deserialize(d)?;
+    let s = s.trim();
+    if s.is_empty() {
+        return Ok(T::default());
+    }
+    s.parse().map_err(serde::de::Error::custom)
+}
+
+fn de_bool<'de, D: serde::Deserializer<'de>>(d: D) -> Result<bool, D::Error> {
+    let s = String::deserialize(d)?;
+    match s.trim().to_ascii_lowercase().as_str() {
+        "" | "false" | "0" | "no" | "n" => Ok(false),
+        "true" | "1" | "yes" | "y" => Ok(true),
+        other => Err(serde::de::Error::custom(format!("'{}' is not a bool", other))),
+    }
+}
+
+fn de_enum<'de, D, T>(d: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::de::DeserializeOwned + Default,
+{
+    use serde::de::IntoDeserializer;
+    let s = String::deserialize(d)?;
+    if s.is_empty() {
+        return Ok(T::default());
+    }
+    T::deserialize(s.into_deserializer())
+}
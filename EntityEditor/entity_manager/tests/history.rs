@@ -0,0 +1,227 @@
+// 되돌리기/다시 실행: 셀 편집 합치기, 행/테이블 스냅샷 복원, redo 스택 정리
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use common::{fixture, info_spec};
+use entity_manager::history::{CellEdit, Command, History, RowEdit, TableEdit, MERGE_WINDOW};
+use entity_manager::{DataSets, Table};
+
+fn sets() -> DataSets {
+    DataSets::load(&[info_spec(&fixture("character_info.csv"))]).unwrap()
+}
+
+fn cell(ds: &DataSets, key: &str, column: &str) -> String {
+    ds.table("info").unwrap().rows[key].get(column).unwrap_or("").to_string()
+}
+
+/// 화면에서처럼 셀을 먼저 바꾸고 기록
+fn edit(ds: &mut DataSets, history: &mut History, key: &str, column: &str, value: &str) {
+    let old = cell(ds, key, column);
+    ds.table_mut("info").unwrap().rows.get_mut(key).unwrap().set(column, value.to_string());
+    history.record(CellEdit {
+        table: "info".into(),
+        key: key.into(),
+        column: column.into(),
+        old,
+        new: value.into(),
+    });
+}
+
+/// 되돌리기 단계마다 (old, new) 목록
+fn steps(history: &History) -> Vec<Vec<(String, String)>> {
+    history
+        .undo_stack()
+        .iter()
+        .map(|c| c.edits.iter().map(|e| (e.old.clone(), e.new.clone())).collect())
+        .collect()
+}
+
+/// 비교용 행 내용: (키, 스키마 순서의 셀 값들)
+fn rows(t: &Table) -> Vec<(String, Vec<String>)> {
+    t.rows
+        .iter()
+        .map(|(k, r)| (k.clone(), t.schema.columns.iter().map(|c| r.get(&c.key).unwrap_or("").to_string()).collect()))
+        .collect()
+}
+
+fn headers(t: &Table) -> Vec<&str> {
+    t.schema.columns.iter().map(|c| c.key.as_str()).collect()
+}
+
+fn pair(old: &str, new: &str) -> (String, String) {
+    (old.to_string(), new.to_string())
+}
+
+#[test]
+fn same_cell_edits_inside_the_window_merge() {
+    let mut ds = sets();
+    let mut h = History::default();
+    edit(&mut ds, &mut h, "1", "Health", "3");
+    edit(&mut ds, &mut h, "1", "Health", "33");
+    edit(&mut ds, &mut h, "1", "Health", "330");
+    assert_eq!(steps(&h), [vec![pair("320", "330")]]);
+    assert_eq!(h.undo_stack()[0].label, "info[1].Health = 330");
+
+    // 다른 셀은 창 안이어도 새 단계, 그 뒤 처음 셀로 돌아와도 합치지 않는다
+    edit(&mut ds, &mut h, "1", "Speed", "2");
+    edit(&mut ds, &mut h, "1", "Health", "331");
+    assert_eq!(steps(&h), [vec![pair("320", "330")], vec![pair("1.5", "2")], vec![pair("330", "331")]]);
+
+    // 원래 값으로 돌아오면 단계 자체가 없어진다
+    edit(&mut ds, &mut h, "1", "Health", "330");
+    assert_eq!(steps(&h).len(), 2);
+
+    // undo/redo 뒤에는 창이 끊긴다
+    h.undo(&mut ds);
+    h.redo(&mut ds);
+    edit(&mut ds, &mut h, "1", "Speed", "2.5");
+    edit(&mut ds, &mut h, "1", "Speed", "3");
+    assert_eq!(steps(&h), [vec![pair("320", "330")], vec![pair("1.5", "2")], vec![pair("2", "3")]]);
+
+    // 묶음 기록 뒤에도 (빈 명령은 기록하지 않음)
+    h.push(Command::edits("빈 단계", Vec::new()));
+    assert_eq!(steps(&h).len(), 3);
+    let old = cell(&ds, "2", "Speed");
+    ds.table_mut("info").unwrap().rows.get_mut("2").unwrap().set("Speed", "1".into());
+    h.push(Command::edits(
+        "일괄",
+        vec![CellEdit { table: "info".into(), key: "2".into(), column: "Speed".into(), old, new: "1".into() }],
+    ));
+    edit(&mut ds, &mut h, "2", "Speed", "1.1");
+    assert_eq!(steps(&h).len(), 5);
+}
+
+#[test]
+fn same_cell_edits_outside_the_window_do_not_merge() {
+    let mut ds = sets();
+    let mut h = History::default();
+    edit(&mut ds, &mut h, "2", "Name", "드워프 왕");
+    thread::sleep(MERGE_WINDOW + Duration::from_millis(50));
+    edit(&mut ds, &mut h, "2", "Name", "드워프 여왕");
+    assert_eq!(steps(&h), [vec![pair("드워프", "드워프 왕")], vec![pair("드워프 왕", "드워프 여왕")]]);
+
+    h.undo(&mut ds);
+    assert_eq!(cell(&ds, "2", "Name"), "드워프 왕");
+    h.undo(&mut ds);
+    assert_eq!(cell(&ds, "2", "Name"), "드워프");
+}
+
+#[test]
+fn undo_and_redo_restore_cells_rows_and_tables() {
+    let mut ds = sets();
+    let original = ds.table("info").unwrap().clone();
+    let mut h = History::default();
+    let rev = h.revision();
+
+    // 셀 여러 개를 한 단계로
+    let edits: Vec<CellEdit> = ["1", "2"]
+        .iter()
+        .map(|k| CellEdit {
+            table: "info".into(),
+            key: k.to_string(),
+            column: "Class".into(),
+            old: cell(&ds, k, "Class"),
+            new: "Mage".into(),
+        })
+        .collect();
+    for e in &edits {
+        ds.table_mut("info").unwrap().rows.get_mut(&e.key).unwrap().set("Class", e.new.clone());
+    }
+    h.push(Command::edits("일괄 편집", edits));
+
+    assert_eq!(headers(&original), ["CharacterUnique", "Name", "Class", "Health", "Speed", "Playable", "Released"]);
+
+    // 행 삭제 + 추가
+    let t = ds.table_mut("info").unwrap();
+    let removed = t.rows.remove("3");
+    let mut added = t.rows["1"].clone();
+    added.key = "4".into();
+    t.rows.insert("4".into(), added.clone());
+    h.push(Command::rows(
+        "행 삭제/추가",
+        vec![
+            RowEdit { table: "info".into(), key: "3".into(), before: removed, after: None },
+            RowEdit { table: "info".into(), key: "4".into(), before: None, after: Some(added) },
+        ],
+    ));
+
+    // 키 컬럼 이름 변경 + 컬럼 삭제 + 행 순서
+    let before = ds.table("info").unwrap().clone();
+    ds.rename_column("info", "CharacterUnique", "Id").unwrap();
+    let t = ds.table_mut("info").unwrap();
+    t.remove_column("Speed").unwrap();
+    t.order.reverse();
+    let after = t.clone();
+    h.push(Command::tables("구조 변경", vec![TableEdit { before, after: after.clone() }]));
+    assert_eq!(h.revision(), rev + 3);
+
+    let t = |ds: &DataSets| ds.table("info").unwrap().clone();
+    h.undo(&mut ds);
+    let undone = t(&ds);
+    assert_eq!(undone.schema.key_column, "CharacterUnique");
+    assert_eq!(undone.spec.key_hint, "CharacterUnique");
+    assert_eq!(headers(&undone), headers(&original));
+    assert_eq!(undone.order, original.order);
+    assert!(undone.rows.contains_key("4") && !undone.rows.contains_key("3"));
+
+    let cmd = h.undo(&mut ds).unwrap();
+    assert_eq!(cmd.first_key(), Some("3"));
+    assert_eq!(t(&ds).rows.keys().collect::<Vec<_>>(), ["1", "2", "3"]);
+    assert_eq!(rows(&t(&ds))[2], rows(&original)[2]);
+
+    let cmd = h.undo(&mut ds).unwrap();
+    assert_eq!(cmd.first_key(), Some("1"));
+    assert_eq!(rows(&t(&ds)), rows(&original));
+    assert!(h.undo(&mut ds).is_none());
+    assert_eq!(h.revision(), rev + 6);
+
+    for _ in 0..3 {
+        h.redo(&mut ds);
+    }
+    let redone = t(&ds);
+    assert_eq!(headers(&redone), headers(&after));
+    assert_eq!(redone.schema.key_column, "Id");
+    assert_eq!(redone.spec.key_hint, "Id");
+    assert_eq!(redone.order, after.order);
+    assert_eq!(rows(&redone), rows(&after));
+    assert_eq!(redone.rows["2"].get("Class"), Some("Mage"));
+    assert!(!h.can_redo());
+}
+
+#[test]
+fn new_edits_drop_the_redo_stack() {
+    let mut ds = sets();
+    let mut h = History::default();
+    edit(&mut ds, &mut h, "1", "Health", "1");
+    edit(&mut ds, &mut h, "2", "Health", "2");
+    edit(&mut ds, &mut h, "3", "Health", "3");
+    h.undo(&mut ds);
+    h.undo(&mut ds);
+    assert_eq!(h.redo_stack().len(), 2);
+    assert_eq!(h.redo_stack().last().unwrap().first_key(), Some("2")); // 다음 redo
+
+    // 셀 편집 기록
+    edit(&mut ds, &mut h, "1", "Speed", "9");
+    assert!(!h.can_redo());
+    assert!(h.redo(&mut ds).is_none());
+    assert_eq!(cell(&ds, "2", "Health"), "610");
+    assert_eq!(steps(&h), [vec![pair("320", "1")], vec![pair("1.5", "9")]]);
+
+    // 여러 단계 묶음 기록
+    h.undo(&mut ds);
+    assert!(h.can_redo());
+    h.push(Command::edits(
+        "일괄",
+        vec![CellEdit { table: "info".into(), key: "3".into(), column: "Name".into(), old: "오크 전사".into(), new: "오크".into() }],
+    ));
+    assert!(h.redo_stack().is_empty());
+    assert_eq!(h.undo_stack().len(), 2);
+
+    // clear는 둘 다 비우고 revision은 계속 증가
+    let rev = h.revision();
+    h.clear();
+    assert!(!h.can_undo() && !h.can_redo());
+    assert!(h.revision() > rev);
+}
@@ -0,0 +1,83 @@
+// 키 컬럼 선택과 dtype 추론
+
+mod common;
+
+use common::{fixture, info_spec};
+use entity_manager::project::{StatusKeyMode, STATUS_TABLE};
+use entity_manager::{load_table, DataType, Table, TableSpec};
+
+fn dtype_of(schema: &entity_manager::TableSchema, column: &str) -> DataType {
+    schema.find(column).unwrap_or_else(|| panic!("no column {column}")).dtype.clone()
+}
+
+#[test]
+fn key_column_exact_hint() {
+    let (schema, rows, _, _) = load_table(&fixture("character_info.csv"), "CharacterUnique", ',', None).unwrap();
+    assert_eq!(schema.key_column, "CharacterUnique");
+    assert_eq!(rows["1"].get("Name"), Some("엘프 궁수"));
+}
+
+#[test]
+fn key_column_hint_ignores_case() {
+    let (schema, rows, _, _) = load_table(&fixture("character_status_info.csv"), "Unique", ',', None).unwrap();
+    assert_eq!(schema.key_column, "unique");
+    assert_eq!(rows["2"].get("Mana"), Some("40"));
+}
+
+#[test]
+fn key_column_falls_back_to_first_column() {
+    let (schema, rows, order, _) = load_table(&fixture("no_key_hint.csv"), "CharacterUnique", ',', None).unwrap();
+    assert_eq!(schema.key_column, "Name");
+    assert_eq!(order, ["alpha", "beta"]);
+    assert_eq!(rows["beta"].get("Score"), Some("2"));
+}
+
+#[test]
+fn status_key_mode_only_overrides_status_table() {
+    let specs = vec![
+        TableSpec::new("info", "Info", "info.csv", "CharacterUnique"),
+        TableSpec::new(STATUS_TABLE, "Status", "status.csv", "CharacterUnique"),
+    ];
+    let applied = StatusKeyMode::Custom("unique".into()).apply(&specs);
+    assert_eq!(applied[0].key_hint, "CharacterUnique");
+    assert_eq!(applied[1].key_hint, "unique");
+}
+
+#[test]
+fn infers_numeric_bool_date_and_text() {
+    let (schema, _, _, _) = load_table(&fixture("types.csv"), "Id", ',', None).unwrap();
+    assert_eq!(dtype_of(&schema, "Id"), DataType::Int);
+    assert_eq!(dtype_of(&schema, "Count"), DataType::Int);
+    assert_eq!(dtype_of(&schema, "Ratio"), DataType::Float); // 0.5, 2
+    assert_eq!(dtype_of(&schema, "Flag"), DataType::Bool); // 대소문자 무시
+    assert_eq!(dtype_of(&schema, "When"), DataType::Date); // '/', '.' 구분자
+    assert_eq!(dtype_of(&schema, "Memo"), DataType::Text);
+}
+
+#[test]
+fn list_enum_and_reference_are_not_inferred() {
+    let (schema, _, _, _) = load_table(&fixture("types.csv"), "Id", ',', None).unwrap();
+    assert_eq!(dtype_of(&schema, "Tags"), DataType::Text);
+}
+
+#[test]
+fn quoted_fields_keep_delimiters() {
+    let (_, rows, _, _) = load_table(&fixture("types.csv"), "Id", ',', None).unwrap();
+    assert_eq!(rows["2"].get("Memo"), Some("with, comma"));
+}
+
+#[test]
+fn tab_delimited_table() {
+    let t = Table::load(&common::attack_spec(&fixture("character_attack_info.txt"))).unwrap();
+    assert_eq!(t.schema.columns.len(), 3);
+    assert_eq!(dtype_of(&t.schema, "DefensePower"), DataType::Int);
+    assert_eq!(t.rows["3"].get("AttackPower"), Some("55"));
+}
+
+#[test]
+fn schema_name_comes_from_file_stem() {
+    let t = Table::load(&info_spec(&fixture("character_info.csv"))).unwrap();
+    assert_eq!(t.schema.name, "character_info");
+    assert_eq!(dtype_of(&t.schema, "Released"), DataType::Date);
+    assert_eq!(dtype_of(&t.schema, "Playable"), DataType::Bool);
+}
@@ -0,0 +1,120 @@
+// 빈 키/중복 키 행: 로드 진단과 해결
+
+mod common;
+
+use std::fs;
+
+use common::{copy_fixture, fixture, info_spec, scratch_dir};
+use entity_manager::dyn_entity::join_key;
+use entity_manager::history::{Command, History, TableEdit};
+use entity_manager::key_conflicts::{self, ConflictKind, MergePrefer};
+use entity_manager::storage::render_table;
+use entity_manager::{save_table, validate, DataSets, Severity, Table, TableSpec};
+
+fn load() -> Table {
+    Table::load(&info_spec(&fixture("key_conflicts.csv"))).unwrap()
+}
+
+#[test]
+fn conflicts_are_listed_with_line_numbers_and_first_row_wins() {
+    let t = load();
+    assert_eq!(t.order, ["1", "2", "3"]);
+    assert_eq!(t.rows["1"].get("Name"), Some("고블린"));
+    assert_eq!(t.rows["2"].get("Name"), Some("오크"));
+
+    let found: Vec<_> = t.conflicts.iter().map(|c| (c.kind, c.line, c.first_line, c.row.key.as_str())).collect();
+    assert_eq!(
+        found,
+        [
+            (ConflictKind::Empty, 4, None, ""),
+            (ConflictKind::Duplicate, 5, Some(3), "2"),
+            (ConflictKind::Duplicate, 7, Some(2), "1"),
+        ]
+    );
+    assert_eq!(t.conflicts[1].to_string(), "5번째 줄: 키 '2' 중복 (3번째 줄과 같음)");
+
+    let ds = DataSets::load(std::slice::from_ref(&t.spec)).unwrap();
+    let errors: Vec<_> = validate(&ds).into_iter().filter(|d| d.severity == Severity::Error).collect();
+    assert_eq!(errors.len(), 3);
+    assert!(errors.iter().all(|d| d.column == "CharacterUnique"));
+}
+
+#[test]
+fn unresolved_conflicts_are_written_back_in_place() {
+    let t = load();
+    let bytes = render_table(&t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+    assert_eq!(bytes, fs::read(fixture("key_conflicts.csv")).unwrap());
+}
+
+#[test]
+fn renumber_keeps_file_position() {
+    let dir = scratch_dir("key_renumber");
+    let spec = info_spec(&copy_fixture(&dir, "key_conflicts.csv"));
+    let mut t = Table::load(&spec).unwrap();
+    assert!(key_conflicts::renumber(&mut t, 0, "2").is_err()); // 이미 있는 키
+    key_conflicts::renumber(&mut t, 0, "10").unwrap();
+    assert_eq!(t.order, ["1", "2", "10", "3"]);
+    assert_eq!(t.rows["10"].get("CharacterUnique"), Some("10"));
+    save_table(&spec.path, &t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+
+    let text = fs::read_to_string(&spec.path).unwrap();
+    assert_eq!(text, "CharacterUnique,Name,Health\n1,고블린,10\n2,오크,20\n10,이름없음,5\n2,오크 복제,\n3,트롤,30\n1,,99\n");
+}
+
+#[test]
+fn merge_fills_blanks_and_respects_preference() {
+    let mut t = load();
+    // 7번째 줄 "1,,99": 이름은 비어 있으므로 기존 값 유지, 체력은 선호에 따라
+    assert_eq!(key_conflicts::merge(&mut t, 2, MergePrefer::Existing).unwrap(), 0);
+    assert_eq!(t.rows["1"].get("Health"), Some("10"));
+
+    let mut t = load();
+    assert_eq!(key_conflicts::merge(&mut t, 2, MergePrefer::Duplicate).unwrap(), 1);
+    assert_eq!(t.rows["1"].get("Health"), Some("99"));
+    assert_eq!(t.rows["1"].get("Name"), Some("고블린"));
+    assert_eq!(t.conflicts.len(), 2);
+
+    assert!(key_conflicts::merge(&mut t, 0, MergePrefer::Existing).is_err()); // 빈 키는 합칠 곳이 없음
+}
+
+#[test]
+fn renumber_all_and_delete_resolve_everything_with_undo() {
+    let mut ds = DataSets::load(&[info_spec(&fixture("key_conflicts.csv"))]).unwrap();
+    let before = ds.tables[0].clone();
+    key_conflicts::delete(&mut ds.tables[0], 2).unwrap();
+    let keys = key_conflicts::renumber_all(&mut ds, "info").unwrap();
+    assert_eq!(keys, ["4", "5"]);
+    let t = &ds.tables[0];
+    assert!(t.conflicts.is_empty());
+    assert_eq!(t.order, ["1", "2", "4", "5", "3"]);
+    assert!(!validate(&ds).iter().any(|d| d.severity == Severity::Error));
+
+    let mut history = History::default();
+    let after = ds.tables[0].clone();
+    history.push(Command::tables("키 문제", vec![TableEdit { before, after }]));
+    history.undo(&mut ds);
+    assert_eq!(ds.tables[0].conflicts.len(), 3);
+    assert_eq!(ds.tables[0].order, ["1", "2", "3"]);
+}
+
+#[test]
+fn renumber_all_numbers_the_slot_of_composite_keys_without_a_parent() {
+    let dir = scratch_dir("key_renumber_composite");
+    let path = dir.join("zone.csv");
+    fs::write(&path, "Zone,Slot,Name\n1,1,a\n1,1,b\n1,2,c\n2,1,d\n2,1,e\n,1,f\n").unwrap();
+    let spec = TableSpec::new("zone", "Zone", &path.to_string_lossy(), "Zone+Slot");
+    let mut ds = DataSets::load(&[spec]).unwrap();
+    ds.tables[0].schema.parent_column = None;
+    assert_eq!(ds.tables[0].conflicts.len(), 3);
+
+    let keys = key_conflicts::renumber_all(&mut ds, "zone").unwrap();
+    let key = |zone: &str, slot: &str| join_key(&[zone, slot]);
+    assert_eq!(keys, [key("1", "3"), key("2", "2")]);
+    let t = &ds.tables[0];
+    assert_eq!(t.rows[&key("1", "3")].get("Name"), Some("b"));
+    assert_eq!(t.rows[&key("2", "2")].get("Slot"), Some("2"));
+    assert_eq!(t.order, [key("1", "1"), key("1", "3"), key("1", "2"), key("2", "1"), key("2", "2")]);
+    // Zone이 빈 행은 어디 것인지 모르므로 남는다
+    assert_eq!(t.conflicts.len(), 1);
+    assert_eq!(t.conflicts[0].row.get("Name"), Some("f"));
+}
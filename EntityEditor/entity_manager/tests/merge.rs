@@ -0,0 +1,161 @@
+// 파일이 밖에서 바뀌었을 때의 3-way 병합 (기준 = 로드 당시, 디스크 = 지금 파일, 내 것 = 메모리)
+//
+// merge_base.csv -> merge_disk.csv (다른 사람이 바꾼 파일):
+//   1 Hp 10 -> 11, 2 Name 오크 -> 오크 전사, 3 Hp 30 -> 33, 5 Hp 50 -> 55, 4/6 삭제, 7 추가,
+//   Note 컬럼 삭제, Tier 컬럼 추가, 행 순서 변경
+
+mod common;
+
+use common::fixture;
+use entity_manager::merge::{merge_table, MergeResult};
+use entity_manager::{Table, TableSpec};
+
+fn load(name: &str) -> Table {
+    Table::load(&TableSpec::new("monster", "Monster", &fixture(name), "Id")).unwrap()
+}
+
+fn set(t: &mut Table, key: &str, column: &str, value: &str) {
+    t.rows.get_mut(key).unwrap().set(column, value.to_string());
+}
+
+fn get<'a>(t: &'a Table, key: &str, column: &str) -> Option<&'a str> {
+    t.rows.get(key).and_then(|r| r.get(column))
+}
+
+fn headers(t: &Table) -> Vec<&str> {
+    t.schema.columns.iter().map(|c| c.key.as_str()).collect()
+}
+
+/// (키, 컬럼, 디스크 값, 내 값)
+fn conflicts(result: &MergeResult) -> Vec<(&str, &str, Option<&str>, Option<&str>)> {
+    result
+        .conflicts
+        .iter()
+        .map(|c| (c.key.as_str(), c.column.as_str(), c.disk.as_deref(), c.mine.as_deref()))
+        .collect()
+}
+
+/// 기준에서 출발한 내 편집: 2는 디스크와 같게, 3은 다르게, 4는 수정(디스크는 삭제),
+/// 5와 9는 삭제(5는 디스크가 수정), 8 추가
+fn mine() -> Table {
+    let mut t = load("merge_base.csv");
+    set(&mut t, "2", "Name", "오크 전사");
+    set(&mut t, "3", "Hp", "35");
+    set(&mut t, "4", "Name", "리치 왕");
+    t.rows.remove("5");
+    t.rows.remove("9");
+    let mut row = t.rows["1"].clone();
+    row.key = "8".into();
+    row.set("Id", "8".into());
+    row.set("Name", "오우거".into());
+    t.rows.insert("8".into(), row);
+    t
+}
+
+#[test]
+fn nothing_changed_on_disk_keeps_my_table() {
+    let base = load("merge_base.csv");
+    let mine = mine();
+    let result = merge_table(&base, &base, &mine);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.from_disk, 0);
+    assert_eq!(headers(&result.merged), headers(&mine));
+    assert_eq!(result.merged.rows.keys().collect::<Vec<_>>(), mine.rows.keys().collect::<Vec<_>>());
+    assert_eq!(get(&result.merged, "3", "Hp"), Some("35"));
+
+    // 나도 안 바꿨으면 디스크 그대로
+    let disk = load("merge_disk.csv");
+    let result = merge_table(&base, &disk, &base);
+    assert!(result.conflicts.is_empty());
+    assert_eq!(result.merged.rows.keys().collect::<Vec<_>>(), disk.rows.keys().collect::<Vec<_>>());
+    assert_eq!(headers(&result.merged), ["Id", "Name", "Hp", "Tier"]);
+    for key in disk.rows.keys() {
+        for column in ["Name", "Hp", "Tier"] {
+            assert_eq!(get(&result.merged, key, column), get(&disk, key, column), "{}.{}", key, column);
+        }
+    }
+}
+
+#[test]
+fn one_sided_and_identical_edits_merge_without_conflicts() {
+    let result = merge_table(&load("merge_base.csv"), &load("merge_disk.csv"), &mine());
+    let m = &result.merged;
+
+    // 디스크만 바꾼 셀은 디스크 값, 나만 바꾼/추가한 것은 내 값
+    assert_eq!(get(m, "1", "Hp"), Some("11"));
+    assert_eq!(get(m, "8", "Name"), Some("오우거"));
+    // 양쪽이 같게 바꾼 셀은 충돌 아님
+    assert_eq!(get(m, "2", "Name"), Some("오크 전사"));
+    // 디스크에서 추가된 행은 들어오고, 디스크에서 지운 행은 내가 안 건드렸으면 빠진다
+    assert_eq!(get(m, "7", "Name"), Some("와이번"));
+    assert!(!m.rows.contains_key("6"));
+    // 내가 지운 행은 디스크가 안 바꿨으면(새 컬럼 값만 생겼으면) 그대로 삭제
+    assert!(!m.rows.contains_key("9"));
+    assert!(!result.conflicts.iter().any(|c| c.key == "9"));
+
+    // Hp(1) + Tier(1, 2, 3) + 행 7 추가 + 행 6 삭제
+    assert_eq!(result.from_disk, 6);
+    // 행 순서는 디스크 파일, 내가 추가한 행은 저장 때 끝에
+    assert_eq!(m.order, ["1", "2", "7", "3", "5", "9"]);
+}
+
+#[test]
+fn conflicting_cells_and_delete_vs_modify_keep_my_side_until_resolved() {
+    let mut result = merge_table(&load("merge_base.csv"), &load("merge_disk.csv"), &mine());
+    assert_eq!(
+        conflicts(&result),
+        [
+            ("3", "Hp", Some("33"), Some("35")),
+            ("4", "", None, Some("(행 있음)")),   // 디스크 삭제 vs 내 수정
+            ("5", "", Some("(행 있음)"), None),   // 내 삭제 vs 디스크 수정
+        ]
+    );
+    assert_eq!(result.conflicts[0].base.as_deref(), Some("30"));
+
+    // 기본은 내 값
+    let mine_side = result.resolve();
+    assert_eq!(get(&mine_side, "3", "Hp"), Some("35"));
+    assert_eq!(get(&mine_side, "4", "Name"), Some("리치 왕"));
+    assert!(!mine_side.rows.contains_key("5"));
+
+    // 디스크 쪽을 고르면 디스크 값 / 디스크 행 / 삭제
+    for c in &mut result.conflicts {
+        c.take_disk = true;
+    }
+    let disk_side = result.resolve();
+    assert_eq!(get(&disk_side, "3", "Hp"), Some("33"));
+    assert!(!disk_side.rows.contains_key("4"));
+    assert_eq!(get(&disk_side, "5", "Hp"), Some("55"));
+    assert_eq!(get(&disk_side, "5", "Tier"), Some("5"));
+    // 충돌 아닌 부분은 그대로
+    assert_eq!(get(&disk_side, "1", "Hp"), Some("11"));
+    assert_eq!(get(&disk_side, "8", "Name"), Some("오우거"));
+    assert!(result.merged.rows.contains_key("4"), "resolve는 병합 결과를 바꾸지 않는다");
+}
+
+#[test]
+fn columns_added_on_disk_are_filled_and_untouched_dropped_columns_go() {
+    let result = merge_table(&load("merge_base.csv"), &load("merge_disk.csv"), &mine());
+    let m = &result.merged;
+    // Note는 디스크에서 지웠고 나는 안 건드렸다
+    assert_eq!(headers(m), ["Id", "Name", "Hp", "Tier"]);
+    assert_eq!(get(m, "3", "Tier"), Some("2"));
+    // 내가 추가한 행과 디스크에 없던 행에는 새 컬럼 기본값
+    assert_eq!(get(m, "8", "Tier"), Some("0"));
+    assert_eq!(get(m, "4", "Tier"), Some("0"));
+}
+
+#[test]
+fn column_dropped_on_disk_stays_when_i_edited_it() {
+    let mut mine = mine();
+    set(&mut mine, "1", "Note", "아주 약함");
+    let result = merge_table(&load("merge_base.csv"), &load("merge_disk.csv"), &mine);
+    let m = &result.merged;
+    assert_eq!(headers(m), ["Id", "Name", "Hp", "Note", "Tier"]);
+    // 디스크 행에는 Note 값이 없으니 내 값 유지, 디스크에서 새로 온 행은 빈 값
+    assert_eq!(get(m, "1", "Note"), Some("아주 약함"));
+    assert_eq!(get(m, "2", "Note"), Some("보통"));
+    assert_eq!(get(m, "7", "Note"), Some(""));
+    assert!(!result.conflicts.iter().any(|c| c.column == "Note"));
+    assert_eq!(conflicts(&result).len(), 3);
+}
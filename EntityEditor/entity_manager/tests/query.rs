@@ -0,0 +1,263 @@
+// 필터/일괄 편집/계산 컬럼 식: 파싱 오류 위치, 우선순위, 타입 변환, 자식 테이블 필터
+
+mod common;
+
+use std::fs;
+
+use common::{fixture, info_spec, scratch_dir, skill_spec};
+use entity_manager::query::{self, Expr, Scope, Value, MAX_CHILD_COMBINATIONS};
+use entity_manager::{DataSets, DataType, TableSpec};
+
+fn sets() -> DataSets {
+    DataSets::load(&[
+        info_spec(&fixture("character_info.csv")),
+        TableSpec::new("status", "Status", &fixture("character_status_info.csv"), "unique"),
+    ])
+    .unwrap()
+}
+
+/// 엔티티 `key`에서 식 값 (기본 테이블 info)
+fn eval(ds: &DataSets, key: &str, src: &str) -> Result<Value, String> {
+    let expr = query::parse(src).map_err(|e| e.to_string())?;
+    expr.check(ds, Some("info")).map_err(|e| e.to_string())?;
+    let scope = Scope { ds, key, default_table: Some("info"), child_rows: &[] };
+    expr.eval(&scope).map_err(|e| e.to_string())
+}
+
+fn value(src: &str) -> Value {
+    eval(&sets(), "1", src).unwrap()
+}
+
+/// (위치, 메시지 일부)
+fn parse_error(src: &str) -> (usize, String) {
+    let e = query::parse(src).unwrap_err();
+    (e.pos, e.message)
+}
+
+fn check_error(src: &str) -> (usize, String) {
+    let e = query::parse(src).unwrap().check(&sets(), Some("info")).unwrap_err();
+    (e.pos, e.message)
+}
+
+fn filter(ds: &DataSets, src: &str) -> Vec<String> {
+    let expr = query::parse(src).unwrap();
+    expr.check(ds, None).unwrap();
+    let keys: Vec<String> = ds.keys().into_iter().collect();
+    let result = query::filter_keys(ds, &expr, &keys);
+    assert_eq!(result.errors, 0, "{}: {:?}", src, result.first_error);
+    result.keys
+}
+
+#[test]
+fn parse_errors_point_at_the_offending_character() {
+    let cases: &[(&str, usize, &str)] = &[
+        ("Health = 3", 7, "'=='"),
+        ("Health > 1 & Speed", 11, "'&&'"),
+        ("Name == \"오크", 8, "닫히지 않은 문자열"),
+        ("`Max Health > 3", 0, "닫히지 않은 `이름`"),
+        ("Health >", 8, "식이 끝났습니다"),
+        ("Health 3", 7, "연산자가 빠졌나요"),
+        ("(1 + 2", 6, "')'이(가) 필요합니다"),
+        ("info. > 3", 6, "'.' 뒤에 컬럼 이름"),
+        ("1 + * 2", 4, "'*'"),
+        ("min(1 2)", 6, "','이(가) 필요합니다"),
+        ("1.2.3", 0, "잘못된 숫자"),
+        // 위치는 바이트가 아니라 글자 단위
+        ("Name == \"오크\" | 1", 13, "'||'"),
+    ];
+    for (src, pos, message) in cases {
+        let (p, m) = parse_error(src);
+        assert_eq!(p, *pos, "{}: {}", src, m);
+        assert!(m.contains(message), "{}: {}", src, m);
+    }
+
+    let e = query::parse("Health = 3").unwrap_err();
+    assert_eq!(e.to_string(), "8번째 글자: 알 수 없는 문자 '=' ('=='를 쓰세요)");
+    assert_eq!(e.render("Health = 3").lines().last(), Some("       ^"));
+}
+
+#[test]
+fn check_errors_point_at_the_name() {
+    let cases: &[(&str, usize, &str)] = &[
+        ("nope.Health > 1", 0, "알 수 없는 테이블 'nope'"),
+        ("1 + info.Mana", 4, "테이블 'info'에 컬럼 'Mana' 없음"),
+        ("Health > 1 && Nope", 14, "어느 테이블에도 컬럼 'Nope' 없음"),
+        ("abs(foo(1))", 4, "알 수 없는 함수 'foo'"),
+        ("2 * min(Health)", 4, "min()는 인자 2개가 필요합니다"),
+    ];
+    for (src, pos, message) in cases {
+        let (p, m) = check_error(src);
+        assert_eq!(p, *pos, "{}: {}", src, m);
+        assert!(m.contains(message), "{}: {}", src, m);
+    }
+    // 테이블 없이 쓴 컬럼은 기본 테이블, 그다음 앞쪽 테이블에서 (대소문자 무시)
+    assert_eq!(value("mana"), Value::Num(150.0));
+    assert_eq!(value("health"), Value::Num(320.0));
+}
+
+#[test]
+fn precedence_and_associativity() {
+    let cases: &[(&str, Value)] = &[
+        ("1 + 2 * 3", Value::Num(7.0)),
+        ("(1 + 2) * 3", Value::Num(9.0)),
+        ("10 - 4 - 3", Value::Num(3.0)),
+        ("100 / 10 / 5", Value::Num(2.0)),
+        ("7 % 4 * 2", Value::Num(6.0)),
+        ("-2 * 3", Value::Num(-6.0)),
+        ("2 * -3", Value::Num(-6.0)),
+        ("--2", Value::Num(2.0)),
+        ("1 + 2 > 2", Value::Bool(true)),
+        ("true || false && false", Value::Bool(true)),
+        ("(true || false) && false", Value::Bool(false)),
+        ("false or true and not false", Value::Bool(true)),
+        // `!`는 &&보다 강하게 묶인다
+        ("!true && false", Value::Bool(false)),
+        ("!false || true", Value::Bool(true)),
+    ];
+    for (src, expected) in cases {
+        assert_eq!(value(src), *expected, "{}", src);
+    }
+    // 비교는 이어 쓸 수 없다
+    let (pos, message) = parse_error("1 < 2 < 3");
+    assert_eq!(pos, 6);
+    assert!(message.contains("해석할 수 없는 나머지"), "{}", message);
+}
+
+/// 설계상 `!`는 비교보다 느슨하게 묶인다: `!a == b`는 `!(a == b)`.
+/// 바꾸면 저장된 필터의 뜻이 조용히 달라지므로 고정해 둔다.
+#[test]
+fn not_binds_looser_than_comparisons() {
+    let col = |column: &str, pos| Expr::Column { table: None, column: column.to_string(), pos };
+    assert_eq!(
+        query::parse("!Health > 400").unwrap(),
+        Expr::Not(Box::new(Expr::Binary(">", Box::new(col("Health", 1)), Box::new(Expr::Lit(Value::Num(400.0))))))
+    );
+    assert_eq!(
+        query::parse("not Class == \"Warrior\" && Health > 0").unwrap(),
+        Expr::Binary(
+            "&&",
+            Box::new(Expr::Not(Box::new(Expr::Binary(
+                "==",
+                Box::new(col("Class", 4)),
+                Box::new(Expr::Lit(Value::Str("Warrior".into())))
+            )))),
+            Box::new(Expr::Binary(">", Box::new(col("Health", 26)), Box::new(Expr::Lit(Value::Num(0.0))))),
+        )
+    );
+    // (!1) == 2였다면 false
+    assert_eq!(value("!1 == 2"), Value::Bool(true));
+    assert_eq!(filter(&sets(), "!Health > 400"), ["1"]);
+}
+
+#[test]
+fn values_are_coerced_by_operator() {
+    let cases: &[(&str, Value)] = &[
+        // 숫자로 읽히면 숫자 비교, 아니면 문자열 비교
+        ("\"10\" == 10", Value::Bool(true)),
+        ("\"9\" < \"10\"", Value::Bool(true)),
+        ("\"abc\" < \"abd\"", Value::Bool(true)),
+        ("\"10\" < \"9x\"", Value::Bool(true)),
+        // +는 둘 다 숫자면 더하기, 아니면 이어 붙이기
+        ("\"1\" + \"2\"", Value::Num(3.0)),
+        ("\"a\" + 1", Value::Str("a1".into())),
+        ("true + 1", Value::Num(2.0)),
+        // 빈 값은 산술에서 값 없음
+        ("\"\" * 3", Value::Null),
+        ("\"Warrior\" ~ \"RRI\"", Value::Bool(true)),
+        ("\"Warrior\" !~ \"arch\"", Value::Bool(true)),
+        ("len(Name) + 1", Value::Num(6.0)),
+        ("upper(Class)", Value::Str("ARCHER".into())),
+        ("round(Speed) + floor(2.7) + ceil(0.1)", Value::Num(5.0)),
+        ("max(Health, \"400\")", Value::Num(400.0)),
+        ("key() * 10", Value::Num(10.0)),
+        // 셀은 숫자로 읽히면 숫자, true/false 셀은 문자열로 bool 리터럴과 같다
+        ("Health", Value::Num(320.0)),
+        ("Playable == false", Value::Bool(true)),
+        ("Released < \"2023-02-01\"", Value::Bool(true)),
+        ("0.1 + 0.2 == 0.3", Value::Bool(false)),
+    ];
+    for (src, expected) in cases {
+        assert_eq!(value(src), *expected, "{}", src);
+    }
+    assert_eq!(value("0.1 + 0.2").to_string(), "0.3"); // 셀에 쓸 때는 잡음 제거
+
+    let ds = sets();
+    for (src, message) in [("1 / 0", "0으로"), ("Health % 0", "0으로"), ("Name * 2", "문자열 '엘프 궁수'"), ("abs(Class)", "'abs'")] {
+        let e = eval(&ds, "1", src).unwrap_err();
+        assert!(e.contains(message), "{}: {}", src, e);
+    }
+
+    // 행이 없으면 null: == / != 만 의미가 있고 순서 비교는 거짓
+    assert_eq!(eval(&ds, "99", "Health == status.Mana").unwrap(), Value::Bool(true));
+    assert_eq!(eval(&ds, "99", "Health != 0").unwrap(), Value::Bool(true));
+    assert_eq!(eval(&ds, "99", "Health > 0 || Health <= 0").unwrap(), Value::Bool(false));
+    assert_eq!(eval(&ds, "99", "Health + 1").unwrap(), Value::Null);
+
+    // 셀로 되돌릴 때는 컬럼 타입에 맞춘다
+    assert_eq!(Value::Num(2.5).to_cell(&DataType::Int, "").unwrap(), "3");
+    assert_eq!(Value::Str(" 7 ".into()).to_cell(&DataType::Int, "").unwrap(), "7");
+    assert!(Value::Str("7.5".into()).to_cell(&DataType::Int, "").is_err());
+    assert_eq!(Value::Bool(false).to_cell(&DataType::Bool, "Yes").unwrap(), "No"); // 기존 값 표기 유지
+    assert_eq!(Value::Num(1.0).to_cell(&DataType::Bool, "0").unwrap(), "1");
+    assert!(Value::Str("2024-02-30".into()).to_cell(&DataType::Date, "").is_err());
+    assert!(Value::Str("C".into()).to_cell(&DataType::Enum(vec!["A".into(), "B".into()]), "").is_err());
+    assert_eq!(Value::Null.to_cell(&DataType::Int, "5").unwrap(), "");
+}
+
+#[test]
+fn child_table_filters_bind_one_row_per_table() {
+    let ds = DataSets::load(&[
+        info_spec(&fixture("character_info.csv")),
+        skill_spec(&fixture("character_skill.csv")),
+        TableSpec::new("skill2", "Skill 2", &fixture("character_skill.csv"), "CharacterUnique+SkillSlot"),
+    ])
+    .unwrap();
+    // 같은 테이블의 컬럼은 같은 행에서
+    assert!(filter(&ds, "skill.Level == 5 && skill.SkillSlot == 2").is_empty());
+    assert_eq!(filter(&ds, "skill.Level == 2 && skill.SkillSlot == 2"), ["2"]);
+    // 다른 자식 테이블은 각자 행을 고른다
+    assert_eq!(filter(&ds, "skill.Level == 5 && skill2.SkillSlot == 2"), ["2"]);
+    assert_eq!(filter(&ds, "skill.Level != skill2.Level && info.Class == \"Archer\""), ["1"]);
+    // 테이블 이름 없이 쓴 컬럼도 자식 테이블이면 행마다
+    assert_eq!(filter(&ds, "SkillName ~ \"함성\""), ["2"]);
+
+    // 자식 행이 없는 엔티티는 빈 값으로 한 번 평가한다 (오류 아님)
+    let expr = query::parse("skill.Level >= 1 || key() == 99").unwrap();
+    let result = query::filter_keys(&ds, &expr, &["99".to_string(), "1".to_string()]);
+    assert_eq!((result.keys, result.errors), (vec!["99".to_string(), "1".to_string()], 0));
+    let expr = query::parse("len(skill.Level) == 0").unwrap();
+    assert_eq!(query::filter_keys(&ds, &expr, &["99".to_string()]).keys, ["99"]);
+}
+
+#[test]
+fn child_row_combinations_are_capped() {
+    let dir = scratch_dir("query_cap");
+    // 엔티티 1에 자식 행 n개: 두 테이블이면 n² 조합
+    let n = (MAX_CHILD_COMBINATIONS as f64).sqrt() as usize + 1;
+    let mut csv = String::from("CharacterUnique,SkillSlot,Level\n");
+    for slot in 1..=n {
+        csv.push_str(&format!("1,{},{}\n", slot, slot));
+    }
+    csv.push_str("2,1,1\n");
+    let path = dir.join("many.csv").to_string_lossy().to_string();
+    fs::write(&path, csv).unwrap();
+    let ds = DataSets::load(&[
+        TableSpec::new("a", "A", &path, "CharacterUnique+SkillSlot"),
+        TableSpec::new("b", "B", &path, "CharacterUnique+SkillSlot"),
+    ])
+    .unwrap();
+
+    let keys = vec!["1".to_string(), "2".to_string()];
+    let expr = query::parse("a.Level == b.Level").unwrap();
+    let result = query::filter_keys(&ds, &expr, &keys);
+    assert_eq!(result.keys, ["2"]);
+    assert_eq!(result.errors, 1);
+    let message = result.first_error.unwrap();
+    assert!(message.starts_with("키 1: 자식 행 조합이 너무 많습니다"), "{}", message);
+    assert!(message.contains(&format!("{}개", n * n)), "{}", message);
+
+    // 자식 테이블 하나면 행 수만큼이라 상한에 걸리지 않는다
+    let expr = query::parse(&format!("a.Level == {}", n)).unwrap();
+    let result = query::filter_keys(&ds, &expr, &keys);
+    assert_eq!((result.keys, result.errors), (vec!["1".to_string()], 0));
+}
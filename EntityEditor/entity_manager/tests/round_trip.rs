@@ -0,0 +1,217 @@
+// 로드 -> 저장 -> 다시 로드
+
+mod common;
+
+use std::fs;
+
+use common::{attack_spec, copy_fixture, fixture, info_spec, legacy_spec, scratch_dir, semicolon_spec, skill_spec};
+use entity_manager::backup::list_backups;
+use entity_manager::convert::{read_json, write_json};
+use entity_manager::history::{Command, History, TableEdit};
+use entity_manager::storage::{render_table, schema_sidecar_path};
+use entity_manager::{save_table, DataSets, DataType, Table, TableSpec};
+
+fn save(t: &Table) {
+    save_table(&t.spec.path, &t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+}
+
+#[test]
+fn unchanged_table_renders_identical_bytes() {
+    for spec in [
+        info_spec(&fixture("character_info.csv")),
+        attack_spec(&fixture("character_attack_info.txt")),
+        semicolon_spec(&fixture("dialect_semicolon.csv")),
+        legacy_spec(&fixture("legacy_cp949.csv")),
+        skill_spec(&fixture("character_skill.csv")),
+    ] {
+        let t = Table::load(&spec).unwrap();
+        let bytes = render_table(&t.schema, &t.rows, &t.order, &t.conflicts, false).unwrap();
+        assert_eq!(bytes, fs::read(&spec.path).unwrap(), "{}", spec.path);
+    }
+}
+
+#[test]
+fn edits_survive_save_and_reload() {
+    let dir = scratch_dir("edits");
+    let spec = info_spec(&copy_fixture(&dir, "character_info.csv"));
+    let mut t = Table::load(&spec).unwrap();
+    t.rows.get_mut("2").unwrap().set("Name", "드워프, 방패병".into());
+    t.rows.get_mut("1").unwrap().set("Health", "999".into());
+    save(&t);
+
+    let back = Table::load(&spec).unwrap();
+    assert_eq!(back.rows["2"].get("Name"), Some("드워프, 방패병"));
+    assert_eq!(back.rows["1"].get("Health"), Some("999"));
+    assert_eq!(back.order, ["3", "1", "2"]); // 파일 순서 유지
+}
+
+#[test]
+fn removed_rows_drop_and_new_rows_append() {
+    let dir = scratch_dir("rows");
+    let mut ds = DataSets::load(&[info_spec(&copy_fixture(&dir, "character_info.csv"))]).unwrap();
+    ds.delete_entity("1");
+    let key = ds.next_free_key();
+    assert_eq!(key, "4");
+    ds.create_entity(&key);
+    save(&ds.tables[0]);
+
+    let back = Table::load(&ds.tables[0].spec).unwrap();
+    assert_eq!(back.order, ["3", "2", "4"]);
+    assert_eq!(back.rows["4"].get("Health"), Some("0")); // Int 기본값
+    assert_eq!(back.rows["4"].get("Playable"), Some("false"));
+}
+
+#[test]
+fn column_changes_are_written_to_header() {
+    let dir = scratch_dir("columns");
+    let mut ds = DataSets::load(&[info_spec(&copy_fixture(&dir, "character_info.csv"))]).unwrap();
+    let t = &mut ds.tables[0];
+    t.add_column("Level", DataType::Int, "1").unwrap();
+    t.remove_column("Speed").unwrap();
+    t.move_column(t.schema.columns.len() - 1, 1);
+    ds.rename_column("info", "Class", "Job").unwrap();
+    save(&ds.tables[0]);
+
+    let text = fs::read_to_string(&ds.tables[0].spec.path).unwrap();
+    assert_eq!(text.lines().next(), Some("CharacterUnique,Level,Name,Job,Health,Playable,Released"));
+    assert_eq!(text.lines().nth(1), Some("3,1,오크 전사,Warrior,500,true,2023-04-01"));
+}
+
+#[test]
+fn undone_key_rename_saves_and_reloads_with_the_old_key() {
+    let dir = scratch_dir("key_rename_undo");
+    let specs = [
+        info_spec(&copy_fixture(&dir, "character_info.csv")),
+        skill_spec(&copy_fixture(&dir, "character_skill.csv")),
+    ];
+    let mut ds = DataSets::load(&specs).unwrap();
+    let mut history = History::default();
+    for (table, old, new) in [("skill", "SkillSlot", "Slot"), ("info", "CharacterUnique", "Id")] {
+        let before = ds.table(table).unwrap().clone();
+        ds.rename_column(table, old, new).unwrap();
+        let after = ds.table(table).unwrap().clone();
+        history.push(Command::tables("이름 변경", vec![TableEdit { before, after }]));
+    }
+    assert_eq!(ds.table("skill").unwrap().spec.key_hint, "CharacterUnique+Slot");
+    history.undo(&mut ds);
+    history.undo(&mut ds);
+    assert_eq!(ds.table("skill").unwrap().spec.key_hint, "CharacterUnique+SkillSlot");
+    assert_eq!(ds.table("info").unwrap().spec.key_hint, "CharacterUnique");
+    ds.save_all(0).unwrap();
+
+    // 프로젝트에 저장되는 설정은 되돌린 테이블의 spec
+    let specs: Vec<_> = ds.tables.iter().map(|t| t.spec.clone()).collect();
+    let back = DataSets::load(&specs).unwrap();
+    assert_eq!(back.table("skill").unwrap().schema.sub_keys, ["SkillSlot"]);
+    assert_eq!(back.table("info").unwrap().schema.key_column, "CharacterUnique");
+    assert_eq!(back.table("skill").unwrap().rows.len(), 6);
+
+    history.redo(&mut ds);
+    assert_eq!(ds.table("skill").unwrap().spec.key_hint, "CharacterUnique+Slot");
+}
+
+#[test]
+fn sidecar_schema_overrides_inference() {
+    let dir = scratch_dir("sidecar");
+    let spec = info_spec(&copy_fixture(&dir, "character_info.csv"));
+    let mut t = Table::load(&spec).unwrap();
+    let class = t.schema.columns.iter_mut().find(|c| c.key == "Class").unwrap();
+    class.dtype = DataType::Enum(vec!["Warrior".into(), "Archer".into()]);
+    class.rules.required = true;
+    t.save_schema().unwrap();
+    assert!(schema_sidecar_path(&spec.path).exists());
+
+    let back = Table::load(&spec).unwrap();
+    let class = back.schema.find("Class").unwrap();
+    assert_eq!(class.dtype, DataType::Enum(vec!["Warrior".into(), "Archer".into()]));
+    assert!(class.rules.required);
+    assert_eq!(back.schema.find("Health").unwrap().dtype, DataType::Int); // 나머지는 추론 그대로
+}
+
+#[test]
+fn sidecar_is_written_only_when_inference_would_lose_something() {
+    let dir = scratch_dir("sidecar_on_save");
+    let specs = [info_spec(&copy_fixture(&dir, "character_info.csv"))];
+    let sidecar = schema_sidecar_path(&specs[0].path);
+    let mut ds = DataSets::load(&specs).unwrap();
+
+    // 값만 고치면 다시 열어도 같은 스키마로 추론되므로 데이터 파일만
+    ds.tables[0].rows.get_mut("1").unwrap().set("Health", "330".into());
+    ds.save_all(0).unwrap();
+    assert!(!sidecar.exists());
+    // 값이 바뀌어 추론이 달라지면(Int -> Text) 원래 dtype을 남긴다
+    ds.tables[0].rows.get_mut("1").unwrap().set("Health", "많음".into());
+    assert_eq!(ds.tables[0].render().unwrap().len(), 2);
+    ds.tables[0].rows.get_mut("1").unwrap().set("Health", "330".into());
+
+    let class = ds.tables[0].schema.columns.iter_mut().find(|c| c.key == "Class").unwrap();
+    class.rules.required = true;
+    ds.save_all(0).unwrap();
+    assert!(sidecar.exists());
+
+    // 한 번 생긴 사이드카는 규칙을 되돌려도 계속 맞춰 쓴다
+    let class = ds.tables[0].schema.columns.iter_mut().find(|c| c.key == "Class").unwrap();
+    class.rules.required = false;
+    ds.save_all(0).unwrap();
+    assert!(!fs::read_to_string(&sidecar).unwrap().contains("required"));
+}
+
+#[test]
+fn computed_columns_stay_out_of_data_file() {
+    let dir = scratch_dir("computed");
+    let specs = [info_spec(&copy_fixture(&dir, "character_info.csv"))];
+    let mut ds = DataSets::load(&specs).unwrap();
+    ds.tables[0].add_computed_column("Power", DataType::Float, "Health * Speed").unwrap();
+    entity_manager::computed::recompute(&mut ds);
+    assert_eq!(ds.tables[0].rows["3"].get("Power"), Some("625"));
+    ds.save_all(0).unwrap();
+
+    assert_eq!(fs::read(&specs[0].path).unwrap(), fs::read(fixture("character_info.csv")).unwrap());
+    let mut back = DataSets::load(&specs).unwrap();
+    entity_manager::computed::recompute(&mut back);
+    assert_eq!(back.tables[0].rows["1"].get("Power"), Some("480"));
+}
+
+#[test]
+fn save_all_backs_up_only_changed_tables() {
+    let dir = scratch_dir("backups");
+    let specs = [
+        info_spec(&copy_fixture(&dir, "character_info.csv")),
+        attack_spec(&copy_fixture(&dir, "character_attack_info.txt")),
+    ];
+    let mut ds = DataSets::load(&specs).unwrap();
+    ds.save_all(2).unwrap(); // 사이드카만 새로 생김
+    for i in 0..3 {
+        ds.tables[0].rows.get_mut("1").unwrap().set("Health", (400 + i).to_string());
+        ds.save_all(2).unwrap();
+    }
+
+    let info = list_backups(&specs[0].path);
+    assert_eq!(info.len(), 2); // 보관 개수만큼만
+    assert!(fs::read_to_string(&info[0].path).unwrap().contains("1,엘프 궁수,Archer,401,"));
+    assert!(list_backups(&specs[1].path).is_empty());
+    let leftovers: Vec<_> = fs::read_dir(&dir)
+        .unwrap()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().ends_with('~'))
+        .collect();
+    assert!(leftovers.is_empty(), "임시 파일 남음: {:?}", leftovers);
+}
+
+#[test]
+fn json_conversion_round_trips() {
+    let dir = scratch_dir("json");
+    let t = Table::load(&info_spec(&fixture("character_info.csv"))).unwrap();
+    let json_path = dir.join("info.json").to_string_lossy().to_string();
+    write_json(&t, &json_path).unwrap();
+
+    let text = fs::read_to_string(&json_path).unwrap();
+    assert!(text.contains("\"Health\": 500"));
+    assert!(text.contains("\"Playable\": true"));
+
+    let back = read_json(&TableSpec::new("info", "Info", &json_path, "")).unwrap();
+    assert_eq!(back.order, t.order);
+    assert_eq!(back.schema.key_column, "CharacterUnique");
+    let bytes = render_table(&back.schema, &back.rows, &back.order, &back.conflicts, false).unwrap();
+    assert_eq!(bytes, fs::read(fixture("character_info.csv")).unwrap());
+}
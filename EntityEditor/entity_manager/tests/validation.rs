@@ -0,0 +1,169 @@
+// 컬럼 규칙(required/min/max/regex/unique/allowed)과 dtype 검사
+
+mod common;
+
+use std::fs;
+
+use common::scratch_dir;
+use entity_manager::{validate, ColumnRef, ColumnRules, DataSets, DataType, Severity, TableSpec};
+
+use Severity::{Error, Warning};
+
+/// `Id,V` 파일(키 1, 2, 3...)의 V 컬럼에 dtype/규칙을 주고 V 컬럼 진단만 (심각도, 키, 메시지)로
+fn check(case: &str, dtype: DataType, rules: ColumnRules, values: &[&str]) -> Vec<(Severity, String, String)> {
+    let dir = scratch_dir(&format!("validation_{}", case));
+    let path = dir.join("t.csv");
+    let mut text = String::from("Id,V\n");
+    for (i, v) in values.iter().enumerate() {
+        text += &format!("{},{}\n", i + 1, v);
+    }
+    fs::write(&path, text).unwrap();
+    let mut ds = DataSets::load(&[TableSpec::new("t", "T", &path.to_string_lossy(), "Id")]).unwrap();
+    let col = ds.tables[0].schema.columns.iter_mut().find(|c| c.key == "V").unwrap();
+    col.dtype = dtype;
+    col.rules = rules;
+    validate(&ds)
+        .into_iter()
+        .filter(|d| d.column == "V")
+        .map(|d| (d.severity, d.key, d.message))
+        .collect()
+}
+
+type Expected<'a> = &'a [(Severity, &'a str, &'a str)];
+
+fn assert_cases(cases: &[(&str, DataType, ColumnRules, &[&str], Expected)]) {
+    for (case, dtype, rules, values, expected) in cases {
+        let found = check(case, dtype.clone(), rules.clone(), values);
+        let expected: Vec<(Severity, String, String)> =
+            expected.iter().map(|(s, k, m)| (*s, k.to_string(), m.to_string())).collect();
+        assert_eq!(found, expected, "{}", case);
+    }
+}
+
+#[test]
+fn rules_report_each_offending_row() {
+    let range = ColumnRules { min: Some(0.0), max: Some(10.0), ..Default::default() };
+    assert_cases(&[
+        ("no_rules", DataType::Text, ColumnRules::default(), &["a", "", "b"], &[]),
+        (
+            "min_max",
+            DataType::Int,
+            range.clone(),
+            &["-1", "0", "10", "11"],
+            &[(Error, "1", "-1 < 최솟값 0"), (Error, "4", "11 > 최댓값 10")],
+        ),
+        (
+            "min_max_not_number",
+            DataType::Text,
+            range,
+            &["5", "many"],
+            &[(Error, "2", "'many'은(는) 숫자가 아닙니다")],
+        ),
+        (
+            "min_only",
+            DataType::Float,
+            ColumnRules { min: Some(0.5), ..Default::default() },
+            &["0.4", "0.5", "99"],
+            &[(Error, "1", "0.4 < 최솟값 0.5")],
+        ),
+        (
+            "regex_full_match",
+            DataType::Text,
+            ColumnRules { regex: Some("[A-Z]\\d+".into()), ..Default::default() },
+            &["A1", "A1x", "b2", "Z99"],
+            &[(Error, "2", "'A1x'이(가) 형식과 맞지 않습니다"), (Error, "3", "'b2'이(가) 형식과 맞지 않습니다")],
+        ),
+        (
+            "regex_empty_pattern",
+            DataType::Text,
+            ColumnRules { regex: Some(String::new()), ..Default::default() },
+            &["anything"],
+            &[],
+        ),
+        (
+            "unique",
+            DataType::Text,
+            ColumnRules { unique: true, ..Default::default() },
+            &["a", "b", "a", "a"],
+            &[(Error, "3", "'a' 중복 (키 1와 같음)"), (Error, "4", "'a' 중복 (키 1와 같음)")],
+        ),
+        (
+            "allowed",
+            DataType::Text,
+            ColumnRules { allowed: vec!["x".into(), "y".into()], ..Default::default() },
+            &["x", "y", "z", "X"],
+            &[(Error, "3", "'z'은(는) 허용되지 않은 값입니다"), (Error, "4", "'X'은(는) 허용되지 않은 값입니다")],
+        ),
+    ]);
+}
+
+#[test]
+fn empty_is_only_an_error_when_required() {
+    // 빈 값은 다른 규칙/dtype 검사를 받지 않는다
+    let all = ColumnRules {
+        min: Some(1.0),
+        max: Some(2.0),
+        regex: Some("\\d".into()),
+        unique: true,
+        allowed: vec!["1".into()],
+        ..Default::default()
+    };
+    let required = ColumnRules { required: true, ..all.clone() };
+    assert_cases(&[
+        ("empty_optional", DataType::Int, all, &["", "", "1"], &[]),
+        (
+            "empty_required",
+            DataType::Int,
+            required,
+            &["", "1", ""],
+            &[(Error, "1", "값이 필요합니다"), (Error, "3", "값이 필요합니다")],
+        ),
+        ("required_filled", DataType::Text, ColumnRules { required: true, ..Default::default() }, &["a", "b"], &[]),
+    ]);
+}
+
+#[test]
+fn dtype_mismatches_are_warnings() {
+    let reference = DataType::Reference(ColumnRef { table: "t".into(), column: "Id".into() });
+    assert_cases(&[
+        ("int", DataType::Int, ColumnRules::default(), &["1", "-7", "1.5", "x"], &[
+            (Warning, "3", "'1.5'은(는) Int 값이 아닙니다"),
+            (Warning, "4", "'x'은(는) Int 값이 아닙니다"),
+        ]),
+        ("float", DataType::Float, ColumnRules::default(), &["1.5", "2", "-0.25"], &[]),
+        ("float_bad", DataType::Float, ColumnRules::default(), &["1e3", "abc"], &[
+            (Warning, "2", "'abc'은(는) Float 값이 아닙니다"),
+        ]),
+        ("bool", DataType::Bool, ColumnRules::default(), &["true", "No", "1", "maybe"], &[
+            (Warning, "4", "'maybe'은(는) Bool 값이 아닙니다"),
+        ]),
+        ("date", DataType::Date, ColumnRules::default(), &["2024-01-02", "2024/1/2", "2024-13-01", "soon"], &[
+            (Warning, "3", "'2024-13-01'은(는) Date 값이 아닙니다"),
+            (Warning, "4", "'soon'은(는) Date 값이 아닙니다"),
+        ]),
+        ("enum", DataType::Enum(vec!["Warrior".into(), "Archer".into()]), ColumnRules::default(), &["Archer", "archer"], &[
+            (Warning, "2", "'archer'은(는) Enum 값이 아닙니다"),
+        ]),
+        ("list", DataType::List(";".into()), ColumnRules::default(), &["a;b", "?"], &[]),
+        ("reference", reference, ColumnRules::default(), &["1", "2"], &[]),
+        // 경고와 규칙 오류가 같이 나면 오류가 먼저
+        ("mixed", DataType::Int, ColumnRules { max: Some(5.0), ..Default::default() }, &["9", "2.5"], &[
+            (Error, "1", "9 > 최댓값 5"),
+            (Warning, "2", "'2.5'은(는) Int 값이 아닙니다"),
+        ]),
+    ]);
+}
+
+#[test]
+fn invalid_regex_is_one_column_error() {
+    let found = check(
+        "regex_invalid",
+        DataType::Text,
+        ColumnRules { regex: Some("(".into()), ..Default::default() },
+        &["a", "b"],
+    );
+    assert_eq!(found.len(), 1);
+    let (severity, key, message) = &found[0];
+    assert_eq!((*severity, key.as_str()), (Error, ""));
+    assert!(message.starts_with("잘못된 정규식 '(': "), "{}", message);
+}
@@ -1,7 +0,0 @@
-pub mod entity;
-pub mod handler;
-pub mod raw_data;
-pub mod dyn_entity;
-pub mod app_state;
-pub mod storage;
-pub mod schema;
\ No newline at end of file
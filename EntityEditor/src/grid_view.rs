@@ -0,0 +1,188 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use eframe::egui;
+use egui::RichText;
+use egui_extras::{Column, TableBuilder};
+
+use entity_manager::app_state::Table;
+use entity_manager::dyn_entity::compare_keys;
+use entity_manager::history::CellEdit;
+use entity_manager::schema::DataType;
+use crate::{ui_cell_editor, ui_computed_cell, CellEnv, CHANGED_COLOR};
+
+const ROW_HEIGHT: f32 = 24.0;
+
+/// 테이블별 그리드 상태: 정렬 컬럼/방향 + 컬럼별 필터 문자열
+#[derive(Debug, Default)]
+pub struct GridState {
+    pub sort: Option<(String, bool)>, // (컬럼, 오름차순?)
+    pub filters: HashMap<String, String>,
+}
+
+/// 컬럼 필터 한 칸: "<", "<=", ">", ">=", "=" 로 시작하면 수치 비교, 아니면 부분 문자열(대소문자 무시)
+fn filter_matches(filter: &str, value: &str) -> bool {
+    let f = filter.trim();
+    if f.is_empty() {
+        return true;
+    }
+    for op in ["<=", ">=", "<", ">", "="] {
+        if let Some(rhs) = f.strip_prefix(op) {
+            let (Ok(a), Ok(b)) = (value.trim().parse::<f64>(), rhs.trim().parse::<f64>()) else {
+                return op == "=" && value == rhs.trim();
+            };
+            return match op {
+                "<=" => a <= b,
+                ">=" => a >= b,
+                "<" => a < b,
+                ">" => a > b,
+                _ => a == b,
+            };
+        }
+    }
+    value.to_lowercase().contains(&f.to_lowercase())
+}
+
+/// dtype에 맞는 정렬: 수치형은 숫자로, 그 외는 키 정렬 규칙(숫자 우선)으로
+fn compare_cells(dtype: &DataType, a: &str, b: &str) -> Ordering {
+    match dtype {
+        DataType::Int | DataType::Float => {
+            let na = a.trim().parse::<f64>().unwrap_or(f64::NEG_INFINITY);
+            let nb = b.trim().parse::<f64>().unwrap_or(f64::NEG_INFINITY);
+            na.partial_cmp(&nb).unwrap_or(Ordering::Equal)
+        }
+        _ => compare_keys(a, b),
+    }
+}
+
+/// 필터/정렬을 적용한 행 키 목록
+fn visible_keys(table: &Table, state: &GridState) -> Vec<String> {
+    let mut keys: Vec<String> = table
+        .rows
+        .iter()
+        .filter(|(_, row)| {
+            state
+                .filters
+                .iter()
+                .all(|(col, f)| filter_matches(f, row.get(col).unwrap_or("")))
+        })
+        .map(|(k, _)| k.clone())
+        .collect();
+
+    match &state.sort {
+        Some((col, asc)) => {
+            let dtype = table.schema.find(col).map(|c| c.dtype.clone()).unwrap_or(DataType::Text);
+            keys.sort_by(|a, b| {
+                let va = table.rows[a].get(col).unwrap_or("");
+                let vb = table.rows[b].get(col).unwrap_or("");
+                let ord = compare_cells(&dtype, va, vb).then_with(|| compare_keys(a, b));
+                if *asc { ord } else { ord.reverse() }
+            });
+        }
+        None => keys.sort_by(|a, b| compare_keys(a, b)),
+    }
+    keys
+}
+
+/// 테이블 전체를 스프레드시트처럼 보여주는 그리드 (보이는 행만 그림).
+/// 셀 편집은 폼과 같은 `ui_cell_editor`를 쓰고 `env.edits`로 undo 기록에 합류한다.
+/// 키 셀을 누르면 그 엔티티를 선택한다 (자식 테이블은 행이 속한 엔티티).
+/// 복합 키 컬럼은 행 키가 바뀌므로 여기서는 고치지 않는다 (폼의 하위 표에서).
+pub fn ui_table_grid(
+    ui: &mut egui::Ui,
+    env: &mut CellEnv,
+    table: &mut Table,
+    state: &mut GridState,
+    selected: &mut Option<String>,
+) {
+    let keys = visible_keys(table, state);
+    ui.label(format!("{} / {} 행", keys.len(), table.rows.len()));
+
+    let Table { spec, schema, rows, .. } = table;
+    let mut toggle_sort: Option<String> = None;
+
+    // 테이블마다 컬럼 폭 상태를 따로 두기 위해 id 분리
+    ui.push_id(("grid", spec.name.as_str()), |ui| {
+        TableBuilder::new(ui)
+            .striped(true)
+            .resizable(true)
+            .min_scrolled_height(0.0)
+            .max_scroll_height(f32::INFINITY)
+            .columns(Column::auto().at_least(80.0).clip(true), schema.columns.len())
+            .header(48.0, |mut header| {
+                for col in &schema.columns {
+                    header.col(|ui| {
+                        ui.vertical(|ui| {
+                            let arrow = match &state.sort {
+                                Some((c, true)) if *c == col.key => " ▲",
+                                Some((c, false)) if *c == col.key => " ▼",
+                                _ => "",
+                            };
+                            let title = RichText::new(format!("{}{}", col.label, arrow)).strong();
+                            if ui.add(egui::Label::new(title).sense(egui::Sense::click())).clicked() {
+                                toggle_sort = Some(col.key.clone());
+                            }
+                            let f = state.filters.entry(col.key.clone()).or_default();
+                            ui.add(egui::TextEdit::singleline(f).hint_text("필터").desired_width(f32::INFINITY));
+                        });
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(ROW_HEIGHT, keys.len(), |mut row| {
+                    let key = &keys[row.index()];
+                    let Some(r) = rows.get_mut(key) else { return };
+                    let is_selected = selected.as_deref() == Some(schema.parent_of(key, r));
+                    row.set_selected(is_selected);
+                    for col in &schema.columns {
+                        row.col(|ui| {
+                            if schema.is_key_column(&col.key) {
+                                if ui.selectable_label(is_selected, schema.cell(key, r, &col.key)).clicked() {
+                                    *selected = Some(schema.parent_of(key, r).to_string());
+                                }
+                                return;
+                            }
+                            if let Some(formula) = &col.formula {
+                                ui_computed_cell(ui, formula, r.get(&col.key).unwrap_or(""));
+                                return;
+                            }
+                            let id = egui::Id::new(("grid_cell", &spec.name, key, &col.key));
+                            let current = r.get(&col.key).unwrap_or("").to_string();
+                            // 저장 후 바뀐 셀은 테두리로 표시
+                            let changed = env.pending.old_value(&spec.name, key, &col.key).map(|s| s.to_string());
+                            let stroke = match changed {
+                                Some(_) => egui::Stroke::new(1.0, CHANGED_COLOR),
+                                None => egui::Stroke::NONE,
+                            };
+                            let edited = egui::Frame::none()
+                                .stroke(stroke)
+                                .show(ui, |ui| ui_cell_editor(ui, env, id, &col.dtype, &current));
+                            if let Some(old) = changed {
+                                edited.response.on_hover_text(format!("저장된 값: '{}'", old));
+                            }
+                            if let Some(v) = edited.inner {
+                                env.edits.push(CellEdit {
+                                    table: spec.name.clone(),
+                                    key: key.clone(),
+                                    column: col.key.clone(),
+                                    old: current,
+                                    new: v.clone(),
+                                });
+                                r.set(&col.key, v);
+                            }
+                        });
+                    }
+                });
+            });
+    });
+
+    // 헤더 클릭: 오름차순 -> 내림차순 -> 정렬 해제
+    if let Some(col) = toggle_sort {
+        state.sort = match state.sort.take() {
+            Some((c, true)) if c == col => Some((c, false)),
+            Some((c, false)) if c == col => None,
+            _ => Some((col, true)),
+        };
+    }
+    state.filters.retain(|_, f| !f.is_empty());
+}
@@ -1,14 +1,272 @@
-mod entity_manager;
+mod grid_view;
 
 use eframe::{egui, App, CreationContext};
 use egui::{FontData, FontDefinitions, FontFamily, ScrollArea, RichText};
+use std::collections::{BTreeSet, HashMap};
 
-use entity_manager::schema::{TableSchema, DataType};
-use entity_manager::dyn_entity::DynRow;
-use entity_manager::app_state::DataSets;
+use entity_manager::schema::{ColumnRules, TableSchema, DataType};
+use entity_manager::dyn_entity::{compare_keys, display_key, DynRow};
+use entity_manager::app_state::{DataSets, Table, TableSpec};
+use entity_manager::storage;
+use entity_manager::dialect::TextEncoding;
+use entity_manager::value;
+use entity_manager::project::{project_dir, NamedFilter, ProjectFile, RecentProjects, StatusKeyMode, STATUS_TABLE};
+use entity_manager::export::{self, ExportFormat, ExportSettings, ExportShape};
+use entity_manager::query;
+use entity_manager::bulk;
+use entity_manager::computed;
+use entity_manager::changes::{self, Pending};
+use entity_manager::merge::{merge_table, MergeResult};
+use entity_manager::watch::{FileWatcher, POLL_INTERVAL};
+use entity_manager::backup::{self, DEFAULT_KEEP};
+use entity_manager::references::RefIndex;
+use entity_manager::history::{CellEdit, Command, History, TableEdit};
+use entity_manager::key_conflicts::{self, ConflictKind, MergePrefer};
+use entity_manager::children;
+use grid_view::{ui_table_grid, GridState};
+use entity_manager::validation::{encoding_warnings, has_errors, validate, Diagnostic, Severity};
+
+// 참조 선택기에 한 번에 보여줄 최대 후보 수
+const MAX_PICKER_ITEMS: usize = 200;
+
+// 로드/저장 이후 바뀐 셀 표시 색
+const CHANGED_COLOR: egui::Color32 = egui::Color32::from_rgb(90, 170, 255);
+
+// ===== 셀 편집에 필요한 주변 정보 =====
+struct CellEnv<'a> {
+    refs: &'a RefIndex,
+    jump_to: Option<String>, // 참조 "이동" 클릭 시 선택할 키
+    focus: Option<&'a (String, String)>, // 강조할 셀 (table, column)
+    scroll_to_focus: bool,
+    edits: Vec<CellEdit>, // 이번 프레임의 편집 (undo 기록용)
+    compact: bool,        // 그리드 셀: 한 줄 위젯만 사용
+    pending: &'a Pending, // 디스크 대비 바뀐 셀 (강조용)
+}
+
+// ===== 셀 편집 위젯: dtype별 컨트롤, 바뀐 경우에만 새 문자열 반환 =====
+fn ui_cell_editor(
+    ui: &mut egui::Ui,
+    env: &mut CellEnv,
+    id: egui::Id,
+    dtype: &DataType,
+    current: &str,
+) -> Option<String> {
+    match dtype {
+        DataType::Int => {
+            let mut v: i64 = current.parse().unwrap_or(0);
+            let resp = ui.add(egui::DragValue::new(&mut v).speed(1));
+            resp.changed().then(|| v.to_string())
+        }
+        DataType::Float => {
+            let mut v: f64 = current.parse().unwrap_or(0.0);
+            let resp = ui.add(egui::DragValue::new(&mut v).speed(0.1));
+            resp.changed().then(|| format!("{}", v))
+        }
+        DataType::Text => {
+            let mut v = current.to_string();
+            ui.text_edit_singleline(&mut v).changed().then_some(v)
+        }
+        DataType::Bool => {
+            let mut b = value::parse_bool(current).unwrap_or(false);
+            ui.checkbox(&mut b, "")
+                .changed()
+                .then(|| value::format_bool(b, current))
+        }
+        DataType::Enum(allowed) => {
+            let mut out = None;
+            let shown = if current.is_empty() { "(비어 있음)" } else { current };
+            let text = if allowed.iter().any(|a| a == current) {
+                RichText::new(shown)
+            } else {
+                RichText::new(shown).color(ui.visuals().warn_fg_color) // 허용 목록 밖의 값
+            };
+            egui::ComboBox::from_id_source(id)
+                .selected_text(text)
+                .show_ui(ui, |ui| {
+                    for a in allowed {
+                        if ui.selectable_label(a == current, a).clicked() && a != current {
+                            out = Some(a.clone());
+                        }
+                    }
+                });
+            out
+        }
+        DataType::Date => match value::Date::parse(current) {
+            Some(mut d) => {
+                let before = d;
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut d.year).speed(1));
+                    ui.add(egui::DragValue::new(&mut d.month).speed(1).clamp_range(1..=12));
+                    ui.add(egui::DragValue::new(&mut d.day).speed(1).clamp_range(1..=31));
+                });
+                d.clamp_day();
+                (d != before).then(|| d.format())
+            }
+            None => {
+                // 비어 있거나 형식이 다르면 텍스트로 입력받는다
+                let mut v = current.to_string();
+                let resp = ui.add(egui::TextEdit::singleline(&mut v).hint_text("YYYY-MM-DD"));
+                resp.changed().then_some(v)
+            }
+        },
+        DataType::List(_) if env.compact => {
+            let mut v = current.to_string();
+            ui.text_edit_singleline(&mut v).changed().then_some(v)
+        }
+        DataType::List(sep) => {
+            let mut items = value::split_list(current, sep);
+            let mut changed = false;
+            let mut remove = None;
+            ui.vertical(|ui| {
+                for (i, item) in items.iter_mut().enumerate() {
+                    ui.horizontal(|ui| {
+                        changed |= ui.text_edit_singleline(item).changed();
+                        if ui.small_button("✖").clicked() {
+                            remove = Some(i);
+                        }
+                    });
+                }
+                if ui.small_button("➕").clicked() {
+                    items.push(String::new());
+                    changed = true;
+                }
+            });
+            if let Some(i) = remove {
+                items.remove(i);
+                changed = true;
+            }
+            changed.then(|| value::join_list(&items, sep))
+        }
+        DataType::Reference(r) => {
+            let mut out = None;
+            let target = env.refs.values(r);
+            let resolved = env.refs.resolve(r, current);
+            ui.horizontal(|ui| {
+                let shown = if current.is_empty() { "(없음)" } else { current };
+                let text = if current.is_empty() || resolved.is_some() {
+                    RichText::new(shown)
+                } else {
+                    RichText::new(shown).color(ui.visuals().error_fg_color) // 끊어진 참조
+                };
+                egui::ComboBox::from_id_source(id)
+                    .selected_text(text)
+                    .show_ui(ui, |ui| {
+                        // 검색어는 팝업이 닫혀도 유지되도록 egui 메모리에 보관
+                        let filter_id = id.with("filter");
+                        let mut filter: String = ui.data_mut(|d| d.get_temp(filter_id).unwrap_or_default());
+                        ui.add(egui::TextEdit::singleline(&mut filter).hint_text("🔍 검색"));
+                        ui.data_mut(|d| d.insert_temp(filter_id, filter.clone()));
+
+                        let Some(values) = target else {
+                            ui.label("대상 테이블/컬럼 없음");
+                            return;
+                        };
+                        if ui.selectable_label(current.is_empty(), "(없음)").clicked() && !current.is_empty() {
+                            out = Some(String::new());
+                        }
+                        let needle = filter.to_lowercase();
+                        for v in values
+                            .keys()
+                            .filter(|v| v.to_lowercase().contains(&needle))
+                            .take(MAX_PICKER_ITEMS)
+                        {
+                            if ui.selectable_label(v == current, v).clicked() && v != current {
+                                out = Some(v.clone());
+                            }
+                        }
+                    });
+                if let Some(k) = resolved {
+                    if ui.small_button("↗").on_hover_text("참조 대상으로 이동").clicked() {
+                        env.jump_to = Some(k.to_string());
+                    }
+                }
+                let column = if r.column.is_empty() { "<key>" } else { &r.column };
+                ui.weak(format!("→ {}.{}", r.table, column));
+            });
+            out
+        }
+    }
+}
+
+// ===== 스키마 편집: 타입 파라미터(Enum 값, List 구분자, Reference 대상) =====
+fn ui_dtype_params(ui: &mut egui::Ui, id: egui::Id, dtype: &mut DataType) {
+    match dtype {
+        DataType::Enum(values) => ui_comma_list(ui, id.with("enum"), values, "값1, 값2, ..."),
+        DataType::List(sep) => {
+            ui.horizontal(|ui| {
+                ui.label("구분자:");
+                ui.add(egui::TextEdit::singleline(sep).desired_width(24.0));
+            });
+        }
+        DataType::Reference(r) => {
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut r.table).hint_text("table").desired_width(60.0));
+                ui.label(".");
+                ui.add(egui::TextEdit::singleline(&mut r.column).hint_text("column (비우면 키)").desired_width(100.0));
+            });
+        }
+        _ => {}
+    }
+}
+
+/// 쉼표 구분 목록 입력. 입력 중인 글(끝의 쉼표 포함)은 포커스가 있는 동안 egui 메모리에 두고,
+/// 포커스를 잃으면(Enter 포함) 목록으로 다시 표시한다. 목록은 입력할 때마다 반영 (메뉴가 그냥 닫혀도 유지)
+fn ui_comma_list(ui: &mut egui::Ui, id: egui::Id, values: &mut Vec<String>, hint: &str) {
+    let mut text = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_else(|| values.join(", "));
+    let resp = ui.add(egui::TextEdit::singleline(&mut text).hint_text(hint));
+    if resp.changed() {
+        *values = text
+            .split(',')
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        ui.data_mut(|d| d.insert_temp(id, text));
+    }
+    if !resp.has_focus() {
+        ui.data_mut(|d| d.remove::<String>(id));
+    }
+}
+
+// ===== 스키마 편집: 컬럼 검증 규칙 =====
+fn ui_column_rules(ui: &mut egui::Ui, id: egui::Id, rules: &mut ColumnRules) {
+    ui.checkbox(&mut rules.required, "필수 (빈 값 금지)");
+    ui.checkbox(&mut rules.unique, "테이블 내 중복 금지");
+    ui_optional_f64(ui, "최솟값", &mut rules.min);
+    ui_optional_f64(ui, "최댓값", &mut rules.max);
+
+    ui.horizontal(|ui| {
+        ui.label("정규식:");
+        let mut pat = rules.regex.clone().unwrap_or_default();
+        if ui.text_edit_singleline(&mut pat).changed() {
+            rules.regex = (!pat.is_empty()).then_some(pat);
+        }
+    });
+
+    ui.label("허용 값 (쉼표 구분):");
+    ui_comma_list(ui, id.with("allowed"), &mut rules.allowed, "");
+}
+
+fn ui_optional_f64(ui: &mut egui::Ui, label: &str, value: &mut Option<f64>) {
+    ui.horizontal(|ui| {
+        let mut on = value.is_some();
+        if ui.checkbox(&mut on, label).changed() {
+            *value = on.then_some(0.0);
+        }
+        if let Some(v) = value {
+            ui.add(egui::DragValue::new(v).speed(1));
+        }
+    });
+}
 
 // ===== 동적 폼: 라벨/컨트롤 2열 그리드 =====
-fn ui_entity_form(ui: &mut egui::Ui, title: &str, schema: &TableSchema, row: &mut DynRow) {
+fn ui_entity_form(
+    ui: &mut egui::Ui,
+    env: &mut CellEnv,
+    table: &str,
+    title: &str,
+    schema: &TableSchema,
+    row: &mut DynRow,
+) {
     use egui::Grid;
     ui.group(|ui| {
         ui.label(RichText::new(title).heading());
@@ -19,71 +277,180 @@ fn ui_entity_form(ui: &mut egui::Ui, title: &str, schema: &TableSchema, row: &mu
             .striped(true)
             .show(ui, |ui| {
                 for col in &schema.columns {
-                    let header = &col.label; // CSV 헤더 그대로(표시/키)
-                    ui.label(header);
-                    match col.dtype {
-                        DataType::Int => {
-                            let mut v: i64 = row.get(header).unwrap_or("0").parse().unwrap_or(0);
-                            let resp = ui.add(egui::DragValue::new(&mut v).speed(1));
-                            if resp.changed() {
-                                row.set(header, v.to_string());
-                            }
+                    let header = &col.key; // CSV 헤더(셀 키)
+                    let focused = env.focus.is_some_and(|(t, c)| t == table && c == header);
+                    let old = env.pending.old_value(table, &row.key, header);
+                    if focused {
+                        let lbl = ui.label(RichText::new(&col.label).strong().color(ui.visuals().warn_fg_color));
+                        if env.scroll_to_focus {
+                            lbl.scroll_to_me(Some(egui::Align::Center));
+                            env.scroll_to_focus = false;
                         }
-                        DataType::Float => {
-                            let mut v: f64 = row.get(header).unwrap_or("0").parse().unwrap_or(0.0);
-                            let resp = ui.add(egui::DragValue::new(&mut v).speed(0.1));
+                    } else if let Some(old) = old {
+                        ui.label(RichText::new(format!("● {}", col.label)).color(CHANGED_COLOR))
+                            .on_hover_text(format!("저장된 값: '{}'", old));
+                    } else {
+                        ui.label(&col.label);
+                    }
+                    if let Some(formula) = &col.formula {
+                        ui_computed_cell(ui, formula, row.get(header).unwrap_or(""));
+                        ui.end_row();
+                        continue;
+                    }
+                    let id = egui::Id::new(("cell", title, header));
+                    let current = row.get(header).unwrap_or("").to_string();
+                    if let Some(v) = ui_cell_editor(ui, env, id, &col.dtype, &current) {
+                        env.edits.push(CellEdit {
+                            table: table.to_string(),
+                            key: row.key.clone(),
+                            column: header.clone(),
+                            old: current,
+                            new: v.clone(),
+                        });
+                        row.set(header, v);
+                    }
+                    ui.end_row();
+                }
+            });
+    });
+}
+
+// ===== 자식 테이블 하위 표에서 요청한 행 변경 (표를 그린 뒤 적용, 스냅샷으로 undo) =====
+enum ChildOp {
+    Add,
+    Remove(String),
+    Move { key: String, up: bool },
+    SetKey { key: String, column: String, value: String },
+}
+
+// ===== 자식 테이블: 엔티티에 딸린 행들을 하위 표로 (부모 컬럼은 숨김) =====
+// 일반 셀은 폼과 같이 바로 편집, 키 셀은 행 키가 바뀌므로 포커스를 잃을 때 `ChildOp::SetKey`로
+fn ui_child_table(ui: &mut egui::Ui, env: &mut CellEnv, t: &mut Table, parent: &str) -> Option<ChildOp> {
+    use egui::Grid;
+    let keys = t.child_keys(parent);
+    let Table { spec, schema, rows, .. } = t;
+    let columns: Vec<_> = schema.columns.iter().filter(|c| schema.parent_column.as_ref() != Some(&c.key)).collect();
+    let mut op = None;
+    let compact = std::mem::replace(&mut env.compact, true);
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(&spec.title).heading());
+            ui.label(format!("{}행", keys.len()));
+            if ui.button("➕ 행 추가").clicked() {
+                op = Some(ChildOp::Add);
+            }
+        });
+        ui.add_space(4.0);
+        if keys.is_empty() {
+            return;
+        }
+        ScrollArea::horizontal().id_source(("child_scroll", &spec.name)).show(ui, |ui| {
+            Grid::new(("child_grid", &spec.name)).striped(true).spacing([8.0, 4.0]).show(ui, |ui| {
+                for col in &columns {
+                    let text = if schema.is_key_column(&col.key) { format!("🔑 {}", col.label) } else { col.label.clone() };
+                    ui.label(RichText::new(text).strong());
+                }
+                ui.end_row();
+                for (i, key) in keys.iter().enumerate() {
+                    let Some(row) = rows.get_mut(key) else { continue };
+                    for col in &columns {
+                        let current = row.get(&col.key).unwrap_or("").to_string();
+                        if let Some(formula) = &col.formula {
+                            ui_computed_cell(ui, formula, &current);
+                            continue;
+                        }
+                        if schema.is_key_column(&col.key) {
+                            let id = egui::Id::new(("child_key", &spec.name, key, &col.key));
+                            let mut text = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_else(|| current.clone());
+                            let resp = ui.add(egui::TextEdit::singleline(&mut text).desired_width(60.0));
                             if resp.changed() {
-                                row.set(header, format!("{}", v));
+                                ui.data_mut(|d| d.insert_temp(id, text.clone()));
                             }
-                        }
-                        DataType::Text => {
-                            let mut v = row.get(header).unwrap_or("").to_string();
-                            if ui.text_edit_singleline(&mut v).changed() {
-                                row.set(header, v);
+                            if resp.lost_focus() {
+                                ui.data_mut(|d| d.remove::<String>(id));
+                                if text != current {
+                                    op = Some(ChildOp::SetKey { key: key.clone(), column: col.key.clone(), value: text });
+                                }
                             }
+                            continue;
+                        }
+                        let id = egui::Id::new(("child_cell", &spec.name, key, &col.key));
+                        let changed = env.pending.old_value(&spec.name, key, &col.key).map(|s| s.to_string());
+                        let stroke = match changed {
+                            Some(_) => egui::Stroke::new(1.0, CHANGED_COLOR),
+                            None => egui::Stroke::NONE,
+                        };
+                        let edited = egui::Frame::none()
+                            .stroke(stroke)
+                            .show(ui, |ui| ui_cell_editor(ui, env, id, &col.dtype, &current));
+                        if let Some(old) = changed {
+                            edited.response.on_hover_text(format!("저장된 값: '{}'", old));
+                        }
+                        if let Some(v) = edited.inner {
+                            env.edits.push(CellEdit {
+                                table: spec.name.clone(),
+                                key: key.clone(),
+                                column: col.key.clone(),
+                                old: current,
+                                new: v.clone(),
+                            });
+                            row.set(&col.key, v);
                         }
                     }
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(i > 0, egui::Button::new("⬆")).clicked() {
+                            op = Some(ChildOp::Move { key: key.clone(), up: true });
+                        }
+                        if ui.add_enabled(i + 1 < keys.len(), egui::Button::new("⬇")).clicked() {
+                            op = Some(ChildOp::Move { key: key.clone(), up: false });
+                        }
+                        if ui.button("🗑").on_hover_text("행 삭제").clicked() {
+                            op = Some(ChildOp::Remove(key.clone()));
+                        }
+                    });
                     ui.end_row();
                 }
             });
+        });
     });
+    env.compact = compact;
+    op
 }
 
-// ===== 상태 키 컬럼 모드 =====
-#[derive(Debug, Clone)]
-enum StatusKeyMode {
-    Auto,               // 자동 추론(권장)
-    Unique,             // "Unique"
-    CharacterUnique,    // "CharacterUnique"
-    Custom(String),     // 임의 헤더명
+/// 계산 컬럼 셀: 읽기 전용 값 (실패하면 오류 색), 마우스를 올리면 식
+fn ui_computed_cell(ui: &mut egui::Ui, formula: &str, value: &str) {
+    let text = if value.starts_with(computed::ERROR_PREFIX) {
+        RichText::new(value).color(ui.visuals().error_fg_color)
+    } else {
+        RichText::new(format!("ƒ {}", value)).italics()
+    };
+    ui.label(text).on_hover_text(format!("= {}", formula));
 }
-impl StatusKeyMode {
-    fn as_hint(&self) -> &str {
-        match self {
-            StatusKeyMode::Auto => "CharacterUnique", // 기본 힌트(없으면 첫 컬럼 사용)
-            StatusKeyMode::Unique => "Unique",
-            StatusKeyMode::CharacterUnique => "CharacterUnique",
-            StatusKeyMode::Custom(s) => s.as_str(),
-        }
-    }
-    fn label(&self) -> String {
-        match self {
-            StatusKeyMode::Auto => "Auto (alias-like)".to_string(),
-            StatusKeyMode::Unique => "Unique".to_string(),
-            StatusKeyMode::CharacterUnique => "CharacterUnique".to_string(),
-            StatusKeyMode::Custom(s) => format!("Custom: {}", s),
-        }
-    }
+
+// ===== 스키마 편집 창에서 요청한 컬럼 구조 변경 (창을 그린 뒤 한 번에 적용) =====
+enum ColumnOp {
+    Add { name: String, dtype: DataType, default: String, formula: String },
+    Rename { old: String, new: String },
+    Move { from: usize, to: usize },
+    Remove { name: String },
+}
+
+// ===== 중앙 뷰 모드 =====
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ViewMode {
+    Form, // 선택한 엔티티 하나를 테이블별 폼으로
+    Grid, // 테이블 하나의 모든 행을 그리드로
 }
 
 // ===== 앱 상태(동적 스키마 기반) =====
 struct EditorApp {
-    // 파일 경로
-    info_path: String,
-    status_path: String,
-    attack_path: String,
-// New Data file GuideLine Step 8:
-    skill_path: String,
+    // 프로젝트 파일
+    project_path: Option<String>,
+    project_path_input: String,
+    recent: RecentProjects,
+
+    // 테이블 목록(파일 경로/키 힌트)
+    tables: Vec<TableSpec>,
 
     // 상태 키 컬럼 지정
     status_key_mode: StatusKeyMode,
@@ -94,7 +461,73 @@ struct EditorApp {
 
     // 선택된 키(문자열 키)
     selected_key: Option<String>,
+    new_key_input: String, // 새 엔티티/복제에 쓸 키 (비우면 자동)
+
+    // 엔티티 목록 필터 (query 식)
+    filter_input: String,
+    filters: Vec<NamedFilter>, // 프로젝트에 저장되는 이름 붙은 필터
+    filter_name_input: String,
+    multi_keys: BTreeSet<String>, // Ctrl+클릭으로 고른 키 (일괄 편집 대상)
+
+    // 일괄 편집
+    show_bulk_edit: bool,
+    bulk_table: usize,
+    bulk_column: String,
+    bulk_use_selection: bool, // true: multi_keys, false: bulk_filter 식
+    bulk_filter: String,
+    bulk_expr: String,
+
+    // 검증 결과
+    diagnostics: Vec<Diagnostic>,
+    show_diagnostics: bool,
+    confirm_save: bool, // 오류가 있어도 저장할지 묻는 중
+    focus_cell: Option<(String, String)>, // 진단 클릭으로 이동한 셀 (table, column)
+    scroll_to_focus: bool,
+
+    // 중앙 뷰
+    view_mode: ViewMode,
+    grid_table: usize,
+    grid_states: HashMap<String, GridState>, // 테이블 이름 -> 정렬/필터
+
+    // 편집 이력 (undo/redo)
+    history: History,
+    show_history: bool,
+    computed_rev: Option<u64>, // 계산 컬럼을 마지막으로 갱신한 시점의 history.revision()
+    refs: RefIndex,            // 폼/그리드의 참조 선택기용 색인
+    refs_rev: Option<u64>,     // `refs`를 마지막으로 만든 시점의 history.revision()
+
+    // 저장 대기 변경 (로드/저장 시점 스냅샷과 비교)
+    baseline: Option<DataSets>,
+    pending: Pending,
+    pending_rev: Option<u64>,
+    show_pending: bool,
+
+    // 외부 변경 감지 (다른 사람/스크립트가 파일을 고친 경우)
+    watcher: FileWatcher,
+    external: Vec<String>, // 디스크에서 바뀐 테이블 (처리 대기)
+    merge: Option<(Table, MergeResult)>, // 충돌 해결 중: (디스크 테이블, 병합 결과)
+
+    // 저장 시 남기는 백업
+    backup_keep: usize, // 테이블마다 보관할 개수 (프로젝트에 저장)
+    show_backups: bool,
+    backup_table: usize,
+
+    // 키 중복/빈 키 해결 창
+    show_key_conflicts: bool,
+    conflict_keys: HashMap<(String, usize), String>, // (테이블, 줄 번호) -> 입력한 새 키
 
+    // 게임 런타임용 내보내기 (프로젝트에 저장)
+    export: ExportSettings,
+    show_export: bool,
+
+    // 스키마 편집 창
+    show_schema_editor: bool,
+    schema_table: usize,
+    rename_col: Option<(usize, String)>, // 이름 변경 중인 컬럼 (인덱스, 입력값)
+    new_col_name: String,
+    new_col_dtype: DataType,
+    new_col_default: String,
+    new_col_formula: String, // 비우지 않으면 계산 컬럼으로 추가
     // 메시지
     last_message: String,
 }
@@ -102,18 +535,79 @@ struct EditorApp {
 impl Default for EditorApp {
     fn default() -> Self {
         Self {
-            // 프로젝트 경로 구조에 맞게 조정하세요
-            info_path: "src/data/character_info.csv".to_string(),
-            status_path: "src/data/character_status_info.csv".to_string(),
-            attack_path: "src/data/character_attack_info.txt".to_string(),
-            // New Data file GuideLine Step 9:
-            skill_path: "src/data/character_skill_info.txt".to_string(),
+            project_path: None,
+            project_path_input: String::new(),
+            recent: RecentProjects::default(),
+
+            // 프로젝트 경로 구조에 맞게 조정하세요 (또는 프로젝트 파일을 여세요)
+            tables: vec![
+                TableSpec::new("info", "Info", "src/data/character_info.csv", "CharacterUnique"),
+                TableSpec::new(STATUS_TABLE, "Status", "src/data/character_status_info.csv", "CharacterUnique"),
+                TableSpec::new("attack", "Attack", "src/data/character_attack_info.txt", "CharacterUnique"),
+                TableSpec::new("skill", "Skill", "src/data/character_skill_info.txt", "CharacterUnique"),
+            ],
 
             status_key_mode: StatusKeyMode::Auto,
             custom_key_input: String::new(),
 
             ds: None,
             selected_key: None,
+            new_key_input: String::new(),
+
+            filter_input: String::new(),
+            filters: Vec::new(),
+            filter_name_input: String::new(),
+            multi_keys: BTreeSet::new(),
+
+            show_bulk_edit: false,
+            bulk_table: 0,
+            bulk_column: String::new(),
+            bulk_use_selection: false,
+            bulk_filter: String::new(),
+            bulk_expr: String::new(),
+
+            diagnostics: Vec::new(),
+            show_diagnostics: false,
+            confirm_save: false,
+            focus_cell: None,
+            scroll_to_focus: false,
+
+            view_mode: ViewMode::Form,
+            grid_table: 0,
+            grid_states: HashMap::new(),
+
+            history: History::default(),
+            show_history: false,
+            computed_rev: None,
+            refs: RefIndex::default(),
+            refs_rev: None,
+
+            baseline: None,
+            pending: Pending::default(),
+            pending_rev: None,
+            show_pending: false,
+
+            watcher: FileWatcher::default(),
+            external: Vec::new(),
+            merge: None,
+
+            backup_keep: DEFAULT_KEEP,
+            show_backups: false,
+            backup_table: 0,
+
+            show_key_conflicts: false,
+            conflict_keys: HashMap::new(),
+
+            export: ExportSettings::default(),
+            show_export: false,
+
+            show_schema_editor: false,
+            schema_table: 0,
+            rename_col: None,
+            new_col_name: String::new(),
+            new_col_dtype: DataType::Text,
+            new_col_default: String::new(),
+            new_col_formula: String::new(),
 
             last_message: String::new(),
         }
@@ -140,66 +634,141 @@ impl EditorApp {
             .insert(0, "my_korean_font".to_string());
         cc.egui_ctx.set_fonts(fonts);
 
-        Self::default()
+        Self {
+            recent: RecentProjects::load(),
+            ..Self::default()
+        }
     }
 
-    fn gather_sorted_unique_keys(ds: &DataSets) -> Vec<String> {
-        use std::collections::BTreeSet;
+    fn open_project(&mut self, path: &str) {
+        match ProjectFile::load(path) {
+            Ok(project) => {
+                self.tables = project.tables;
+                if let StatusKeyMode::Custom(s) = &project.status_key_mode {
+                    self.custom_key_input = s.clone();
+                }
+                self.status_key_mode = project.status_key_mode;
+                self.filters = project.filters;
+                self.backup_keep = project.backups;
+                self.export = project.export;
+                self.set_project_path(path);
+                self.try_load();
+            }
+            Err(e) => self.last_message = format!("❌ 프로젝트 열기 실패: {e}"),
+        }
+    }
 
-        // 1) 중복 제거 (빈 키는 제외)
-        let mut uniq: BTreeSet<String> = BTreeSet::new();
-        for k in 
-        ds.info.keys()
-        .chain(ds.status.keys())
-        .chain(ds.attack.keys())
-        .chain(ds.skill.keys()) // New Data file GuideLine Step 15:
-         {
-            if !k.is_empty() {
-                uniq.insert(k.clone());
+    fn save_project(&mut self, path: &str) {
+        let project = ProjectFile {
+            tables: self.tables.clone(),
+            status_key_mode: self.status_key_mode.clone(),
+            filters: self.filters.clone(),
+            backups: self.backup_keep,
+            export: self.export.clone(),
+        };
+        match project.save(path) {
+            Ok(_) => {
+                self.set_project_path(path);
+                self.last_message = format!("💾 프로젝트 저장: {path}");
             }
+            Err(e) => self.last_message = format!("❌ 프로젝트 저장 실패: {e}"),
         }
+    }
 
-        // 2) 정렬: 숫자 가능하면 숫자로, 아니면 문자열로
-        let mut keys: Vec<String> = uniq.into_iter().collect();
-        keys.sort_by(|a, b| {
-            let pa = a.parse::<u64>();
-            let pb = b.parse::<u64>();
-            match (pa, pb) {
-                (Ok(na), Ok(nb)) => na.cmp(&nb),
-                _ => a.cmp(b),
+    fn set_project_path(&mut self, path: &str) {
+        self.project_path = Some(path.to_string());
+        self.project_path_input = path.to_string();
+        self.recent.push(path);
+        if let Err(e) = self.recent.save() {
+            self.last_message = format!("⚠️ 최근 프로젝트 목록 저장 실패: {e}");
+        }
+    }
+
+    fn ui_project(&mut self, ui: &mut egui::Ui) {
+        ui.heading("🗂 프로젝트");
+        ui.label(self.project_path.as_deref().unwrap_or("(저장되지 않은 프로젝트)"));
+        ui.text_edit_singleline(&mut self.project_path_input);
+        ui.horizontal(|ui| {
+            if ui.button("📂 열기").clicked() {
+                let path = self.project_path_input.clone();
+                self.open_project(&path);
+            }
+            if ui.button("💾 저장").clicked() {
+                // 저장 위치가 없으면 입력란 경로로 저장
+                let path = self
+                    .project_path
+                    .clone()
+                    .unwrap_or_else(|| self.project_path_input.clone());
+                self.save_project(&path);
+            }
+            if ui.button("다른 이름으로 저장").clicked() {
+                let path = self.project_path_input.clone();
+                self.save_project(&path);
             }
         });
+
+        let mut open = None;
+        egui::ComboBox::from_id_source("recent_projects")
+            .selected_text("최근 프로젝트")
+            .show_ui(ui, |ui| {
+                if self.recent.paths.is_empty() {
+                    ui.label("(없음)");
+                }
+                for p in &self.recent.paths {
+                    if ui.selectable_label(false, p).clicked() {
+                        open = Some(p.clone());
+                    }
+                }
+            });
+        if let Some(p) = open {
+            self.open_project(&p);
+        }
+    }
+
+    fn gather_sorted_unique_keys(ds: &DataSets) -> Vec<String> {
+        // 1) 중복 제거 (빈 키는 제외)
+        let mut keys: Vec<String> = ds.keys().into_iter().filter(|k| !k.is_empty()).collect();
+
+        // 2) 정렬: 숫자 가능하면 숫자로, 아니면 문자열로
+        keys.sort_by(|a, b| compare_keys(a, b));
         keys
     }
 
     fn try_load(&mut self) {
-        // 동적 로드: DataSets::load
-        let hint = self.status_key_mode.as_hint().to_string();
-        match DataSets::load(
-            &self.info_path,
-            &self.status_path,
-            &self.attack_path,
-            // New Data file GuideLine Step 10:            
-            &self.skill_path,
-            &hint, // status key 힌트
-        ) {
-            Ok(ds) => {
+        // 동적 로드: DataSets::load (status key 힌트 적용)
+        let specs = self.status_key_mode.apply(&self.tables);
+        match DataSets::load(&specs) {
+            Ok(mut ds) => {
                 // 로드 성공
-                // 기본 선택 키: info의 첫 번째 키 or status/attack 중 하나
+                // 기본 선택 키: 앞쪽 테이블부터 첫 번째 키
                 let first_key = ds
-                    .info
-                    .keys()
-                    .next()
-                    .cloned()
-                    .or_else(|| ds.status.keys().next().cloned())
-                    .or_else(|| ds.attack.keys().next().cloned())
-                    
-                    // New Data file GuideLine Step 17:    
-                    .or_else(|| ds.skill.keys().next().cloned());
+                    .tables
+                    .iter()
+                    .find_map(|t| t.rows.iter().next().map(|(k, r)| t.schema.parent_of(k, r).to_string()));
 
                 self.selected_key = first_key;
+                computed::recompute(&mut ds);
+                self.diagnostics = validate(&ds);
+                self.history.clear();
+                self.computed_rev = Some(self.history.revision());
+                self.refs_rev = None;
+                self.baseline = Some(ds.clone());
+                self.pending_rev = None;
+                self.watcher.reset(&ds);
+                self.external.clear();
+                self.merge = None;
+                let conflicts: usize = ds.tables.iter().map(|t| t.conflicts.len()).sum();
                 self.ds = Some(ds);
-                self.last_message = "✅ 데이터 로드 성공".into();
+                self.conflict_keys.clear();
+                if conflicts > 0 {
+                    self.last_message = format!("⚠️ 로드 완료, 키가 비었거나 겹치는 행 {}개 — 해결하세요", conflicts);
+                    self.show_key_conflicts = true;
+                } else if has_errors(&self.diagnostics) {
+                    self.last_message = format!("⚠️ 로드 완료, 검증 문제 {}건", self.diagnostics.len());
+                    self.show_diagnostics = true;
+                } else {
+                    self.last_message = "✅ 데이터 로드 성공".into();
+                }
             }
             Err(e) => {
                 self.last_message = format!("❌ 데이터 로드 실패: {e}");
@@ -207,17 +776,44 @@ impl EditorApp {
         }
     }
 
+    /// 검증 오류가 있으면 결과 창을 띄우고 확인을 받은 뒤 저장
     fn try_save(&mut self) {
         if let Some(ds) = &self.ds {
-            let res = ds.save_all(
-                &self.info_path, 
-                &self.status_path, 
-                &self.attack_path,
-                // New Data file GuideLine Step 11:
-                &self.skill_path
-                );
-            match res {
-                Ok(_) => self.last_message = "💾 저장 완료".into(),
+            self.diagnostics = validate(ds);
+            if has_errors(&self.diagnostics) {
+                self.show_diagnostics = true;
+                self.confirm_save = true;
+                self.last_message = "⚠️ 검증 오류가 있습니다 — 확인 후 저장하세요".into();
+                return;
+            }
+            // 경고지만 저장하면 글자가 바뀌므로 확인을 받는다
+            if !encoding_warnings(&ds.tables).is_empty() {
+                self.show_diagnostics = true;
+                self.confirm_save = true;
+                self.last_message = "⚠️ 파일 인코딩으로 저장할 수 없는 글자가 있습니다 — 확인 후 저장하세요".into();
+                return;
+            }
+        }
+        self.save_now();
+    }
+
+    fn save_now(&mut self) {
+        self.confirm_save = false;
+        if let Some(ds) = &self.ds {
+            // 로드 이후 다른 곳에서 파일이 바뀌었으면 덮어쓰지 않고 먼저 처리하게 한다
+            let changed = self.watcher.poll(ds, true);
+            if !changed.is_empty() {
+                self.last_message = format!("⚠️ 디스크에서 바뀐 파일이 있어 저장하지 않았습니다: {}", changed.join(", "));
+                self.note_external(changed);
+                return;
+            }
+            match ds.save_all(self.backup_keep) {
+                Ok(_) => {
+                    self.baseline = Some(ds.clone());
+                    self.pending_rev = None;
+                    self.watcher.reset(ds);
+                    self.last_message = "💾 저장 완료".into();
+                }
                 Err(e) => self.last_message = format!("❌ 저장 실패: {e}"),
             }
         } else {
@@ -226,24 +822,49 @@ impl EditorApp {
     }
 
     fn ui_left_panel(&mut self, ui: &mut egui::Ui) {
-        ui.heading("📁 데이터 파일");
-        ui.label("character_info.csv");
-        ui.text_edit_singleline(&mut self.info_path);
-        ui.add_space(4.0);
+        self.ui_project(ui);
 
-        ui.label("character_status_info.csv");
-        ui.text_edit_singleline(&mut self.status_path);
+        ui.separator();
+        ui.heading("📁 데이터 파일");
+        for spec in &mut self.tables {
+            ui.label(&spec.title);
+            ui.text_edit_singleline(&mut spec.path);
+            ui.horizontal(|ui| {
+                ui.label("키:");
+                ui.add(egui::TextEdit::singleline(&mut spec.key_hint).desired_width(110.0))
+                    .on_hover_text("복합 키는 '+'로 잇기 (예: CharacterUnique+SkillSlot)");
+                ui.label("구분자:");
+                let mut delim = if spec.delimiter == '\t' { "\\t".to_string() } else { spec.delimiter.to_string() };
+                if ui.add(egui::TextEdit::singleline(&mut delim).desired_width(24.0)).changed() {
+                    spec.delimiter = match delim.as_str() {
+                        "\\t" => '\t',
+                        _ => delim.chars().rev().find(char::is_ascii).unwrap_or(','), // 구분자는 ASCII만
+                    };
+                }
+                egui::ComboBox::from_id_source(("encoding", &spec.name))
+                    .width(70.0)
+                    .selected_text(spec.encoding.map_or("자동", |e| e.label()))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut spec.encoding, None, "자동");
+                        for e in TextEncoding::ALL {
+                            ui.selectable_value(&mut spec.encoding, Some(e), e.label());
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label("부모:");
+                let mut parent = spec.parent.clone().unwrap_or_default();
+                let resp = ui
+                    .add(egui::TextEdit::singleline(&mut parent).hint_text("없음").desired_width(110.0))
+                    .on_hover_text("엔티티 키가 든 컬럼. 지정하면 엔티티마다 여러 행 (예: 캐릭터별 스킬)");
+                if resp.changed() {
+                    spec.parent = Some(parent.trim().to_string()).filter(|p| !p.is_empty());
+                }
+            });
+            ui.add_space(4.0);
+        }
         ui.add_space(4.0);
 
-        ui.label("character_attack_info.txt");
-        ui.text_edit_singleline(&mut self.attack_path);
-        ui.add_space(8.0);
-
-        // New Data file GuideLine Step 16:
-        ui.label("character_skill_info.txt");
-        ui.text_edit_singleline(&mut self.skill_path);
-        ui.add_space(8.0);
-
         ui.separator();
         ui.heading("🔑 상태 키컬럼");
         egui::ComboBox::from_id_source("status_key_mode")
@@ -304,6 +925,39 @@ impl EditorApp {
         if ui.button("💾 저장").clicked() {
             self.try_save();
         }
+        if ui.button("🧬 스키마 편집").clicked() {
+            self.show_schema_editor = true;
+        }
+        if ui.button("🧮 일괄 편집").clicked() {
+            if self.bulk_filter.is_empty() {
+                self.bulk_filter = self.filter_input.clone();
+            }
+            self.bulk_use_selection = !self.multi_keys.is_empty();
+            self.show_bulk_edit = true;
+        }
+        let pending = format!("📝 변경 대기 ({})", self.pending.changes.len());
+        if ui.button(pending).clicked() {
+            self.show_pending = true;
+        }
+        if ui.button("📤 내보내기").clicked() {
+            self.show_export = true;
+        }
+        if ui.button("🗄 백업 복원").clicked() {
+            self.show_backups = true;
+        }
+        let conflicts: usize = self.ds.as_ref().map_or(0, |ds| ds.tables.iter().map(|t| t.conflicts.len()).sum());
+        if ui.button(format!("🔑 키 문제 ({})", conflicts)).clicked() {
+            self.show_key_conflicts = true;
+        }
+        if ui.button("🕘 편집 이력").clicked() {
+            self.show_history = true;
+        }
+        if ui.button("🩺 검증").clicked() {
+            if let Some(ds) = &self.ds {
+                self.diagnostics = validate(ds);
+            }
+            self.show_diagnostics = true;
+        }
 
         ui.add_space(8.0);
         if !self.last_message.is_empty() {
@@ -312,26 +966,58 @@ impl EditorApp {
 
         ui.separator();
         ui.heading("📦 엔티티 목록");
+        self.ui_entity_actions(ui);
+        self.ui_filter(ui);
 
-        // 좌측 리스트: info/status/attack 모든 키를 합쳐 표시
+        // 좌측 리스트: 모든 테이블의 키를 합쳐 표시
         egui::ScrollArea::vertical()
         .max_height(320.0)
         .show(ui, |ui| {
             if let Some(ds) = &self.ds {
                 // 🔧 중복 제거된 키 목록
-                let keys = Self::gather_sorted_unique_keys(ds);
+                let all_keys = Self::gather_sorted_unique_keys(ds);
 
-                // 선택 유지(선택 키가 더 이상 존재하지 않으면 해제)
+                // 선택 유지(선택 키가 더 이상 존재하지 않으면 해제). 필터로 가려진 키는 유지.
                 if let Some(sel) = self.selected_key.clone() {
-                    if !keys.iter().any(|k| k == &sel) {
+                    if !all_keys.iter().any(|k| k == &sel) {
                         self.selected_key = None;
                     }
                 }
 
+                self.multi_keys.retain(|k| all_keys.contains(k));
+
+                let keys = match Self::parse_filter(ds, &self.filter_input) {
+                    Some(Ok(expr)) => query::filter_keys(ds, &expr, &all_keys).keys,
+                    _ => all_keys,
+                };
+                if !self.multi_keys.is_empty() {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{}개 선택 (Ctrl+클릭)", self.multi_keys.len()));
+                        if ui.small_button("선택 해제").clicked() {
+                            self.multi_keys.clear();
+                        }
+                    });
+                }
+
                 for k in keys {
-                    let selected = self.selected_key.as_ref().map(|s| s == &k).unwrap_or(false);
-                    if ui.selectable_label(selected, format!("Key = {}", k)).clicked() {
-                        self.selected_key = Some(k);
+                    let selected = self.selected_key.as_ref() == Some(&k) || self.multi_keys.contains(&k);
+                    let text = if self.pending.key_changed(&k) {
+                        RichText::new(format!("● Key = {}", k)).color(CHANGED_COLOR)
+                    } else {
+                        RichText::new(format!("Key = {}", k))
+                    };
+                    let resp = ui.selectable_label(selected, text);
+                    if resp.clicked() {
+                        // Ctrl+클릭: 일괄 편집용 다중 선택 토글
+                        if ui.input(|i| i.modifiers.command) {
+                            if !self.multi_keys.remove(&k) {
+                                self.multi_keys.insert(k);
+                            }
+                        } else {
+                            self.multi_keys.clear();
+                            self.selected_key = Some(k);
+                            self.focus_cell = None;
+                        }
                     }
                 }
             } else {
@@ -340,73 +1026,1281 @@ impl EditorApp {
         });
     }
 
-    // ===== 우측 상세뷰(동적) =====
-    fn ui_entity_detail(&mut self, ui: &mut egui::Ui) {
-        if self.ds.is_none() {
-            ui.heading("📝 Main View");
-            ui.label("좌측에서 파일 경로 설정 후 '로드'를 클릭하세요.");
+    /// 필터 식 파싱 + 스키마 검사. 빈 식이면 None (필터 없음).
+    fn parse_filter(ds: &DataSets, src: &str) -> Option<Result<query::Expr, query::ParseError>> {
+        if src.trim().is_empty() {
+            return None;
+        }
+        Some(query::parse(src).and_then(|e| e.check(ds, None).map(|_| e)))
+    }
+
+    // ===== 엔티티 목록 필터 (식 입력 + 일치 수 + 저장된 필터) =====
+    fn ui_filter(&mut self, ui: &mut egui::Ui) {
+        ui.add(
+            egui::TextEdit::singleline(&mut self.filter_input)
+                .hint_text("필터: status.Health > 500 && info.Name ~ \"orc\"")
+                .desired_width(f32::INFINITY)
+                .font(egui::TextStyle::Monospace),
+        );
+
+        if let Some(ds) = &self.ds {
+            let all_keys = Self::gather_sorted_unique_keys(ds);
+            match Self::parse_filter(ds, &self.filter_input) {
+                None => {}
+                Some(Err(e)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, RichText::new(e.render(&self.filter_input)).monospace());
+                }
+                Some(Ok(expr)) => {
+                    let result = query::filter_keys(ds, &expr, &all_keys);
+                    ui.label(format!("일치 {} / {}", result.keys.len(), all_keys.len()));
+                    if let Some(err) = &result.first_error {
+                        ui.colored_label(ui.visuals().warn_fg_color, format!("⚠️ 평가 오류 {}행 (예: {})", result.errors, err));
+                    }
+                }
+            }
+        }
+
+        ui.horizontal(|ui| {
+            let mut pick = None;
+            egui::ComboBox::from_id_source("saved_filters")
+                .selected_text("저장된 필터")
+                .show_ui(ui, |ui| {
+                    if self.filters.is_empty() {
+                        ui.label("(없음)");
+                    }
+                    for f in &self.filters {
+                        if ui.selectable_label(false, &f.name).on_hover_text(&f.query).clicked() {
+                            pick = Some(f.clone());
+                        }
+                    }
+                });
+            if let Some(f) = pick {
+                self.filter_input = f.query;
+                self.filter_name_input = f.name;
+            }
+            ui.add(egui::TextEdit::singleline(&mut self.filter_name_input).hint_text("이름").desired_width(80.0));
+            let name = self.filter_name_input.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty() && !self.filter_input.trim().is_empty(), egui::Button::new("⭐"))
+                .on_hover_text("현재 식을 이 이름으로 저장 (프로젝트 저장 시 기록)")
+                .clicked()
+            {
+                let query = self.filter_input.clone();
+                match self.filters.iter_mut().find(|f| f.name == name) {
+                    Some(f) => f.query = query,
+                    None => self.filters.push(NamedFilter { name, query }),
+                }
+            } else if ui
+                .add_enabled(self.filters.iter().any(|f| f.name == name), egui::Button::new("🗑"))
+                .on_hover_text("이 이름의 저장된 필터 삭제")
+                .clicked()
+            {
+                self.filters.retain(|f| f.name != name);
+            }
+            if ui.button("✖").on_hover_text("필터 지우기").clicked() {
+                self.filter_input.clear();
+            }
+        });
+    }
+
+    // ===== 엔티티 추가/복제/삭제 (모든 테이블에 걸쳐, undo 가능) =====
+    fn ui_entity_actions(&mut self, ui: &mut egui::Ui) {
+        let Some(ds) = self.ds.as_mut() else { return };
+        let auto_key = ds.next_free_key();
+        ui.horizontal(|ui| {
+            ui.label("새 키:");
+            ui.add(egui::TextEdit::singleline(&mut self.new_key_input).hint_text(&auto_key).desired_width(100.0));
+        });
+        let key = if self.new_key_input.trim().is_empty() {
+            auto_key
+        } else {
+            self.new_key_input.trim().to_string()
+        };
+        let exists = ds.keys().contains(&key);
+        let selected = self.selected_key.clone();
+
+        let mut cmd = None;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!exists, egui::Button::new("➕ 새 엔티티")).clicked() {
+                cmd = Some(Command::rows(format!("새 엔티티 {}", key), ds.create_entity(&key)));
+            }
+            if let Some(src) = &selected {
+                if ui.add_enabled(!exists, egui::Button::new("📄 복제")).clicked() {
+                    cmd = Some(Command::rows(format!("복제 {} → {}", src, key), ds.duplicate_entity(src, &key)));
+                }
+                if ui.button("🗑 삭제").clicked() {
+                    cmd = Some(Command::rows(format!("삭제 {}", src), ds.delete_entity(src)));
+                }
+            }
+        });
+        if exists {
+            ui.weak(format!("키 {}은(는) 이미 있습니다.", key));
+        }
+
+        if let Some(cmd) = cmd {
+            self.last_message = format!("🟢 {} (저장은 따로)", cmd.label);
+            self.selected_key = cmd.first_key().map(|k| k.to_string());
+            self.history.push(cmd);
+            self.new_key_input.clear();
+        }
+    }
+
+    fn undo(&mut self) {
+        let Some(ds) = self.ds.as_mut() else { return };
+        if let Some(cmd) = self.history.undo(ds) {
+            self.last_message = format!("↶ 되돌림: {}", cmd.label);
+            self.selected_key = cmd.first_key().map(|k| k.to_string());
+        }
+        self.sync_specs();
+    }
+
+    fn redo(&mut self) {
+        let Some(ds) = self.ds.as_mut() else { return };
+        if let Some(cmd) = self.history.redo(ds) {
+            self.last_message = format!("↷ 다시 실행: {}", cmd.label);
+            self.selected_key = cmd.first_key().map(|k| k.to_string());
+        }
+        self.sync_specs();
+    }
+
+    /// 키 컬럼 이름 변경(과 그 undo/redo)으로 바뀐 키 힌트/부모 컬럼을 좌측 패널/프로젝트에 맞춘다
+    fn sync_specs(&mut self) {
+        let Some(ds) = self.ds.as_ref() else { return };
+        for spec in &mut self.tables {
+            if let Some(t) = ds.table(&spec.name) {
+                spec.key_hint = t.spec.key_hint.clone();
+                spec.parent = t.spec.parent.clone();
+            }
+        }
+    }
+
+    /// Ctrl+Z / Ctrl+Y (Ctrl+Shift+Z)
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        use egui::{Key, KeyboardShortcut, Modifiers};
+        let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+        let redo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Y);
+        let redo_alt = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+
+        // Shift 조합을 먼저 소비해야 Ctrl+Z로 잡히지 않는다
+        if ctx.input_mut(|i| i.consume_shortcut(&redo_alt) || i.consume_shortcut(&redo)) {
+            self.redo();
+        } else if ctx.input_mut(|i| i.consume_shortcut(&undo)) {
+            self.undo();
+        }
+    }
+
+    // ===== 편집 이력: 항목을 누르면 그 시점까지 되돌리기/다시 실행 =====
+    fn ui_history(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_history;
+        let mut undo_steps = 0;
+        let mut redo_steps = 0;
+        egui::Window::new("🕘 편집 이력")
+            .open(&mut open)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(self.history.can_undo(), egui::Button::new("↶ 되돌리기")).clicked() {
+                        undo_steps = 1;
+                    }
+                    if ui.add_enabled(self.history.can_redo(), egui::Button::new("↷ 다시 실행")).clicked() {
+                        redo_steps = 1;
+                    }
+                });
+                ui.separator();
+                ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    // 다시 실행 가능한 항목: 흐리게, 가장 먼 것부터
+                    let redo = self.history.redo_stack();
+                    for (i, cmd) in redo.iter().enumerate() {
+                        if ui.add(egui::Label::new(RichText::new(&cmd.label).weak()).sense(egui::Sense::click())).clicked() {
+                            redo_steps = redo.len() - i;
+                        }
+                    }
+                    // 되돌리기 가능한 항목: 최신이 위
+                    let undo = self.history.undo_stack();
+                    for (i, cmd) in undo.iter().enumerate().rev() {
+                        let current = i + 1 == undo.len();
+                        if ui.selectable_label(current, &cmd.label).clicked() {
+                            undo_steps = undo.len() - 1 - i;
+                        }
+                    }
+                    if undo.is_empty() && redo.is_empty() {
+                        ui.label("편집 이력이 없습니다.");
+                    }
+                });
+            });
+        self.show_history = open;
+        for _ in 0..undo_steps {
+            self.undo();
+        }
+        for _ in 0..redo_steps {
+            self.redo();
+        }
+    }
+
+    /// 마지막 비교 이후 편집/undo가 있었으면 디스크 기준 스냅샷과 다시 비교
+    fn refresh_pending(&mut self) {
+        let (Some(ds), Some(base)) = (&self.ds, &self.baseline) else { return };
+        let rev = self.history.revision();
+        if self.pending_rev == Some(rev) {
             return;
         }
-        let ds = self.ds.as_mut().unwrap();
+        self.pending_rev = Some(rev);
+        self.pending = Pending::diff(base, ds);
+    }
 
-        if let Some(selected_key) = self.selected_key.clone() {
-            ui.heading(format!("🔧 엔티티 편집: {}", &selected_key));
+    // ===== 저장 대기 변경: 하나씩 되돌리기 (되돌리기도 undo 가능) =====
+    fn ui_pending(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_pending;
+        let mut revert = None;
+        let mut goto = None;
+        egui::Window::new("📝 변경 대기")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                if self.pending.is_empty() {
+                    ui.label("디스크와 다른 점이 없습니다.");
+                    return;
+                }
+                ui.label(format!("{}건 — 저장하면 파일에 기록됩니다.", self.pending.changes.len()));
+                ui.separator();
+                ScrollArea::vertical().max_height(400.0).show_rows(ui, 20.0, self.pending.changes.len(), |ui, range| {
+                    for (i, c) in self.pending.changes.iter().enumerate().skip(range.start).take(range.len()) {
+                        ui.horizontal(|ui| {
+                            if ui.small_button("↩").on_hover_text("이 변경 되돌리기").clicked() {
+                                revert = Some(i);
+                            }
+                            let resp = ui.add(egui::Label::new(c.to_string()).sense(egui::Sense::click()));
+                            if resp.clicked() && !c.key.is_empty() {
+                                goto = Some(c.clone());
+                            }
+                        });
+                    }
+                });
+            });
+        self.show_pending = open;
+
+        if let Some(c) = goto {
+            self.selected_key = self.ds.as_ref().map(|ds| ds.entity_key(&c.table, &c.key).to_string());
+            self.focus_cell = (!c.column.is_empty()).then_some((c.table, c.column));
+            self.scroll_to_focus = true;
+            self.view_mode = ViewMode::Form;
+        }
+        let Some(i) = revert else { return };
+        let (Some(ds), Some(base)) = (self.ds.as_mut(), &self.baseline) else { return };
+        let change = self.pending.changes[i].clone();
+        match changes::revert(base, ds, &change) {
+            Ok(cmd) => {
+                self.last_message = format!("↩ {}", cmd.label);
+                self.history.push(cmd);
+            }
+            Err(e) => self.last_message = format!("❌ 되돌리기 실패: {e}"),
+        }
+    }
+
+    fn note_external(&mut self, tables: Vec<String>) {
+        for t in tables {
+            if !self.external.contains(&t) {
+                self.external.push(t);
+            }
+        }
+    }
+
+    /// 주기적으로 파일을 확인하고, 바뀐 게 없어도 다음 확인을 위해 다시 그리도록 예약
+    fn poll_external(&mut self, ctx: &egui::Context) {
+        let Some(ds) = &self.ds else { return };
+        let changed = self.watcher.poll(ds, false);
+        self.note_external(changed);
+        ctx.request_repaint_after(POLL_INTERVAL);
+    }
+
+    /// 디스크에서 바뀐 테이블을 메모리의 내 상태 대신 넣는다 (undo 가능). 새 기준은 디스크 내용.
+    fn replace_with(&mut self, disk: Table, merged: Table, label: String) {
+        let name = disk.spec.name.clone();
+        let Some(t) = self.ds.as_mut().and_then(|ds| ds.table_mut(&name)) else { return };
+        let before = t.clone();
+        *t = merged;
+        let after = t.clone();
+        self.history.push(Command::tables(label.clone(), vec![TableEdit { before, after }]));
+        self.watcher.accept(&name, &disk.spec.path);
+        if let Some(b) = self.baseline.as_mut().and_then(|b| b.table_mut(&name)) {
+            *b = disk;
+        }
+        self.pending_rev = None;
+        self.external.retain(|t| *t != name);
+        self.last_message = format!("🟢 {}", label);
+    }
+
+    /// 디스크 파일을 다시 읽는다. 키 힌트/구분자는 지금 테이블 설정 그대로.
+    fn load_disk_table(&mut self, name: &str) -> Option<Table> {
+        let spec = self.ds.as_ref()?.table(name)?.spec.clone();
+        match Table::load(&spec) {
+            Ok(t) => Some(t),
+            Err(e) => {
+                self.last_message = format!("❌ {} 다시 읽기 실패: {e}", spec.path);
+                None
+            }
+        }
+    }
+
+    // ===== 외부 변경: 다시 로드 / 3-way 병합 / 무시 =====
+    fn ui_external(&mut self, ctx: &egui::Context) {
+        if self.external.is_empty() && self.merge.is_none() {
+            return;
+        }
+        enum Action {
+            Reload(String),
+            Merge(String),
+            Ignore(String),
+            ApplyMerge,
+            CancelMerge,
+        }
+        let mut action = None;
+        egui::Window::new("⚠️ 외부 변경 감지").default_width(560.0).show(ctx, |ui| {
+            if let Some((_, result)) = &mut self.merge {
+                let name = &result.merged.spec.name;
+                ui.label(format!(
+                    "{}: 디스크 변경 {}건은 자동으로 합쳤습니다. 충돌 {}건 — 남길 값을 고르세요.",
+                    name,
+                    result.from_disk,
+                    result.conflicts.len()
+                ));
+                ui.horizontal(|ui| {
+                    if ui.button("모두 내 값").clicked() {
+                        result.conflicts.iter_mut().for_each(|c| c.take_disk = false);
+                    }
+                    if ui.button("모두 디스크 값").clicked() {
+                        result.conflicts.iter_mut().for_each(|c| c.take_disk = true);
+                    }
+                });
+                ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    egui::Grid::new("merge_conflicts").striped(true).show(ui, |ui| {
+                        ui.strong("키");
+                        ui.strong("컬럼");
+                        ui.strong("원래");
+                        ui.strong("디스크");
+                        ui.strong("내 값");
+                        ui.end_row();
+                        let shown = |v: &Option<String>| v.clone().unwrap_or_else(|| "(없음)".to_string());
+                        for c in &mut result.conflicts {
+                            ui.label(&c.key);
+                            ui.label(if c.column.is_empty() { "(행)" } else { &c.column });
+                            ui.weak(shown(&c.base));
+                            ui.radio_value(&mut c.take_disk, true, shown(&c.disk));
+                            ui.radio_value(&mut c.take_disk, false, shown(&c.mine));
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("✅ 병합 적용").clicked() {
+                        action = Some(Action::ApplyMerge);
+                    }
+                    if ui.button("취소").clicked() {
+                        action = Some(Action::CancelMerge);
+                    }
+                });
+                return;
+            }
+
+            ui.label("불러온 뒤 다른 곳에서 바뀐 파일입니다. 이대로 저장하면 그 변경을 덮어씁니다.");
             ui.separator();
+            for name in &self.external {
+                ui.horizontal(|ui| {
+                    ui.strong(name);
+                    if ui.button("🔄 다시 로드").on_hover_text("이 테이블의 내 변경을 버리고 디스크 내용으로").clicked() {
+                        action = Some(Action::Reload(name.clone()));
+                    }
+                    if ui.button("🔀 병합").on_hover_text("로드 당시 / 디스크 / 내 변경을 셀 단위로 합치기").clicked() {
+                        action = Some(Action::Merge(name.clone()));
+                    }
+                    if ui.button("무시").on_hover_text("경고만 닫기 (저장 시 디스크 변경을 덮어씀)").clicked() {
+                        action = Some(Action::Ignore(name.clone()));
+                    }
+                });
+            }
+        });
 
-            // 편집 버퍼(복사본) 생성
-            let mut info = ds.info.get(&selected_key).cloned();
-            let mut status = ds.status.get(&selected_key).cloned();
-            let mut attack = ds.attack.get(&selected_key).cloned();
-            // New Data file GuideLine Step 12:
-            let mut skill = ds.skill.get(&selected_key).cloned();
+        match action {
+            Some(Action::Reload(name)) => {
+                if let Some(disk) = self.load_disk_table(&name) {
+                    let label = format!("디스크에서 다시 로드: {}", name);
+                    self.replace_with(disk.clone(), disk, label);
+                }
+            }
+            Some(Action::Merge(name)) => {
+                let Some(disk) = self.load_disk_table(&name) else { return };
+                let (Some(ds), Some(base)) = (&self.ds, &self.baseline) else { return };
+                let (Some(mine), Some(base)) = (ds.table(&name), base.table(&name)) else { return };
+                let result = merge_table(base, &disk, mine);
+                if result.conflicts.is_empty() {
+                    let label = format!("디스크 변경 병합: {} ({}건)", name, result.from_disk);
+                    self.replace_with(disk, result.merged, label);
+                } else {
+                    self.merge = Some((disk, result));
+                }
+            }
+            Some(Action::Ignore(name)) => {
+                if let Some(t) = self.ds.as_ref().and_then(|ds| ds.table(&name)) {
+                    self.watcher.accept(&name, &t.spec.path);
+                }
+                self.external.retain(|t| *t != name);
+            }
+            Some(Action::ApplyMerge) => {
+                if let Some((disk, result)) = self.merge.take() {
+                    let label = format!(
+                        "디스크 변경 병합: {} ({}건, 충돌 {}건)",
+                        disk.spec.name,
+                        result.from_disk,
+                        result.conflicts.len()
+                    );
+                    self.replace_with(disk, result.resolve(), label);
+                }
+            }
+            Some(Action::CancelMerge) => self.merge = None,
+            None => {}
+        }
+    }
 
-            ScrollArea::vertical()
-            .auto_shrink([false, false])
-            .show(ui, |ui| {
-                // 블록을 쪼개서 빌림 충돌(여러 &mut 동시 대출) 피하기
-                {
-                    if let Some(r) = ds.info.get_mut(&selected_key) {
-                        ui_entity_form(ui, "Info", &ds.info_schema, r);
-                        ui.add_space(8.0);
+    /// 내보내기 상대 경로의 기준: 프로젝트 폴더, 없으면 첫 테이블 폴더
+    fn export_base_dir(&self) -> std::path::PathBuf {
+        match &self.project_path {
+            Some(p) => project_dir(p),
+            None => self
+                .tables
+                .first()
+                .and_then(|t| std::path::Path::new(&t.path).parent().map(|p| p.to_path_buf()))
+                .unwrap_or_default(),
+        }
+    }
+
+    // ===== 내보내기: 게임 런타임용 JSON/RON/MessagePack/바이너리 =====
+    fn ui_export(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_export;
+        let mut run = false;
+        let dir = export::resolve_dir(&self.export, &self.export_base_dir());
+        egui::Window::new("📤 내보내기")
+            .open(&mut open)
+            .default_width(380.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("폴더:");
+                    ui.add(egui::TextEdit::singleline(&mut self.export.dir).hint_text("export"));
+                });
+                ui.weak(dir.display().to_string());
+                ui.horizontal(|ui| {
+                    for f in ExportFormat::ALL {
+                        let mut on = self.export.formats.contains(&f);
+                        if ui.checkbox(&mut on, f.name()).changed() {
+                            if on {
+                                self.export.formats.push(f);
+                            } else {
+                                self.export.formats.retain(|x| *x != f);
+                            }
+                        }
                     }
+                });
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.export.shape, ExportShape::Tables, "테이블별 파일");
+                    ui.radio_value(&mut self.export.shape, ExportShape::Entities, "키로 합친 엔티티");
+                });
+                ui.weak("값은 스키마 타입대로 기록됩니다 (계산 컬럼 포함). 설정은 프로젝트에 저장됩니다.");
+                ui.separator();
+                run = ui.add_enabled(self.ds.is_some(), egui::Button::new("📤 내보내기")).clicked();
+            });
+        self.show_export = open;
+
+        let (true, Some(ds)) = (run, &self.ds) else { return };
+        // 형식 순서를 고정해 같은 설정이면 같은 파일 목록
+        self.export.formats.sort_by_key(|f| ExportFormat::ALL.iter().position(|x| x == f));
+        self.last_message = match export::export(ds, &self.export, &dir) {
+            Ok(files) => format!("📤 {}개 파일 내보냄: {}", files.len(), dir.display()),
+            Err(e) => format!("❌ 내보내기 실패: {e}"),
+        };
+    }
+
+    // ===== 백업 복원: 저장 때마다 남긴 이전 파일로 되돌리기 =====
+    fn ui_backups(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_backups;
+        let mut restore = None;
+        egui::Window::new("🗄 백업 복원")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("테이블마다 보관:");
+                    ui.add(egui::DragValue::new(&mut self.backup_keep).clamp_range(0..=100));
+                    ui.weak("개 (0 = 백업 안 함, 프로젝트에 저장)");
+                });
+                let Some(ds) = &self.ds else {
+                    ui.label("먼저 데이터를 로드하세요.");
+                    return;
+                };
+                self.backup_table = self.backup_table.min(ds.tables.len().saturating_sub(1));
+                let Some(t) = ds.tables.get(self.backup_table) else { return };
+                egui::ComboBox::from_id_source("backup_table")
+                    .selected_text(&t.spec.title)
+                    .show_ui(ui, |ui| {
+                        for (i, t) in ds.tables.iter().enumerate() {
+                            ui.selectable_value(&mut self.backup_table, i, &t.spec.title);
+                        }
+                    });
+                ui.weak(format!("{}", backup::backup_dir(&t.spec.path).display()));
+                ui.separator();
+
+                let entries = backup::list_backups(&t.spec.path);
+                if entries.is_empty() {
+                    ui.label("백업이 없습니다. 저장하면 이전 파일이 백업으로 남습니다.");
+                    return;
                 }
-                {
-                    if let Some(r) = ds.status.get_mut(&selected_key) {
-                        ui_entity_form(ui, "Status", &ds.status_schema, r);
-                        ui.add_space(8.0);
+                ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+                    egui::Grid::new("backup_list").striped(true).show(ui, |ui| {
+                        for e in entries {
+                            ui.label(e.display_time());
+                            ui.label(format!("{} bytes", e.len));
+                            let hint = "이 백업을 파일에 되살리고 다시 로드 (지금 파일도 백업으로 남음, 메모리 변경은 undo 가능)";
+                            if ui.button("↩ 복원").on_hover_text(hint).clicked() {
+                                restore = Some((t.spec.name.clone(), t.spec.path.clone(), e));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            });
+        self.show_backups = open;
+
+        let Some((name, path, entry)) = restore else { return };
+        if let Err(e) = backup::restore(&path, &entry, self.backup_keep) {
+            self.last_message = format!("❌ 백업 복원 실패: {e}");
+            return;
+        }
+        if let Some(disk) = self.load_disk_table(&name) {
+            let label = format!("백업 복원: {} ({})", name, entry.display_time());
+            self.replace_with(disk.clone(), disk, label);
+        }
+    }
+
+    // ===== 검증 결과: 클릭하면 해당 엔티티/셀로 이동 =====
+    fn ui_diagnostics(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_diagnostics;
+        let mut save = false;
+        egui::Window::new("🩺 검증 결과")
+            .open(&mut open)
+            .default_width(460.0)
+            .show(ctx, |ui| {
+                if ui.button("🔄 다시 검사").clicked() {
+                    if let Some(ds) = &self.ds {
+                        self.diagnostics = validate(ds);
                     }
                 }
-                {
-                    if let Some(r) = ds.attack.get_mut(&selected_key) {
-                        ui_entity_form(ui, "Attack", &ds.attack_schema, r);
+                let errors = self.diagnostics.iter().filter(|d| d.severity == Severity::Error).count();
+                let warnings = self.diagnostics.len() - errors;
+                if self.diagnostics.is_empty() {
+                    ui.label("✅ 문제가 없습니다.");
+                } else {
+                    ui.label(format!("오류 {}건, 경고 {}건", errors, warnings));
+                }
+                ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for d in &self.diagnostics {
+                        if ui.link(d.to_string()).clicked() && !d.key.is_empty() {
+                            self.selected_key = self.ds.as_ref().map(|ds| ds.entity_key(&d.table, &d.key).to_string());
+                            self.focus_cell = Some((d.table.clone(), d.column.clone()));
+                            self.scroll_to_focus = true;
+                            self.view_mode = ViewMode::Form;
+                        }
+                    }
+                });
+                if self.confirm_save {
+                    ui.separator();
+                    if errors > 0 {
+                        ui.label(format!("오류 {}건이 있습니다. 그래도 저장할까요?", errors));
+                    } else {
+                        ui.label("파일 인코딩으로 쓸 수 없는 글자는 '?'로 저장됩니다. 그래도 저장할까요?");
                     }
+                    ui.horizontal(|ui| {
+                        if ui.button("⚠️ 무시하고 저장").clicked() {
+                            save = true;
+                        }
+                        if ui.button("취소").clicked() {
+                            self.confirm_save = false;
+                        }
+                    });
                 }
-                // New Data file GuideLine Step 13:
-                {
-                    if let Some(r) = ds.skill.get_mut(&selected_key) {
-                        ui.add_space(8.0);
-                        ui_entity_form(ui, "Skill", &ds.skill_schema, r);
+            });
+        self.show_diagnostics = open;
+        if !open {
+            self.confirm_save = false;
+        }
+        if save {
+            self.save_now();
+        }
+    }
+
+    // ===== 키 문제: 빈 키/중복 키 행을 새 키로 옮기거나, 같은 키 행에 합치거나, 삭제 =====
+    fn ui_key_conflicts(&mut self, ctx: &egui::Context) {
+        enum Action {
+            Renumber(usize, String),
+            RenumberAll,
+            Merge(usize, MergePrefer),
+            Delete(usize),
+        }
+        let mut open = self.show_key_conflicts;
+        let mut action: Option<(String, Action)> = None;
+        egui::Window::new("🔑 키 문제")
+            .open(&mut open)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                let Some(ds) = &self.ds else {
+                    ui.label("먼저 데이터를 로드하세요.");
+                    return;
+                };
+                if ds.tables.iter().all(|t| t.conflicts.is_empty()) {
+                    ui.label("✅ 키가 비었거나 겹치는 행이 없습니다.");
+                    return;
+                }
+                ui.weak("해결하지 않은 행은 저장할 때 파일에 그대로 남습니다.");
+                let suggestion = ds.next_free_key();
+                ScrollArea::vertical().max_height(420.0).show(ui, |ui| {
+                    for t in ds.tables.iter().filter(|t| !t.conflicts.is_empty()) {
+                        ui.horizontal(|ui| {
+                            ui.strong(format!("{} ({}개)", t.spec.title, t.conflicts.len()));
+                            if ui.button("🔢 모두 새 키로").clicked() {
+                                action = Some((t.spec.name.clone(), Action::RenumberAll));
+                            }
+                        });
+                        egui::Grid::new(("key_conflicts", &t.spec.name)).striped(true).show(ui, |ui| {
+                            for (i, c) in t.conflicts.iter().enumerate() {
+                                let preview: Vec<&str> = t
+                                    .schema
+                                    .columns
+                                    .iter()
+                                    .filter(|col| !t.schema.is_key_column(&col.key))
+                                    .filter_map(|col| c.row.get(&col.key))
+                                    .take(4)
+                                    .collect();
+                                ui.label(c.to_string()).on_hover_text(preview.join(" | "));
+                                let input = self
+                                    .conflict_keys
+                                    .entry((t.spec.name.clone(), c.line))
+                                    .or_insert_with(|| {
+                                        if !t.is_child() {
+                                            return suggestion.clone();
+                                        }
+                                        // 자식 테이블: 같은 엔티티 안의 다음 번호 ("1 / 3")
+                                        let parent = t.schema.parent_of(&c.row.key, &c.row);
+                                        children::copy_row(t, &c.row, parent)
+                                            .map(|r| display_key(&r.key).into_owned())
+                                            .unwrap_or_default()
+                                    });
+                                ui.add(egui::TextEdit::singleline(input).desired_width(80.0));
+                                if ui.button("🔢 새 키로").clicked() {
+                                    action = Some((t.spec.name.clone(), Action::Renumber(i, input.clone())));
+                                }
+                                let mergeable = c.kind == ConflictKind::Duplicate && t.rows.contains_key(&c.row.key);
+                                ui.add_enabled_ui(mergeable, |ui| {
+                                    ui.menu_button("⤵ 합치기", |ui| {
+                                        if ui.button("다른 값은 먼저 나온 행 것으로").clicked() {
+                                            action = Some((t.spec.name.clone(), Action::Merge(i, MergePrefer::Existing)));
+                                            ui.close_menu();
+                                        }
+                                        if ui.button("다른 값은 이 행 것으로").clicked() {
+                                            action = Some((t.spec.name.clone(), Action::Merge(i, MergePrefer::Duplicate)));
+                                            ui.close_menu();
+                                        }
+                                    });
+                                });
+                                if ui.button("🗑 삭제").clicked() {
+                                    action = Some((t.spec.name.clone(), Action::Delete(i)));
+                                }
+                                ui.end_row();
+                            }
+                        });
+                        ui.separator();
                     }
+                });
+            });
+        self.show_key_conflicts = open;
+
+        let Some((name, action)) = action else { return };
+        let Some(ds) = self.ds.as_mut() else { return };
+        let Some(before) = ds.table(&name).cloned() else { return };
+        let result = match action {
+            Action::Renumber(i, key) => {
+                let line = before.conflicts[i].line;
+                ds.table_mut(&name)
+                    .map_or(Ok(()), |t| key_conflicts::renumber(t, i, &key))
+                    .map(|_| format!("{} {}번째 줄 → 키 {}", name, line, key.trim()))
+            }
+            Action::RenumberAll => key_conflicts::renumber_all(ds, &name).map(|keys| {
+                let keys: Vec<_> = keys.iter().map(|k| display_key(k)).collect();
+                let left = ds.table(&name).map_or(0, |t| t.conflicts.len());
+                let left = if left > 0 { format!(", 부모 값이 빈 {}개는 그대로", left) } else { String::new() };
+                format!("{}: {}개 행에 새 키 ({}){}", name, keys.len(), keys.join(", "), left)
+            }),
+            Action::Merge(i, prefer) => {
+                let c = &before.conflicts[i];
+                let label = format!("{} {}번째 줄을 키 {}에 합침", name, c.line, display_key(&c.row.key));
+                ds.table_mut(&name)
+                    .map_or(Ok(0), |t| key_conflicts::merge(t, i, prefer))
+                    .map(|n| format!("{} (바뀐 칸 {}개)", label, n))
+            }
+            Action::Delete(i) => {
+                let line = before.conflicts[i].line;
+                ds.table_mut(&name)
+                    .map_or(Ok(()), |t| key_conflicts::delete(t, i))
+                    .map(|_| format!("{} {}번째 줄 삭제", name, line))
+            }
+        };
+        // 실패해도 테이블이 바뀌었으면 되돌릴 수 있게 기록한다
+        let changed = result.is_ok()
+            || ds.table(&name).is_some_and(|t| t.order != before.order || t.conflicts.len() != before.conflicts.len());
+        if changed {
+            let label = result.as_ref().map_or_else(|_| format!("{} 일부 해결", name), String::clone);
+            let after = ds.table(&name).cloned().expect("table exists");
+            computed::recompute(ds);
+            self.diagnostics = validate(ds);
+            self.history.push(Command::tables(format!("키 문제: {}", label), vec![TableEdit { before, after }]));
+        }
+        self.last_message = match result {
+            Ok(label) => format!("🔑 {}", label),
+            Err(e) => format!("❌ {e}"),
+        };
+    }
+
+    // ===== 스키마 편집 창: dtype/라벨/순서 수정 후 사이드카로 저장 =====
+    fn ui_schema_editor(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_schema_editor;
+        let mut op: Option<ColumnOp> = None;
+        let mut start_rename = None;
+        let mut cancel_rename = false;
+        let mut op_table = String::new();
+        let mut formula_changed = false;
+        let mut dtype_changed = false;
+        egui::Window::new("🧬 스키마 편집")
+            .open(&mut open)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let Some(ds) = self.ds.as_mut() else {
+                    ui.label("먼저 로드하세요.");
+                    return;
+                };
+                if ds.tables.is_empty() {
+                    return;
+                }
+                self.schema_table = self.schema_table.min(ds.tables.len() - 1);
+
+                egui::ComboBox::from_id_source("schema_table")
+                    .selected_text(&ds.tables[self.schema_table].spec.title)
+                    .show_ui(ui, |ui| {
+                        for (i, t) in ds.tables.iter().enumerate() {
+                            ui.selectable_value(&mut self.schema_table, i, &t.spec.title);
+                        }
+                    });
+
+                let table = &mut ds.tables[self.schema_table];
+                op_table = table.spec.name.clone();
+                ui.label(format!(
+                    "사이드카: {}",
+                    storage::schema_sidecar_path(&table.spec.path).display()
+                ));
+                ui.horizontal(|ui| {
+                    ui.label(format!("파일 형식: {}", table.schema.dialect.describe()));
+                    // 저장할 때 쓸 인코딩 (바꾸면 다음 저장에서 파일 전체가 그 인코딩으로)
+                    egui::ComboBox::from_id_source("schema_encoding")
+                        .selected_text(table.schema.dialect.encoding.label())
+                        .show_ui(ui, |ui| {
+                            for e in TextEncoding::ALL {
+                                ui.selectable_value(&mut table.schema.dialect.encoding, e, e.label());
+                            }
+                        });
+                });
+                ui.separator();
+
+                let ncols = table.schema.columns.len();
+                let key_columns: Vec<String> = table.schema.key_columns().map(str::to_string).collect();
+                let parent_column = table.schema.parent_column.clone();
+                egui::Grid::new("schema_grid")
+                    .num_columns(5)
+                    .spacing([8.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("헤더");
+                        ui.strong("표시 라벨");
+                        ui.strong("타입");
+                        ui.strong("규칙");
+                        ui.strong("순서");
+                        ui.end_row();
+                        for (i, col) in table.schema.columns.iter_mut().enumerate() {
+                            ui.horizontal(|ui| match &mut self.rename_col {
+                                Some((ri, buf)) if *ri == i => {
+                                    ui.add(egui::TextEdit::singleline(buf).desired_width(110.0));
+                                    if ui.small_button("✔").clicked() {
+                                        op = Some(ColumnOp::Rename { old: col.key.clone(), new: buf.clone() });
+                                    }
+                                    if ui.small_button("✖").clicked() {
+                                        cancel_rename = true;
+                                    }
+                                }
+                                _ => {
+                                    if key_columns.contains(&col.key) {
+                                        ui.label(RichText::new(&col.key).strong()).on_hover_text("키 컬럼");
+                                    } else if parent_column.as_ref() == Some(&col.key) {
+                                        ui.label(RichText::new(&col.key).strong()).on_hover_text("부모 컬럼 (엔티티 키)");
+                                    } else {
+                                        ui.label(&col.key);
+                                    }
+                                    if ui.small_button("✏").on_hover_text("헤더 이름 변경").clicked() {
+                                        start_rename = Some((i, col.key.clone()));
+                                    }
+                                    let removable = !key_columns.contains(&col.key) && parent_column.as_ref() != Some(&col.key);
+                                    if ui.add_enabled(removable, egui::Button::new("🗑").small()).clicked() {
+                                        op = Some(ColumnOp::Remove { name: col.key.clone() });
+                                    }
+                                }
+                            });
+                            ui.text_edit_singleline(&mut col.label);
+                            let dtype_before = col.dtype.clone();
+                            ui.vertical(|ui| {
+                                egui::ComboBox::from_id_source(("schema_dtype", i))
+                                    .selected_text(col.dtype.name())
+                                    .show_ui(ui, |ui| {
+                                        for dt in DataType::templates() {
+                                            let same = col.dtype.same_kind(&dt);
+                                            if ui.selectable_label(same, dt.name()).clicked() && !same {
+                                                col.dtype = dt;
+                                            }
+                                        }
+                                    });
+                                ui_dtype_params(ui, egui::Id::new(("schema_col", &op_table, &col.key)), &mut col.dtype);
+                                if let Some(formula) = &mut col.formula {
+                                    let edit = egui::TextEdit::singleline(formula)
+                                        .font(egui::TextStyle::Monospace)
+                                        .desired_width(160.0);
+                                    if ui.horizontal(|ui| ui.label("ƒ =") | ui.add(edit)).inner.changed() {
+                                        formula_changed = true;
+                                    }
+                                }
+                            });
+                            dtype_changed |= col.dtype != dtype_before;
+                            let rules_label = if col.rules.is_empty() { "없음".to_string() } else { "✔ 설정됨".to_string() };
+                            let rules_id = egui::Id::new(("schema_col", &op_table, &col.key));
+                            ui.menu_button(rules_label, |ui| ui_column_rules(ui, rules_id, &mut col.rules));
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(i > 0, egui::Button::new("▲")).clicked() {
+                                    op = Some(ColumnOp::Move { from: i, to: i - 1 });
+                                }
+                                if ui.add_enabled(i + 1 < ncols, egui::Button::new("▼")).clicked() {
+                                    op = Some(ColumnOp::Move { from: i, to: i + 1 });
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                if start_rename.is_some() {
+                    self.rename_col = start_rename;
+                }
+                if cancel_rename {
+                    self.rename_col = None;
+                }
+
+                ui.separator();
+                ui.label("컬럼 추가");
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.new_col_name).hint_text("헤더명").desired_width(110.0));
+                    egui::ComboBox::from_id_source("new_col_dtype")
+                        .selected_text(self.new_col_dtype.name())
+                        .show_ui(ui, |ui| {
+                            for dt in DataType::templates() {
+                                let same = self.new_col_dtype.same_kind(&dt);
+                                if ui.selectable_label(same, dt.name()).clicked() && !same {
+                                    self.new_col_default = dt.default_value();
+                                    self.new_col_dtype = dt;
+                                }
+                            }
+                        });
+                    ui.add(egui::TextEdit::singleline(&mut self.new_col_default).hint_text("기본값").desired_width(80.0));
+                    if ui.button("➕ 추가").clicked() {
+                        op = Some(ColumnOp::Add {
+                            name: self.new_col_name.clone(),
+                            dtype: self.new_col_dtype.clone(),
+                            default: self.new_col_default.clone(),
+                            formula: self.new_col_formula.clone(),
+                        });
+                    }
+                });
+                ui_dtype_params(ui, egui::Id::new("new_col_dtype"), &mut self.new_col_dtype);
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.new_col_formula)
+                        .hint_text("계산식 (선택, 예: Health * (1 + DefensePower / 100))")
+                        .desired_width(f32::INFINITY)
+                        .font(egui::TextStyle::Monospace),
+                );
+
+                ui.separator();
+                if ui.button("💾 사이드카 저장").clicked() {
+                    self.last_message = match table.save_schema() {
+                        Ok(_) => format!("💾 스키마 저장: {}", table.spec.title),
+                        Err(e) => format!("❌ 스키마 저장 실패: {e}"),
+                    };
                 }
             });
+        self.show_schema_editor = open;
+        if formula_changed {
+            self.computed_rev = None;
+        }
+        if dtype_changed {
+            self.refs_rev = None; // 참조 대상이 바뀌었을 수 있다
+        }
+        if let Some(op) = op {
+            self.apply_column_op(&op_table, op);
+        }
+    }
+
+    /// 컬럼 구조 변경을 적용하고, 바뀐 테이블의 전후 스냅샷을 한 단계로 기록
+    fn apply_column_op(&mut self, table: &str, op: ColumnOp) {
+        let Some(ds) = self.ds.as_mut() else { return };
+        let before = ds.tables.clone();
+        let (label, res) = match op {
+            ColumnOp::Add { name, dtype, default, formula } => (
+                format!("컬럼 추가 {}.{}", table, name),
+                ds.table_mut(table)
+                    .map_or(Ok(()), |t| {
+                        if formula.trim().is_empty() {
+                            t.add_column(&name, dtype, &default)
+                        } else {
+                            t.add_computed_column(&name, dtype, &formula)
+                        }
+                    })
+                    .map(|_| vec![table.to_string()]),
+            ),
+            ColumnOp::Rename { old, new } => (
+                format!("컬럼 이름 변경 {}.{} → {}", table, old, new),
+                ds.rename_column(table, &old, &new),
+            ),
+            ColumnOp::Move { from, to } => {
+                if let Some(t) = ds.table_mut(table) {
+                    t.move_column(from, to);
+                }
+                (format!("컬럼 이동 {} {}→{}", table, from, to), Ok(vec![table.to_string()]))
+            }
+            ColumnOp::Remove { name } => (
+                format!("컬럼 삭제 {}.{}", table, name),
+                ds.table_mut(table)
+                    .map_or(Ok(()), |t| t.remove_column(&name))
+                    .map(|_| vec![table.to_string()]),
+            ),
+        };
+        match res {
+            Ok(changed) => {
+                let edits = before
+                    .into_iter()
+                    .filter(|t| changed.contains(&t.spec.name))
+                    .filter_map(|b| {
+                        let after = ds.table(&b.spec.name)?.clone();
+                        Some(TableEdit { before: b, after })
+                    })
+                    .collect();
+                self.history.push(Command::tables(label.clone(), edits));
+                self.sync_specs(); // 키 컬럼 이름이 바뀌었으면 키 힌트도
+                self.rename_col = None;
+                self.new_col_name.clear();
+                self.last_message = format!("🟢 {} (저장 시 파일에 반영)", label);
+            }
+            Err(e) => self.last_message = format!("❌ {e}"),
+        }
+    }
 
-            ui.add_space(8.0);
-            if ui.button("✅ 변경사항 적용(메모리)").clicked() {
-                // 편집 버퍼를 실제 ds 맵에 반영
-                if let Some(r) = info {
-                    ds.info.insert(selected_key.clone(), r);
+    // ===== 일괄 편집: 대상 컬럼 + 행 선택(필터 식/다중 선택) + 식, 미리보기 후 한 단계로 적용 =====
+    fn ui_bulk_edit(&mut self, ctx: &egui::Context) {
+        if !self.show_bulk_edit {
+            return;
+        }
+        let mut open = true;
+        let mut apply = None;
+        egui::Window::new("🧮 일괄 편집")
+            .open(&mut open)
+            .default_width(520.0)
+            .show(ctx, |ui| {
+                let Some(ds) = &self.ds else {
+                    ui.label("먼저 로드하세요.");
+                    return;
+                };
+                if ds.tables.is_empty() {
+                    return;
                 }
-                if let Some(r) = status {
-                    ds.status.insert(selected_key.clone(), r);
+                self.bulk_table = self.bulk_table.min(ds.tables.len() - 1);
+                let table = &ds.tables[self.bulk_table];
+
+                ui.horizontal(|ui| {
+                    ui.label("대상:");
+                    egui::ComboBox::from_id_source("bulk_table")
+                        .selected_text(&table.spec.title)
+                        .show_ui(ui, |ui| {
+                            for (i, t) in ds.tables.iter().enumerate() {
+                                ui.selectable_value(&mut self.bulk_table, i, &t.spec.title);
+                            }
+                        });
+                    // 키 컬럼(행 식별자)과 계산 컬럼(읽기 전용)은 편집 대상에서 뺀다
+                    let columns: Vec<&str> = table
+                        .schema
+                        .columns
+                        .iter()
+                        .filter(|c| !table.schema.is_key_column(&c.key) && !c.is_computed())
+                        .map(|c| c.key.as_str())
+                        .collect();
+                    if !columns.contains(&self.bulk_column.as_str()) {
+                        self.bulk_column = columns.first().map(|c| c.to_string()).unwrap_or_default();
+                    }
+                    egui::ComboBox::from_id_source("bulk_column")
+                        .selected_text(&self.bulk_column)
+                        .show_ui(ui, |ui| {
+                            for c in columns {
+                                ui.selectable_value(&mut self.bulk_column, c.to_string(), c);
+                            }
+                        });
+                });
+
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.bulk_use_selection, false, "필터 식");
+                    ui.radio_value(
+                        &mut self.bulk_use_selection,
+                        true,
+                        format!("목록에서 선택한 키 ({}개)", self.multi_keys.len()),
+                    );
+                });
+                let all_keys = Self::gather_sorted_unique_keys(ds);
+                let keys = if self.bulk_use_selection {
+                    all_keys.into_iter().filter(|k| self.multi_keys.contains(k)).collect()
+                } else {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.bulk_filter)
+                            .hint_text("비우면 전체 (예: key() >= 2000 && key() <= 2999)")
+                            .desired_width(f32::INFINITY)
+                            .font(egui::TextStyle::Monospace),
+                    );
+                    match Self::parse_filter(ds, &self.bulk_filter) {
+                        None => all_keys,
+                        Some(Ok(expr)) => query::filter_keys(ds, &expr, &all_keys).keys,
+                        Some(Err(e)) => {
+                            ui.colored_label(ui.visuals().error_fg_color, RichText::new(e.render(&self.bulk_filter)).monospace());
+                            return;
+                        }
+                    }
+                };
+
+                ui.label("새 값 식 (같은 엔티티의 컬럼 사용 가능, 예: AttackPower * 1.1):");
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.bulk_expr)
+                        .desired_width(f32::INFINITY)
+                        .font(egui::TextStyle::Monospace),
+                );
+                if self.bulk_expr.trim().is_empty() || self.bulk_column.is_empty() {
+                    return;
+                }
+                let expr = match query::parse(&self.bulk_expr).and_then(|e| e.check_single_row(ds, Some(&table.spec.name)).map(|_| e)) {
+                    Ok(e) => e,
+                    Err(e) => {
+                        ui.colored_label(ui.visuals().error_fg_color, RichText::new(e.render(&self.bulk_expr)).monospace());
+                        return;
+                    }
+                };
+
+                let changes = bulk::preview(ds, &table.spec.name, &self.bulk_column, &expr, &keys);
+                let changed = changes.iter().filter(|c| c.changed()).count();
+                let errors = changes.iter().filter(|c| c.new.is_err()).count();
+                ui.separator();
+                ui.label(format!("대상 {}행 · 변경 {}행 · 오류 {}행", changes.len(), changed, errors));
+
+                ScrollArea::vertical().max_height(320.0).show_rows(ui, 18.0, changes.len(), |ui, range| {
+                    egui::Grid::new("bulk_preview").striped(true).show(ui, |ui| {
+                        for c in &changes[range] {
+                            ui.label(&c.key);
+                            ui.label(&c.old);
+                            ui.label("→");
+                            match &c.new {
+                                Ok(v) if c.changed() => ui.label(RichText::new(v).strong()),
+                                Ok(v) => ui.weak(v),
+                                Err(e) => ui.colored_label(ui.visuals().error_fg_color, e),
+                            };
+                            ui.end_row();
+                        }
+                    });
+                });
+
+                let text = if errors > 0 {
+                    format!("✅ 적용 ({}행, 오류 행 제외)", changed)
+                } else {
+                    format!("✅ 적용 ({}행)", changed)
+                };
+                if ui.add_enabled(changed > 0, egui::Button::new(text)).clicked() {
+                    apply = Some((table.spec.name.clone(), changes));
+                }
+            });
+        self.show_bulk_edit = open;
+
+        if let (Some((table, changes)), Some(ds)) = (apply, self.ds.as_mut()) {
+            let edits = bulk::apply(ds, &table, &self.bulk_column, &changes);
+            let label = format!("일괄 편집 {}.{} ({}행)", table, self.bulk_column, edits.len());
+            self.history.push(Command::edits(label.clone(), edits));
+            self.last_message = format!("🟢 {} (저장은 따로)", label);
+        }
+    }
+
+    // ===== 중앙 패널: 폼/그리드 전환 =====
+    fn ui_main_view(&mut self, ui: &mut egui::Ui) {
+        if self.ds.is_none() {
+            ui.heading("📝 Main View");
+            ui.label("좌측에서 파일 경로 설정 후 '로드'를 클릭하세요.");
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.view_mode, ViewMode::Form, "📝 폼");
+            ui.selectable_value(&mut self.view_mode, ViewMode::Grid, "▦ 그리드");
+        });
+        ui.separator();
+        match self.view_mode {
+            ViewMode::Form => self.ui_entity_detail(ui),
+            ViewMode::Grid => self.ui_grid(ui),
+        }
+    }
+
+    // ===== 그리드 뷰: 테이블 하나의 모든 행 =====
+    fn ui_grid(&mut self, ui: &mut egui::Ui) {
+        let Some(ds) = self.ds.as_mut() else { return };
+        if ds.tables.is_empty() {
+            return;
+        }
+        self.grid_table = self.grid_table.min(ds.tables.len() - 1);
+        egui::ComboBox::from_id_source("grid_table")
+            .selected_text(&ds.tables[self.grid_table].spec.title)
+            .show_ui(ui, |ui| {
+                for (i, t) in ds.tables.iter().enumerate() {
+                    ui.selectable_value(&mut self.grid_table, i, &t.spec.title);
                 }
-                if let Some(r) = attack {
-                    ds.attack.insert(selected_key.clone(), r);
+            });
+
+        let mut env = CellEnv {
+            refs: &self.refs,
+            jump_to: None,
+            focus: None,
+            scroll_to_focus: false,
+            edits: Vec::new(),
+            compact: true,
+            pending: &self.pending,
+        };
+        let table = &mut ds.tables[self.grid_table];
+        let has_computed = table.schema.columns.iter().any(|c| c.is_computed());
+        if has_computed && ui.button("⤓ 계산 컬럼 포함 내보내기").clicked() {
+            let path = computed_export_path(&table.spec.path);
+            self.last_message = match table.export_with_computed(&path) {
+                Ok(_) => format!("⤓ 내보냄: {path}"),
+                Err(e) => format!("❌ 내보내기 실패: {e}"),
+            };
+        }
+        let state = self.grid_states.entry(table.spec.name.clone()).or_default();
+        let before = self.selected_key.clone();
+        ui_table_grid(ui, &mut env, table, state, &mut self.selected_key);
+
+        for e in env.edits {
+            self.history.record(e);
+        }
+        if let Some(k) = env.jump_to {
+            self.selected_key = Some(k);
+        }
+        if self.selected_key != before {
+            self.focus_cell = None;
+        }
+    }
+
+    /// 마지막 색인 이후 편집/undo가 있었거나 데이터를 다시 읽었으면 참조 색인을 다시 만든다
+    fn refresh_refs(&mut self) {
+        let Some(ds) = &self.ds else { return };
+        let rev = self.history.revision();
+        if self.refs_rev == Some(rev) {
+            return;
+        }
+        self.refs_rev = Some(rev);
+        self.refs = RefIndex::build(ds);
+    }
+
+    /// 마지막 갱신 이후 편집/undo가 있었으면 계산 컬럼 값을 다시 계산
+    fn refresh_computed(&mut self, ctx: &egui::Context) {
+        let Some(ds) = self.ds.as_mut() else { return };
+        let rev = self.history.revision();
+        if self.computed_rev == Some(rev) {
+            return;
+        }
+        self.computed_rev = Some(rev);
+        if computed::recompute(ds) {
+            ctx.request_repaint();
+        }
+    }
+
+    // ===== 우측 상세뷰(동적) =====
+    fn ui_entity_detail(&mut self, ui: &mut egui::Ui) {
+        let Some(ds) = self.ds.as_mut() else { return };
+
+        if let Some(selected_key) = self.selected_key.clone() {
+            ui.heading(format!("🔧 엔티티 편집: {}", &selected_key));
+            ui.separator();
+
+            let mut env = CellEnv {
+                refs: &self.refs,
+                jump_to: None,
+                focus: self.focus_cell.as_ref(),
+                scroll_to_focus: self.scroll_to_focus,
+                edits: Vec::new(),
+                compact: false,
+                pending: &self.pending,
+            };
+            let mut child_op: Option<(String, ChildOp)> = None;
+            ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                for t in &mut ds.tables {
+                    if t.is_child() {
+                        if let Some(op) = ui_child_table(ui, &mut env, t, &selected_key) {
+                            child_op = Some((t.spec.name.clone(), op));
+                        }
+                        ui.add_space(8.0);
+                    } else if let Some(r) = t.rows.get_mut(&selected_key) {
+                        ui_entity_form(ui, &mut env, &t.spec.name, &t.spec.title, &t.schema, r);
+                        ui.add_space(8.0);
+                    }
                 }
-                // New Data file GuideLine Step 14:
-                if let Some(r) = skill {
-                    ds.skill.insert(selected_key.clone(), r);
+            });
+            for e in env.edits {
+                self.history.record(e);
+            }
+            if let Some((name, op)) = child_op {
+                if let Some(t) = ds.table_mut(&name) {
+                    let before = t.clone();
+                    let done = match op {
+                        ChildOp::Add => children::add_child(t, &selected_key).map(|_| "행 추가"),
+                        ChildOp::Remove(key) => children::remove_child(t, &key).map(|_| "행 삭제"),
+                        ChildOp::Move { key, up } => {
+                            children::move_child(t, &key, up); // 끝 행의 버튼은 꺼져 있다
+                            Ok("행 순서")
+                        }
+                        ChildOp::SetKey { key, column, value } => {
+                            children::set_key_cell(t, &key, &column, &value).map(|_| "키 변경")
+                        }
+                    };
+                    match done {
+                        Ok(label) => {
+                            let label = format!("{}: {}", t.spec.title, label);
+                            self.history.push(Command::tables(label, vec![TableEdit { before, after: t.clone() }]));
+                        }
+                        Err(e) => self.last_message = format!("❌ {e}"),
+                    }
                 }
-                self.last_message = "🟢 메모리에 적용됨 (저장은 따로)".to_string();
+            }
+            let scrolled = !env.scroll_to_focus;
+            if let Some(k) = env.jump_to {
+                self.selected_key = Some(k);
+                self.focus_cell = None;
+            }
+            if scrolled {
+                self.scroll_to_focus = false;
             }
         } else {
             ui.heading("📝 Main View");
@@ -417,15 +2311,42 @@ impl EditorApp {
 
 impl App for EditorApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.handle_shortcuts(ctx);
+        self.poll_external(ctx);
+        self.refresh_pending();
+        self.refresh_refs();
+
         egui::SidePanel::left("left_panel")
             .resizable(true)
             .default_width(300.0)
             .show(ctx, |ui| self.ui_left_panel(ui));
 
-        egui::CentralPanel::default().show(ctx, |ui| self.ui_entity_detail(ui));
+        egui::CentralPanel::default().show(ctx, |ui| self.ui_main_view(ui));
+
+        self.ui_schema_editor(ctx);
+        self.ui_diagnostics(ctx);
+        self.ui_key_conflicts(ctx);
+        self.ui_history(ctx);
+        self.ui_bulk_edit(ctx);
+        self.ui_pending(ctx);
+        self.ui_external(ctx);
+        self.ui_backups(ctx);
+        self.ui_export(ctx);
+        self.refresh_computed(ctx);
     }
 }
 
+/// 계산 컬럼 포함 내보내기 경로: 원본 옆 `<stem>.computed.<ext>`
+fn computed_export_path(path: &str) -> String {
+    let p = std::path::Path::new(path);
+    let stem = p.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match p.extension() {
+        Some(ext) => format!("{}.computed.{}", stem, ext.to_string_lossy()),
+        None => format!("{}.computed", stem),
+    };
+    p.with_file_name(name).to_string_lossy().to_string()
+}
+
 fn main() -> Result<(), eframe::Error> {
     let options = eframe::NativeOptions::default();
     eframe::run_native(